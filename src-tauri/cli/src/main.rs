@@ -0,0 +1,517 @@
+//! 干了么命令行工具：与桌面端共用同一个数据库
+
+mod mcp;
+
+use clap::{Args, Parser, Subcommand};
+use ganle_core::db::connection;
+use ganle_core::models::maintenance::MaintenanceReport;
+use ganle_core::models::stats::{Distribution, StatsPeriod};
+use ganle_core::models::task::{NewTask, Task, TaskStatus};
+use ganle_core::models::time_entry::TimeTotal;
+use ganle_core::services::nlp;
+use ganle_core::services::task_service::{normalize_due, normalize_start};
+use ganle_core::services::workspace_service::WorkspaceRegistry;
+use ganle_core::TaskStore;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// 与 tauri.conf.json 中的 identifier 保持一致
+const APP_IDENTIFIER: &str = "com.todolist.pro";
+
+#[derive(Parser)]
+#[command(name = "ganle", version, about = "干了么 - 命令行待办")]
+struct Cli {
+    /// 以 JSON 格式输出
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// 数据库文件路径（默认与桌面端相同，也可用 GANLE_DB 环境变量指定）
+    #[arg(long, global = true, env = "GANLE_DB")]
+    db: Option<PathBuf>,
+
+    /// 工作区名称（默认为桌面端当前使用的工作区）
+    #[arg(long, global = true, env = "GANLE_WORKSPACE", conflicts_with = "db")]
+    workspace: Option<String>,
+
+    /// 加密数据库的密码（也可用 GANLE_DB_KEY 环境变量指定）
+    #[arg(long, global = true, env = "GANLE_DB_KEY", hide_env_values = true)]
+    key: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 添加任务
+    Add(AddArgs),
+    /// 列出任务
+    Ls(LsArgs),
+    /// 完成任务
+    Done {
+        /// 任务ID（可只写前几位）
+        id: String,
+    },
+    /// 开始为任务计时（会结束正在进行的计时）
+    Start {
+        /// 任务ID（可只写前几位）
+        id: String,
+    },
+    /// 结束正在进行的计时
+    Stop,
+    /// 查看正在进行的计时，以及按任务、项目和标签汇总的预估与实际用时
+    Time,
+    /// 完成统计：最近各周期完成的任务数、完成率、连续完成天数和各分组分布
+    Stats {
+        /// 统计周期：day / week / month
+        #[arg(long, default_value = "day")]
+        period: String,
+        /// 列出最近多少个周期
+        #[arg(long, default_value_t = 7)]
+        count: u32,
+    },
+    /// 检查并修复数据库（完整性、外键、孤立数据），然后整理文件
+    Maintain,
+    /// 删除示例数据（示例任务、项目和标签）
+    RemoveSample,
+    /// 以 MCP 服务模式运行（stdio），供 AI 助手读取和管理任务
+    Mcp,
+}
+
+#[derive(Args)]
+struct AddArgs {
+    /// 任务标题，可直接写“明天下午3点 开周会 #会议 ~工作 !high”
+    title: String,
+    /// 截止日期：today / tomorrow / 今天 / 明天 / 后天 / YYYY-MM-DD [HH:MM]
+    #[arg(long)]
+    due: Option<String>,
+    /// 开始日期（之前不出现在今日待办等列表中）：today / tomorrow / 明天 / YYYY-MM-DD
+    #[arg(long)]
+    start: Option<String>,
+    /// 预估用时（分钟）
+    #[arg(long)]
+    estimate: Option<u32>,
+    /// 标签名，可重复指定，不存在时自动创建
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// 项目名，不存在时自动创建
+    #[arg(long)]
+    project: Option<String>,
+    /// 优先级：high / medium / low
+    #[arg(long)]
+    priority: Option<String>,
+    /// 重复：none / daily / weekly / monthly（默认取标题中的“每周三”等）
+    #[arg(long)]
+    repeat: Option<String>,
+}
+
+#[derive(Args)]
+struct LsArgs {
+    /// 今日待办
+    #[arg(long, group = "view")]
+    today: bool,
+    /// 近 7 天
+    #[arg(long, group = "view")]
+    week: bool,
+    /// 已完成
+    #[arg(long, group = "view")]
+    completed: bool,
+    /// 收藏
+    #[arg(long, group = "view")]
+    favorite: bool,
+    /// 已计划（开始日期未到）
+    #[arg(long, group = "view")]
+    scheduled: bool,
+    /// 按项目名筛选
+    #[arg(long, group = "view")]
+    project: Option<String>,
+    /// 按标签名筛选
+    #[arg(long, group = "view")]
+    tag: Option<String>,
+    /// 搜索标题和描述
+    #[arg(long, default_value = "")]
+    search: String,
+    /// 同时显示已完成任务
+    #[arg(long, short)]
+    all: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("错误: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), String> {
+    let db_path = match &cli.db {
+        Some(path) => path.clone(),
+        None => workspace_db_path(cli.workspace.as_deref())?,
+    };
+    if cli.key.is_none() && connection::is_encrypted(&db_path) {
+        return Err("数据库已加密，请用 --key 或 GANLE_DB_KEY 提供密码".to_string());
+    }
+    let store = TaskStore::open_with_key(&db_path, cli.key.as_deref())?;
+
+    match &cli.command {
+        Command::Add(args) => {
+            let task = add(&store, args)?;
+            if cli.json {
+                print_json(&task)?;
+            } else {
+                println!("已添加 {} {}", short_id(&task.id), task.title);
+            }
+        }
+        Command::Ls(args) => {
+            let tasks = list(&store, args)?;
+            if cli.json {
+                print_json(&tasks)?;
+            } else {
+                print_table(&store, &tasks)?;
+            }
+        }
+        Command::Done { id } => {
+            let id = resolve_task_id(&store, id)?;
+            let task = store.task(&id)?;
+            let task = if task.status == TaskStatus::Completed {
+                task
+            } else {
+                store.toggle_task(&id)?
+            };
+            if cli.json {
+                print_json(&task)?;
+            } else {
+                println!("已完成 {} {}", short_id(&task.id), task.title);
+            }
+        }
+        Command::Start { id } => {
+            let id = resolve_task_id(&store, id)?;
+            let entry = store.start_timer(&id)?;
+            if cli.json {
+                print_json(&entry)?;
+            } else {
+                println!("开始计时 {} {}", short_id(&id), store.task(&id)?.title);
+            }
+        }
+        Command::Stop => {
+            let entry = store.stop_timer()?;
+            if cli.json {
+                print_json(&entry)?;
+            } else {
+                match entry {
+                    Some(entry) => println!(
+                        "已结束计时 {} {}，本次 {}",
+                        short_id(&entry.task_id),
+                        store.task(&entry.task_id)?.title,
+                        format_duration(entry.seconds.unwrap_or(0))
+                    ),
+                    None => println!("没有正在进行的计时"),
+                }
+            }
+        }
+        Command::Time => {
+            let running = store.running_timer()?;
+            let totals = store.time_totals()?;
+            if cli.json {
+                print_json(&serde_json::json!({ "running": running, "totals": totals }))?;
+            } else {
+                if let Some(entry) = running {
+                    let title = store.task(&entry.task_id)?.title;
+                    println!("正在计时 {} {}（{} 开始）", short_id(&entry.task_id), title, entry.started_at);
+                }
+                print_time_totals("任务", &totals.tasks);
+                print_time_totals("项目", &totals.projects);
+                print_time_totals("标签", &totals.tags);
+            }
+        }
+        Command::Stats { period, count } => {
+            let period = match period.as_str() {
+                "day" => StatsPeriod::Day,
+                "week" => StatsPeriod::Week,
+                "month" => StatsPeriod::Month,
+                other => return Err(format!("无法识别的统计周期: {}", other)),
+            };
+            let series = store.completion_series(period, *count)?;
+            let summary = store.stats_summary()?;
+            if cli.json {
+                print_json(&serde_json::json!({ "series": series, "summary": summary }))?;
+            } else {
+                for point in &series {
+                    println!("{}  {}", point.start, point.completed);
+                }
+                println!(
+                    "\n完成 {}/{}（{:.0}%），连续 {} 天，最长 {} 天",
+                    summary.completed,
+                    summary.total,
+                    summary.completion_rate * 100.0,
+                    summary.current_streak,
+                    summary.longest_streak
+                );
+                if let Some(hours) = summary.average_lead_hours {
+                    println!("平均耗时 {:.1} 小时", hours);
+                }
+                println!("逾期 {} 个（{:.0}%）", summary.overdue, summary.overdue_rate * 100.0);
+                print_distribution("标签", &summary.by_tag);
+                print_distribution("项目", &summary.by_project);
+                print_distribution("优先级", &summary.by_priority);
+            }
+        }
+        Command::Maintain => {
+            let report = store.maintain()?;
+            if cli.json {
+                print_json(&report)?;
+            } else {
+                print_report(&report);
+            }
+        }
+        Command::RemoveSample => {
+            let removed = store.remove_sample_data()?;
+            if cli.json {
+                print_json(&removed)?;
+            } else {
+                println!("已删除 {} 个示例任务", removed);
+            }
+        }
+        Command::Mcp => mcp::serve(&store)?,
+    }
+    Ok(())
+}
+
+fn add(store: &TaskStore, args: &AddArgs) -> Result<Task, String> {
+    // 标题中的自然语言部分先解析，命令行参数优先
+    let tz = store.timezone();
+    let parsed = nlp::parse(&args.title, tz);
+    if parsed.title.is_empty() {
+        return Err("任务标题不能为空".to_string());
+    }
+    let due = match args.due.as_ref().or(parsed.due_date.as_ref()) {
+        Some(due) => Some(normalize_due(due, tz)?),
+        None => None,
+    };
+
+    let project_id = match args.project.as_ref().or(parsed.project.as_ref()) {
+        Some(name) => Some(store.find_or_create_project(name)?.id),
+        None => None,
+    };
+    let mut tag_ids = Vec::new();
+    for name in args.tags.iter().chain(&parsed.tags) {
+        let id = store.find_or_create_tag(name)?.id;
+        if !tag_ids.contains(&id) {
+            tag_ids.push(id);
+        }
+    }
+
+    let mut new = NewTask::new(parsed.title);
+    new.priority = args.priority.clone().or(parsed.priority).unwrap_or(new.priority);
+    new.project_id = project_id;
+    new.due = due;
+    new.start_date = args.start.as_deref().map(|s| normalize_start(s, tz)).transpose()?;
+    new.estimate_minutes = args.estimate;
+    new.repeat_type = args.repeat.clone().or(parsed.repeat_type).unwrap_or(new.repeat_type);
+    new.tag_ids = tag_ids;
+    store.create_task(&new)
+}
+
+fn list(store: &TaskStore, args: &LsArgs) -> Result<Vec<Task>, String> {
+    let (view_type, view_id) = if args.today {
+        ("smart", "today".to_string())
+    } else if args.week {
+        ("smart", "week".to_string())
+    } else if args.completed {
+        ("smart", "completed".to_string())
+    } else if args.favorite {
+        ("smart", "favorite".to_string())
+    } else if args.scheduled {
+        ("smart", "scheduled".to_string())
+    } else if let Some(name) = &args.project {
+        let project = store.projects()?
+            .into_iter()
+            .find(|p| &p.name == name)
+            .ok_or_else(|| format!("项目不存在: {}", name))?;
+        ("project", project.id)
+    } else if let Some(name) = &args.tag {
+        let tag = store.tags()?
+            .into_iter()
+            .find(|t| &t.name == name)
+            .ok_or_else(|| format!("标签不存在: {}", name))?;
+        ("tag", tag.id)
+    } else {
+        ("smart", "all".to_string())
+    };
+
+    let tasks = store.filtered_tasks(view_type, &view_id, &args.search)?;
+    Ok(tasks
+        .into_iter()
+        .filter(|t| args.all || args.completed || t.status != TaskStatus::Completed)
+        .collect())
+}
+
+/// 支持完整ID、去掉 task- 前缀的ID或唯一前缀
+fn resolve_task_id(store: &TaskStore, input: &str) -> Result<String, String> {
+    let tasks = store.all_tasks()?;
+    if let Some(task) = tasks.iter().find(|t| t.id == input) {
+        return Ok(task.id.clone());
+    }
+    let matches: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.id.starts_with(input) || t.id.trim_start_matches("task-").starts_with(input))
+        .collect();
+    match matches.as_slice() {
+        [task] => Ok(task.id.clone()),
+        [] => Err(format!("找不到任务: {}", input)),
+        _ => Err(format!("ID 前缀 {} 匹配到多个任务，请写得更长一些", input)),
+    }
+}
+
+/// 与桌面端相同的数据库位置（Tauri 的 app_data_dir 下的工作区）
+fn workspace_db_path(name: Option<&str>) -> Result<PathBuf, String> {
+    let dir = dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or("无法确定数据目录，请用 --db 指定数据库")?;
+    let registry = WorkspaceRegistry::load(&dir)?;
+    let workspace = match name {
+        Some(name) => registry.find_by_name(name)?,
+        None => registry.current(),
+    };
+    Ok(registry.path_of(workspace))
+}
+
+fn short_id(id: &str) -> &str {
+    let short = id.strip_prefix("task-").unwrap_or(id);
+    short.get(..8).unwrap_or(short)
+}
+
+/// 秒数显示为“1小时5分”“12分”
+fn format_duration(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}分", m),
+        (h, 0) => format!("{}小时", h),
+        (h, m) => format!("{}小时{}分", h, m),
+    }
+}
+
+fn print_time_totals(label: &str, totals: &[TimeTotal]) {
+    if totals.is_empty() {
+        return;
+    }
+    println!("\n按{}：", label);
+    for total in totals {
+        let estimate = if total.estimate_minutes > 0 {
+            format_duration(total.estimate_minutes * 60)
+        } else {
+            "-".to_string()
+        };
+        println!("  {}  预估 {}  实际 {}", total.name, estimate, format_duration(total.tracked_seconds));
+    }
+}
+
+fn print_distribution(label: &str, items: &[Distribution]) {
+    if items.is_empty() {
+        return;
+    }
+    println!("\n按{}：", label);
+    for item in items {
+        println!("  {}  {}/{}", item.name, item.completed, item.total);
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", text);
+    Ok(())
+}
+
+fn print_table(store: &TaskStore, tasks: &[Task]) -> Result<(), String> {
+    if tasks.is_empty() {
+        println!("没有任务");
+        return Ok(());
+    }
+    let tags = store.tags()?;
+
+    let rows: Vec<[String; 5]> = tasks
+        .iter()
+        .map(|t| {
+            let done = if t.status == TaskStatus::Completed { "✓" } else { " " };
+            let tag_names: Vec<&str> = tags
+                .iter()
+                .filter(|tag| t.tags.contains(&tag.id))
+                .map(|tag| tag.name.as_str())
+                .collect();
+            [
+                format!("{} {}", done, short_id(&t.id)),
+                t.priority.as_str().to_string(),
+                t.due_date.clone().unwrap_or_default().replace('T', " "),
+                t.title.clone(),
+                tag_names.join(","),
+            ]
+        })
+        .collect();
+
+    let header = ["ID", "优先级", "截止", "标题", "标签"].map(|s| s.to_string());
+    let mut widths = [0usize; 5];
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(display_width(cell));
+        }
+    }
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| pad(cell, widths[i]))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+    Ok(())
+}
+
+fn print_report(report: &MaintenanceReport) {
+    if report.integrity_errors.is_empty() {
+        println!("完整性检查：通过");
+    } else {
+        println!("完整性检查发现 {} 个问题：", report.integrity_errors.len());
+        for error in &report.integrity_errors {
+            println!("  {}", error);
+        }
+    }
+    if !report.foreign_key_errors.is_empty() {
+        println!("外键检查发现 {} 处悬空引用", report.foreign_key_errors.len());
+    }
+    let repaired = &report.repaired;
+    if repaired.total() > 0 {
+        println!(
+            "已修复：标签关联 {}，子任务 {}，变更记录 {}，项目引用 {}",
+            repaired.task_tags, repaired.subtasks, repaired.task_events, repaired.task_projects
+        );
+    }
+    println!(
+        "数据库大小：{:.1} KB → {:.1} KB",
+        report.size_before as f64 / 1024.0,
+        report.size_after as f64 / 1024.0
+    );
+}
+
+/// 终端显示宽度（中文等全角字符占两列）
+fn display_width(s: &str) -> usize {
+    s.chars().map(|c| if is_wide(c) { 2 } else { 1 }).sum()
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6)
+}
+
+fn pad(s: &str, width: usize) -> String {
+    format!("{}{}", s, " ".repeat(width.saturating_sub(display_width(s))))
+}
@@ -0,0 +1,330 @@
+//! MCP（Model Context Protocol）服务：通过 stdio 以 JSON-RPC 2.0 通信，每行一条消息
+
+use crate::resolve_task_id;
+use ganle_core::models::task::{NewTask, Task, TaskStatus};
+use ganle_core::services::task_service::{normalize_due, normalize_start};
+use ganle_core::TaskStore;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+/// 支持的协议版本，第一个为默认
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const PROJECTS_URI: &str = "ganle://projects";
+const TAGS_URI: &str = "ganle://tags";
+
+/// JSON-RPC 错误码
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// 持续读取标准输入直到 EOF
+pub fn serve(store: &TaskStore) -> Result<(), String> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(store, &message),
+            Err(e) => Some(error_response(Value::Null, RpcError::new(PARSE_ERROR, e.to_string()))),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response).map_err(|e| e.to_string())?;
+            stdout.flush().map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// 处理一条消息；通知（无 id）不需要回复
+fn handle_message(store: &TaskStore, message: &Value) -> Option<Value> {
+    let id = message.get("id").cloned()?;
+    let method = message.get("method").and_then(|m| m.as_str()).unwrap_or("");
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(store, &params),
+        "resources/list" => Ok(json!({ "resources": resource_definitions() })),
+        "resources/read" => read_resource(store, &params),
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("未知方法: {}", method))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(|v| v.as_str());
+    let version = requested
+        .filter(|v| PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {}, "resources": {} },
+        "serverInfo": { "name": "ganle", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+// ========== 工具 ==========
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "list_tasks",
+            "description": "列出任务。view 为智能清单（today 今日、week 近 7 天、all 全部、completed 已完成、favorite 收藏、scheduled 开始日期未到的已计划任务），也可按项目或标签筛选。默认不含已完成任务。",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "view": { "type": "string", "enum": ["today", "week", "all", "completed", "favorite", "scheduled"] },
+                    "project": { "type": "string", "description": "项目名或项目ID" },
+                    "tag": { "type": "string", "description": "标签名或标签ID" },
+                    "include_completed": { "type": "boolean" }
+                }
+            }
+        },
+        {
+            "name": "search",
+            "description": "按关键词搜索任务标题和描述",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "include_completed": { "type": "boolean" }
+                },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "create_task",
+            "description": "创建任务，项目和标签按名称匹配，不存在时自动创建",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "due": { "type": "string", "description": "today / tomorrow / YYYY-MM-DD / YYYY-MM-DD HH:MM" },
+                    "start": { "type": "string", "description": "开始日期，之前不出现在今日等清单中：today / tomorrow / YYYY-MM-DD" },
+                    "priority": { "type": "string", "enum": ["high", "medium", "low"] },
+                    "estimate_minutes": { "type": "integer", "minimum": 1, "description": "预估用时（分钟）" },
+                    "project": { "type": "string" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "repeat": { "type": "string", "enum": ["none", "daily", "weekly", "monthly"] }
+                },
+                "required": ["title"]
+            }
+        },
+        {
+            "name": "complete_task",
+            "description": "将任务标记为已完成（重复任务会生成下一个）",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string", "description": "任务ID或其唯一前缀" }
+                },
+                "required": ["id"]
+            }
+        },
+        {
+            "name": "add_subtask",
+            "description": "为任务添加子任务",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "task_id": { "type": "string", "description": "任务ID或其唯一前缀" },
+                    "title": { "type": "string" }
+                },
+                "required": ["task_id", "title"]
+            }
+        }
+    ])
+}
+
+fn call_tool(store: &TaskStore, params: &Value) -> Result<Value, RpcError> {
+    let name = params
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "缺少工具名"))?;
+    let args = params.get("arguments").cloned().unwrap_or(Value::Null);
+
+    let output = match name {
+        "list_tasks" => list_tasks(store, &args).and_then(to_text),
+        "search" => search(store, &args).and_then(to_text),
+        "create_task" => create_task(store, &args).and_then(to_text),
+        "complete_task" => complete_task(store, &args).and_then(to_text),
+        "add_subtask" => add_subtask(store, &args).and_then(to_text),
+        _ => return Err(RpcError::new(INVALID_PARAMS, format!("未知工具: {}", name))),
+    };
+
+    // 工具执行失败按协议放进结果里，交给模型自行处理
+    Ok(match output {
+        Ok(text) => json!({ "content": [{ "type": "text", "text": text }] }),
+        Err(e) => json!({ "content": [{ "type": "text", "text": e }], "isError": true }),
+    })
+}
+
+fn list_tasks(store: &TaskStore, args: &Value) -> Result<Vec<Task>, String> {
+    let (view_type, view_id) = if let Some(project) = arg_str(args, "project") {
+        let project = store
+            .projects()?
+            .into_iter()
+            .find(|p| p.id == project || p.name == project)
+            .ok_or_else(|| format!("项目不存在: {}", project))?;
+        ("project", project.id)
+    } else if let Some(tag) = arg_str(args, "tag") {
+        let tag = store
+            .tags()?
+            .into_iter()
+            .find(|t| t.id == tag || t.name == tag)
+            .ok_or_else(|| format!("标签不存在: {}", tag))?;
+        ("tag", tag.id)
+    } else {
+        ("smart", arg_str(args, "view").unwrap_or("all").to_string())
+    };
+
+    let include_completed = view_id == "completed" || arg_bool(args, "include_completed");
+    let tasks = store.filtered_tasks(view_type, &view_id, "")?;
+    Ok(hide_completed(tasks, include_completed))
+}
+
+fn search(store: &TaskStore, args: &Value) -> Result<Vec<Task>, String> {
+    let query = arg_str(args, "query").ok_or("缺少 query")?;
+    let tasks = store.filtered_tasks("smart", "all", query)?;
+    Ok(hide_completed(tasks, arg_bool(args, "include_completed")))
+}
+
+fn create_task(store: &TaskStore, args: &Value) -> Result<Task, String> {
+    let title = arg_str(args, "title")
+        .filter(|t| !t.trim().is_empty())
+        .ok_or("缺少 title")?;
+    let tz = store.timezone();
+    let due = arg_str(args, "due").map(|d| normalize_due(d, tz)).transpose()?;
+    let start_date = arg_str(args, "start").map(|d| normalize_start(d, tz)).transpose()?;
+    let project_id = match arg_str(args, "project") {
+        Some(name) => Some(store.find_or_create_project(name)?.id),
+        None => None,
+    };
+    let mut tag_ids = Vec::new();
+    for name in args.get("tags").and_then(|v| v.as_array()).into_iter().flatten() {
+        if let Some(name) = name.as_str() {
+            tag_ids.push(store.find_or_create_tag(name)?.id);
+        }
+    }
+
+    let mut new = NewTask::new(title);
+    new.project_id = project_id;
+    new.due = due;
+    new.start_date = start_date;
+    new.estimate_minutes = args.get("estimate_minutes").and_then(|v| v.as_u64()).and_then(|m| u32::try_from(m).ok());
+    new.tag_ids = tag_ids;
+    if let Some(priority) = arg_str(args, "priority") {
+        new.priority = priority.to_string();
+    }
+    if let Some(repeat) = arg_str(args, "repeat") {
+        new.repeat_type = repeat.to_string();
+    }
+    store.create_task(&new)
+}
+
+fn complete_task(store: &TaskStore, args: &Value) -> Result<Task, String> {
+    let id = resolve_task_id(store, arg_str(args, "id").ok_or("缺少 id")?)?;
+    let task = store.task(&id)?;
+    if task.status == TaskStatus::Completed {
+        return Ok(task);
+    }
+    store.toggle_task(&id)
+}
+
+fn add_subtask(store: &TaskStore, args: &Value) -> Result<Task, String> {
+    let id = resolve_task_id(store, arg_str(args, "task_id").ok_or("缺少 task_id")?)?;
+    let title = arg_str(args, "title").ok_or("缺少 title")?;
+    store.add_subtask(&id, title)?;
+    store.task(&id)
+}
+
+fn hide_completed(tasks: Vec<Task>, include_completed: bool) -> Vec<Task> {
+    tasks
+        .into_iter()
+        .filter(|t| include_completed || t.status != TaskStatus::Completed)
+        .collect()
+}
+
+// ========== 资源 ==========
+
+fn resource_definitions() -> Value {
+    json!([
+        {
+            "uri": PROJECTS_URI,
+            "name": "projects",
+            "description": "全部项目",
+            "mimeType": "application/json"
+        },
+        {
+            "uri": TAGS_URI,
+            "name": "tags",
+            "description": "全部标签",
+            "mimeType": "application/json"
+        }
+    ])
+}
+
+fn read_resource(store: &TaskStore, params: &Value) -> Result<Value, RpcError> {
+    let uri = params
+        .get("uri")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "缺少 uri"))?;
+    let text = match uri {
+        PROJECTS_URI => store.projects().and_then(to_text),
+        TAGS_URI => store.tags().and_then(to_text),
+        _ => return Err(RpcError::new(INVALID_PARAMS, format!("未知资源: {}", uri))),
+    }
+    .map_err(|e| RpcError::new(INVALID_PARAMS, e))?;
+
+    Ok(json!({
+        "contents": [{ "uri": uri, "mimeType": "application/json", "text": text }]
+    }))
+}
+
+// ========== 通用工具 ==========
+
+fn to_text<T: Serialize>(value: T) -> Result<String, String> {
+    serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+}
+
+fn arg_str<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key).and_then(|v| v.as_str())
+}
+
+fn arg_bool(args: &Value, key: &str) -> bool {
+    args.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+}
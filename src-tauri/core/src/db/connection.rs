@@ -0,0 +1,215 @@
+use rusqlite::{params, Connection, OpenFlags, ToSql};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// 写锁冲突时的等待时间（GUI 与命令行可能同时访问数据库）
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// 只读连接数
+const READERS: usize = 4;
+/// 未加密 SQLite 文件的文件头，加密后的文件头是随机的
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// 数据库连接管理：一个写连接加若干只读连接。
+/// WAL 模式下读不阻塞写，查询可以与写入、以及彼此之间并发执行
+pub struct Database {
+    writer: Mutex<Connection>,
+    readers: Mutex<Vec<Connection>>,
+    reader_returned: Condvar,
+}
+
+impl Database {
+    /// 创建新的数据库连接。key 为加密数据库的密码，未加密时传 None
+    pub fn new(db_path: &Path, key: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        if key.is_none() && is_encrypted(db_path) {
+            return Err("数据库已加密，需要输入密码".into());
+        }
+        let writer = Connection::open(db_path)?;
+        apply_key(&writer, key)?;
+        writer.busy_timeout(BUSY_TIMEOUT)?;
+        // WAL 模式下读写互不阻塞，便于多进程共享同一数据库
+        writer.execute_batch("PRAGMA journal_mode = WAL;")?;
+        // 启用外键约束
+        writer.execute_batch("PRAGMA foreign_keys = ON;")?;
+
+        let mut readers = Vec::with_capacity(READERS);
+        for _ in 0..READERS {
+            let reader = Connection::open_with_flags(
+                db_path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX | OpenFlags::SQLITE_OPEN_URI,
+            )?;
+            apply_key(&reader, key)?;
+            reader.busy_timeout(BUSY_TIMEOUT)?;
+            readers.push(reader);
+        }
+
+        Ok(Self {
+            writer: Mutex::new(writer),
+            readers: Mutex::new(readers),
+            reader_returned: Condvar::new(),
+        })
+    }
+
+    /// 执行数据库迁移
+    pub fn run_migrations(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let conn = self.writer.lock().map_err(|e| e.to_string())?;
+        super::migration::run(&conn)?;
+        Ok(())
+    }
+
+    /// 独占写连接执行操作
+    pub fn write<T>(&self, f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, String> {
+        let conn = self.writer.lock().map_err(|e| e.to_string())?;
+        f(&conn)
+    }
+
+    /// 借一个只读连接执行查询，全部被占用时等待归还
+    pub fn read<T>(&self, f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, String> {
+        let conn = {
+            let mut idle = self.readers.lock().map_err(|e| e.to_string())?;
+            loop {
+                if let Some(conn) = idle.pop() {
+                    break conn;
+                }
+                idle = self.reader_returned.wait(idle).map_err(|e| e.to_string())?;
+            }
+        };
+        let result = f(&conn);
+        if let Ok(mut idle) = self.readers.lock() {
+            idle.push(conn);
+        }
+        self.reader_returned.notify_one();
+        result
+    }
+}
+
+/// 是否编译了 SQLCipher 加密支持（encryption 特性）
+pub fn encryption_supported() -> bool {
+    cfg!(feature = "encryption")
+}
+
+/// 数据库文件是否已加密（文件不存在或为空时视为未加密）
+pub fn is_encrypted(db_path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    match std::fs::File::open(db_path).and_then(|mut f| f.read_exact(&mut header)) {
+        Ok(()) => &header != SQLITE_HEADER,
+        Err(_) => false,
+    }
+}
+
+/// 设置密码并验证。SQLCipher 用 PBKDF2 从密码派生出实际的加密密钥
+fn apply_key(conn: &Connection, key: Option<&str>) -> Result<(), String> {
+    let Some(key) = key else {
+        return Ok(());
+    };
+    if !encryption_supported() {
+        return Err("当前版本未启用数据库加密".to_string());
+    }
+    conn.pragma_update(None, "key", key).map_err(|e| e.to_string())?;
+    // 密码错误时第一次读取才会失败
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+        .map_err(|_| "密码错误".to_string())?;
+    Ok(())
+}
+
+/// 检查密码能否打开加密的数据库
+pub fn verify_key(db_path: &Path, key: &str) -> Result<(), String> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|e| e.to_string())?;
+    apply_key(&conn, Some(key))
+}
+
+/// 加密或解密迁移时写入的新数据库文件，下次打开前替换原文件
+pub fn pending_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(".pending");
+    PathBuf::from(name)
+}
+
+/// 把当前数据库完整导出到 target：key 为 Some 时导出为加密数据库，None 时导出为明文。
+/// 先导出到临时文件，分离并写入磁盘后再改名为 target，中途失败不会留下不完整的 target
+pub fn export_copy(conn: &Connection, target: &Path, key: Option<&str>) -> Result<(), String> {
+    if !encryption_supported() {
+        return Err("当前版本未启用数据库加密".to_string());
+    }
+    let mut partial = target.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let _ = std::fs::remove_file(target);
+    let _ = std::fs::remove_file(&partial);
+
+    let path = partial.to_str().ok_or("数据库路径包含无效字符")?;
+    conn.execute(
+        "ATTACH DATABASE ?1 AS migrated KEY ?2",
+        params![path, key.unwrap_or("")],
+    )
+    .map_err(|e| e.to_string())?;
+    let exported = conn
+        .query_row("SELECT sqlcipher_export('migrated')", [], |_| Ok(()))
+        .map_err(|e| e.to_string());
+    let detached = conn.execute_batch("DETACH DATABASE migrated").map_err(|e| e.to_string());
+    let result = exported
+        .and(detached)
+        .and_then(|_| std::fs::File::open(&partial).and_then(|f| f.sync_all()).map_err(|e| e.to_string()))
+        .and_then(|_| std::fs::rename(&partial, target).map_err(|e| e.to_string()));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+/// 用迁移好的新文件替换原数据库（须在打开连接之前调用）
+pub fn apply_pending(db_path: &Path) -> Result<(), String> {
+    let pending = pending_path(db_path);
+    if !pending.exists() {
+        return Ok(());
+    }
+    std::fs::rename(&pending, db_path).map_err(|e| e.to_string())?;
+    // 旧文件的 WAL 已导出到新文件中，留着会被当成新文件的日志
+    for suffix in ["-wal", "-shm"] {
+        let mut name = db_path.as_os_str().to_owned();
+        name.push(suffix);
+        let _ = std::fs::remove_file(PathBuf::from(name));
+    }
+    Ok(())
+}
+
+/// 在事务中执行一组写操作，出错时整体回滚。
+/// 用保存点实现，外层已有事务（如导入、撤销）时可以嵌套
+pub fn transaction<T>(conn: &Connection, f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    conn.execute_batch("SAVEPOINT repo_write").map_err(|e| e.to_string())?;
+    let result = f().and_then(|value| {
+        conn.execute_batch("RELEASE repo_write").map_err(|e| e.to_string())?;
+        Ok(value)
+    });
+    if result.is_err() {
+        let _ = conn.execute_batch("ROLLBACK TO repo_write; RELEASE repo_write");
+    }
+    result
+}
+
+/// 把给出的列拼成一条 UPDATE 语句，没有列时不执行
+pub fn update_columns(conn: &Connection, table: &str, id: &str, columns: &[(&str, &dyn ToSql)]) -> Result<(), String> {
+    if columns.is_empty() {
+        return Ok(());
+    }
+    let sets: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, (column, _))| format!("{} = ?{}", column, i + 1))
+        .collect();
+    let sql = format!("UPDATE {} SET {} WHERE id = ?{}", table, sets.join(", "), columns.len() + 1);
+    let mut values: Vec<&dyn ToSql> = columns.iter().map(|(_, value)| *value).collect();
+    values.push(&id);
+    conn.execute(&sql, values.as_slice()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 测试用的内存数据库（已建表）
+#[cfg(test)]
+pub(crate) fn memory() -> Connection {
+    let conn = Connection::open_in_memory().expect("内存数据库");
+    conn.execute_batch("PRAGMA foreign_keys = ON;").expect("启用外键");
+    super::migration::run(&conn).expect("迁移");
+    conn
+}
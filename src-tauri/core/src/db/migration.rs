@@ -0,0 +1,152 @@
+use crate::db::settings_repo;
+use crate::models::task::Due;
+use crate::services::seed_service;
+use rusqlite::Connection;
+
+/// 执行数据库迁移：建表。初始数据由首次启动时的选择决定（见 seed_service）
+pub fn run(conn: &Connection) -> Result<(), Box<dyn std::error::Error>> {
+    create_tables(conn)?;
+    add_column(conn, "tasks", "due_tz", "TEXT")?;
+    add_column(conn, "tasks", "start_date", "TEXT")?;
+    add_column(conn, "tasks", "estimate_minutes", "INTEGER")?;
+    add_column(conn, "time_entries", "source", "TEXT NOT NULL DEFAULT 'timer'")?;
+    migrate_due_dates(conn)?;
+    seed_service::mark_legacy_sample_data(conn)?;
+    Ok(())
+}
+
+/// 为旧版本数据库补充新增的列（已存在时跳过）
+fn add_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let exists: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = ?1", table),
+        [column],
+        |row| row.get(0),
+    )?;
+    if exists == 0 {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))?;
+    }
+    Ok(())
+}
+
+/// 旧版本把带钟点的截止时间存为不带时区的本地时间，按用户时区换算为 UTC 时刻
+fn migrate_due_dates(conn: &Connection) -> Result<(), Box<dyn std::error::Error>> {
+    let tz = settings_repo::timezone(conn);
+    let mut stmt = conn.prepare("SELECT id, due_date FROM tasks WHERE due_tz IS NULL AND LENGTH(due_date) > 10")?;
+    let rows: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    for (id, value) in rows {
+        let Ok(due) = Due::parse(&value, tz) else {
+            continue;
+        };
+        let (due_date, due_tz) = due.to_db();
+        conn.execute(
+            "UPDATE tasks SET due_date = ?1, due_tz = ?2 WHERE id = ?3",
+            rusqlite::params![due_date, due_tz, id],
+        )?;
+    }
+    Ok(())
+}
+
+/// 创建所有表
+fn create_tables(conn: &Connection) -> Result<(), Box<dyn std::error::Error>> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS projects (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            color TEXT NOT NULL DEFAULT '#228B22',
+            icon TEXT NOT NULL DEFAULT 'briefcase',
+            archived INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS tags (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            color TEXT NOT NULL DEFAULT '#3B82F6'
+        );
+
+        CREATE TABLE IF NOT EXISTS tasks (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL DEFAULT '',
+            status TEXT NOT NULL DEFAULT 'todo',
+            priority TEXT NOT NULL DEFAULT 'medium',
+            project_id TEXT,
+            due_date TEXT,
+            -- 为空时 due_date 是全天日期，否则 due_date 是 UTC 时刻、due_tz 是设定时的时区
+            due_tz TEXT,
+            -- 开始日期（YYYY-MM-DD），之前任务不出现在智能列表中
+            start_date TEXT,
+            -- 预估用时（分钟）
+            estimate_minutes INTEGER,
+            reminder TEXT,
+            repeat_type TEXT NOT NULL DEFAULT 'none',
+            favorite INTEGER NOT NULL DEFAULT 0,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            completed_at TEXT,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE SET NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS subtasks (
+            id TEXT PRIMARY KEY,
+            task_id TEXT NOT NULL,
+            title TEXT NOT NULL,
+            completed INTEGER NOT NULL DEFAULT 0,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS task_tags (
+            task_id TEXT NOT NULL,
+            tag_id TEXT NOT NULL,
+            PRIMARY KEY (task_id, tag_id),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS task_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            field TEXT,
+            old_value TEXT,
+            new_value TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_task_events_task ON task_events(task_id, created_at);
+
+        -- 计时记录，ended_at 为空表示正在计时（同一时间最多一条，重启后继续）
+        CREATE TABLE IF NOT EXISTS time_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            seconds INTEGER,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_time_entries_task ON time_entries(task_id, started_at);
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_time_entries_running ON time_entries((ended_at IS NULL))
+            WHERE ended_at IS NULL;
+
+        CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );
+
+        -- 示例数据登记（kind 为 task / project / tag），用于一键删除
+        CREATE TABLE IF NOT EXISTS sample_items (
+            kind TEXT NOT NULL,
+            item_id TEXT NOT NULL,
+            PRIMARY KEY (kind, item_id)
+        );
+        ",
+    )?;
+    Ok(())
+}
@@ -3,4 +3,6 @@ pub mod migration;
 pub mod project_repo;
 pub mod settings_repo;
 pub mod tag_repo;
+pub mod task_event_repo;
 pub mod task_repo;
+pub mod time_entry_repo;
@@ -1,5 +1,6 @@
+use crate::db::connection::{transaction, update_columns};
 use crate::models::project::Project;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, ToSql};
 
 /// 获取所有项目
 pub fn get_all(conn: &Connection) -> Result<Vec<Project>, String> {
@@ -53,22 +54,21 @@ pub fn update(
     icon: Option<&str>,
     archived: Option<bool>,
 ) -> Result<Project, String> {
-    if let Some(v) = name {
-        conn.execute("UPDATE projects SET name = ?1 WHERE id = ?2", params![v, id])
-            .map_err(|e| e.to_string())?;
+    let archived = archived.map(|v| v as i32);
+    let mut columns: Vec<(&str, &dyn ToSql)> = Vec::new();
+    if let Some(v) = &name {
+        columns.push(("name", v));
     }
-    if let Some(v) = color {
-        conn.execute("UPDATE projects SET color = ?1 WHERE id = ?2", params![v, id])
-            .map_err(|e| e.to_string())?;
+    if let Some(v) = &color {
+        columns.push(("color", v));
     }
-    if let Some(v) = icon {
-        conn.execute("UPDATE projects SET icon = ?1 WHERE id = ?2", params![v, id])
-            .map_err(|e| e.to_string())?;
+    if let Some(v) = &icon {
+        columns.push(("icon", v));
     }
-    if let Some(v) = archived {
-        conn.execute("UPDATE projects SET archived = ?1 WHERE id = ?2", params![v as i32, id])
-            .map_err(|e| e.to_string())?;
+    if let Some(v) = &archived {
+        columns.push(("archived", v));
     }
+    update_columns(conn, "projects", id, &columns)?;
 
     get_by_id(conn, id)
 }
@@ -94,12 +94,14 @@ pub fn get_by_id(conn: &Connection, id: &str) -> Result<Project, String> {
 
 /// 删除项目
 pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
-    // 将关联任务的 project_id 设为 NULL
-    conn.execute("UPDATE tasks SET project_id = NULL WHERE project_id = ?1", params![id])
-        .map_err(|e| e.to_string())?;
-    conn.execute("DELETE FROM projects WHERE id = ?1", params![id])
-        .map_err(|e| e.to_string())?;
-    Ok(())
+    transaction(conn, || {
+        // 将关联任务的 project_id 设为 NULL
+        conn.execute("UPDATE tasks SET project_id = NULL WHERE project_id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        conn.execute("DELETE FROM projects WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    })
 }
 
 /// 获取项目下未完成任务数
@@ -0,0 +1,93 @@
+use crate::models::pomodoro::PomodoroSettings;
+use crate::models::settings::{
+    AppSettings, DEFAULT_API_PORT, DEFAULT_APP_LOCK_MINUTES, DEFAULT_QUICK_ADD_SHORTCUT,
+};
+use chrono_tz::Tz;
+use rusqlite::{params, Connection};
+
+/// 获取应用设置
+pub fn get_settings(conn: &Connection) -> Result<AppSettings, String> {
+    let is_dark = get_value(conn, "is_dark").unwrap_or_else(|| "false".to_string());
+    let todotxt_path = get_value(conn, "todotxt_path");
+    let api_enabled = get_value(conn, "api_enabled").unwrap_or_else(|| "false".to_string());
+    let api_port = get_value(conn, "api_port")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_API_PORT);
+    let api_token = get_value(conn, "api_token");
+    let quick_add_shortcut = get_value(conn, "quick_add_shortcut")
+        .unwrap_or_else(|| DEFAULT_QUICK_ADD_SHORTCUT.to_string());
+    let app_lock_minutes = get_value(conn, "app_lock_minutes")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_APP_LOCK_MINUTES);
+
+    Ok(AppSettings {
+        is_dark: is_dark == "true",
+        todotxt_path,
+        api_enabled: api_enabled == "true",
+        api_port,
+        api_token,
+        quick_add_shortcut,
+        app_lock_enabled: get_value(conn, "app_lock_hash").is_some(),
+        app_lock_minutes,
+        timezone: timezone(conn).name().to_string(),
+        show_deferred: show_deferred(conn),
+        pomodoro: pomodoro_settings(conn),
+    })
+}
+
+/// 番茄钟时长设置，未设置的项取默认值
+pub fn pomodoro_settings(conn: &Connection) -> PomodoroSettings {
+    let defaults = PomodoroSettings::default();
+    let minutes = |key: &str, default: u32| {
+        get_value(conn, key)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    PomodoroSettings {
+        work_minutes: minutes("pomodoro_work_minutes", defaults.work_minutes),
+        short_break_minutes: minutes("pomodoro_short_break_minutes", defaults.short_break_minutes),
+        long_break_minutes: minutes("pomodoro_long_break_minutes", defaults.long_break_minutes),
+        long_break_every: minutes("pomodoro_long_break_every", defaults.long_break_every),
+    }
+}
+
+/// 用户设置的时区，未设置时跟随系统
+pub fn timezone(conn: &Connection) -> Tz {
+    get_value(conn, "timezone")
+        .and_then(|name| name.parse().ok())
+        .unwrap_or_else(system_timezone)
+}
+
+/// 是否在智能列表中显示开始日期未到的任务（默认隐藏）
+pub fn show_deferred(conn: &Connection) -> bool {
+    get_value(conn, "show_deferred").as_deref() == Some("true")
+}
+
+/// 系统时区，无法识别时为 UTC
+pub fn system_timezone() -> Tz {
+    iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// 读取单个设置值，不存在或为空时返回 None
+pub fn get_value(conn: &Connection, key: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT value FROM settings WHERE key = ?1",
+        params![key],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .filter(|v| !v.is_empty())
+}
+
+/// 更新设置
+pub fn update_setting(conn: &Connection, key: &str, value: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        params![key, value],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
+use crate::db::connection::{transaction, update_columns};
 use crate::models::tag::Tag;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, ToSql};
 
 /// 获取所有标签
 pub fn get_all(conn: &Connection) -> Result<Vec<Tag>, String> {
@@ -44,14 +45,14 @@ pub fn update(
     name: Option<&str>,
     color: Option<&str>,
 ) -> Result<Tag, String> {
-    if let Some(v) = name {
-        conn.execute("UPDATE tags SET name = ?1 WHERE id = ?2", params![v, id])
-            .map_err(|e| e.to_string())?;
+    let mut columns: Vec<(&str, &dyn ToSql)> = Vec::new();
+    if let Some(v) = &name {
+        columns.push(("name", v));
     }
-    if let Some(v) = color {
-        conn.execute("UPDATE tags SET color = ?1 WHERE id = ?2", params![v, id])
-            .map_err(|e| e.to_string())?;
+    if let Some(v) = &color {
+        columns.push(("color", v));
     }
+    update_columns(conn, "tags", id, &columns)?;
     get_by_id(conn, id)
 }
 
@@ -73,11 +74,13 @@ pub fn get_by_id(conn: &Connection, id: &str) -> Result<Tag, String> {
 
 /// 删除标签（同时清理关联）
 pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
-    conn.execute("DELETE FROM task_tags WHERE tag_id = ?1", params![id])
-        .map_err(|e| e.to_string())?;
-    conn.execute("DELETE FROM tags WHERE id = ?1", params![id])
-        .map_err(|e| e.to_string())?;
-    Ok(())
+    transaction(conn, || {
+        conn.execute("DELETE FROM task_tags WHERE tag_id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        conn.execute("DELETE FROM tags WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    })
 }
 
 /// 获取标签下未完成任务数
@@ -0,0 +1,117 @@
+use crate::models::task::Task;
+use crate::models::task_event::TaskEvent;
+use rusqlite::{params, Connection};
+
+/// 记录一条任务变更
+pub fn record(
+    conn: &Connection,
+    task_id: &str,
+    kind: &str,
+    field: Option<&str>,
+    old_value: Option<&str>,
+    new_value: Option<&str>,
+) -> Result<(), String> {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    conn.execute(
+        "INSERT INTO task_events (task_id, kind, field, old_value, new_value, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![task_id, kind, field, old_value, new_value, &now],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 用快照替换任务的全部变更记录（撤销删除任务时按原ID写回）
+pub fn restore(conn: &Connection, task_id: &str, events: &[TaskEvent]) -> Result<(), String> {
+    conn.execute("DELETE FROM task_events WHERE task_id = ?1", params![task_id])
+        .map_err(|e| e.to_string())?;
+    for event in events {
+        conn.execute(
+            "INSERT OR REPLACE INTO task_events (id, task_id, kind, field, old_value, new_value, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                event.id,
+                event.task_id,
+                event.kind,
+                event.field,
+                event.old_value,
+                event.new_value,
+                event.created_at
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// 比较任务修改前后的字段，逐个记录变化
+pub fn record_diff(conn: &Connection, before: &Task, after: &Task) -> Result<(), String> {
+    let fields: [(&str, Option<String>, Option<String>); 11] = [
+        ("title", Some(before.title.clone()), Some(after.title.clone())),
+        ("description", Some(before.description.clone()), Some(after.description.clone())),
+        ("status", Some(before.status.as_str().to_string()), Some(after.status.as_str().to_string())),
+        ("priority", Some(before.priority.as_str().to_string()), Some(after.priority.as_str().to_string())),
+        ("project_id", before.project_id.clone(), after.project_id.clone()),
+        ("due_date", before.due_date.clone(), after.due_date.clone()),
+        ("start_date", before.start_date.map(|d| d.to_string()), after.start_date.map(|d| d.to_string())),
+        ("estimate_minutes", before.estimate_minutes.map(|m| m.to_string()), after.estimate_minutes.map(|m| m.to_string())),
+        ("reminder", before.reminder.clone(), after.reminder.clone()),
+        ("repeat_type", Some(before.repeat.as_str().to_string()), Some(after.repeat.as_str().to_string())),
+        ("favorite", Some(before.favorite.to_string()), Some(after.favorite.to_string())),
+    ];
+    for (field, old, new) in fields {
+        if old == new {
+            continue;
+        }
+        let kind = match (field, new.as_deref()) {
+            ("status", Some("completed")) => "completed",
+            ("status", _) if old.as_deref() == Some("completed") => "reopened",
+            _ => "updated",
+        };
+        record(conn, &after.id, kind, Some(field), old.as_deref(), new.as_deref())?;
+    }
+
+    let mut old_tags = before.tags.clone();
+    let mut new_tags = after.tags.clone();
+    old_tags.sort();
+    new_tags.sort();
+    if old_tags != new_tags {
+        record(
+            conn,
+            &after.id,
+            "updated",
+            Some("tags"),
+            Some(&old_tags.join(",")),
+            Some(&new_tags.join(",")),
+        )?;
+    }
+    Ok(())
+}
+
+/// 获取任务的变更记录，按时间先后排列
+pub fn get_by_task(conn: &Connection, task_id: &str) -> Result<Vec<TaskEvent>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, task_id, kind, field, old_value, new_value, created_at
+             FROM task_events WHERE task_id = ?1 ORDER BY created_at, id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let events = stmt
+        .query_map(params![task_id], |row| {
+            Ok(TaskEvent {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                kind: row.get(2)?,
+                field: row.get(3)?,
+                old_value: row.get(4)?,
+                new_value: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(events)
+}
@@ -0,0 +1,641 @@
+use crate::db::connection::{transaction, update_columns};
+use crate::db::{settings_repo, task_event_repo};
+use crate::models::task::{Due, Priority, RepeatType, SubTask, Task, TaskCounts, TaskStatus};
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use rusqlite::{params, Connection, Row, ToSql};
+
+/// 获取所有任务（含子任务和标签）
+pub fn get_all(conn: &Connection) -> Result<Vec<Task>, String> {
+    let tz = settings_repo::timezone(conn);
+    let mut stmt = conn
+        .prepare(&select_tasks("ORDER BY sort_order ASC"))
+        .map_err(|e| e.to_string())?;
+
+    let tasks: Vec<Task> = stmt
+        .query_map([], read_row)
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .map(|row| {
+            let subtasks = get_subtasks(conn, &row.id).unwrap_or_default();
+            let tags = get_task_tags(conn, &row.id).unwrap_or_default();
+            row_to_task(row, subtasks, tags, tz)
+        })
+        .collect();
+
+    Ok(tasks)
+}
+
+/// 根据ID获取单个任务
+pub fn get_by_id(conn: &Connection, id: &str) -> Result<Task, String> {
+    let mut stmt = conn
+        .prepare(&select_tasks("WHERE id = ?1"))
+        .map_err(|e| e.to_string())?;
+
+    let row = stmt
+        .query_row(params![id], read_row)
+        .map_err(|e| e.to_string())?;
+
+    let subtasks = get_subtasks(conn, &row.id).unwrap_or_default();
+    let tags = get_task_tags(conn, &row.id).unwrap_or_default();
+
+    Ok(row_to_task(row, subtasks, tags, settings_repo::timezone(conn)))
+}
+
+/// 查询任务的 SELECT 语句，tail 为 WHERE / ORDER BY 部分。列顺序与 read_row 对应
+fn select_tasks(tail: &str) -> String {
+    format!(
+        "SELECT id, title, description, status, priority, project_id,
+                due_date, due_tz, start_date, estimate_minutes,
+                (SELECT COALESCE(SUM(seconds), 0) FROM time_entries WHERE task_id = tasks.id),
+                reminder, repeat_type, favorite, sort_order,
+                created_at, updated_at, completed_at
+         FROM tasks {}",
+        tail
+    )
+}
+
+fn read_row(row: &Row) -> rusqlite::Result<TaskRow> {
+    Ok(TaskRow {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        description: row.get(2)?,
+        status: row.get(3)?,
+        priority: row.get(4)?,
+        project_id: row.get(5)?,
+        due_date: row.get(6)?,
+        due_tz: row.get(7)?,
+        start_date: row.get(8)?,
+        estimate_minutes: row.get(9)?,
+        tracked_seconds: row.get(10)?,
+        reminder: row.get(11)?,
+        repeat_type: row.get(12)?,
+        favorite: row.get::<_, i32>(13)?,
+        sort_order: row.get(14)?,
+        created_at: row.get(15)?,
+        updated_at: row.get(16)?,
+        completed_at: row.get(17)?,
+    })
+}
+
+/// 创建任务
+#[allow(clippy::too_many_arguments)]
+pub fn create(
+    conn: &Connection,
+    id: &str,
+    title: &str,
+    priority: &str,
+    project_id: Option<&str>,
+    due: Option<&Due>,
+    start_date: Option<NaiveDate>,
+    estimate_minutes: Option<u32>,
+    repeat_type: &str,
+    tag_ids: &[String],
+) -> Result<Task, String> {
+    transaction(conn, || {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let (due_date, due_tz) = due.map(Due::to_db).unzip();
+
+        // 获取当前最大 sort_order
+        let max_order: i32 = conn
+            .query_row("SELECT COALESCE(MAX(sort_order), -1) FROM tasks", [], |row| row.get(0))
+            .unwrap_or(-1);
+
+        conn.execute(
+            "INSERT INTO tasks (id, title, description, status, priority, project_id,
+             due_date, due_tz, start_date, estimate_minutes, reminder, repeat_type, favorite, sort_order,
+             created_at, updated_at, completed_at)
+             VALUES (?1, ?2, '', 'todo', ?3, ?4, ?5, ?6, ?7, ?8, NULL, ?9, 0, ?10, ?11, ?11, NULL)",
+            params![
+                id,
+                title,
+                priority,
+                project_id,
+                due_date,
+                due_tz.flatten(),
+                start_date,
+                estimate_minutes,
+                repeat_type,
+                max_order + 1,
+                &now
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+
+        // 插入标签关联
+        for tag_id in tag_ids {
+            conn.execute(
+                "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
+                params![id, tag_id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        task_event_repo::record(conn, id, "created", None, None, Some(title))?;
+        get_by_id(conn, id)
+    })
+}
+
+/// 内部行结构（从数据库读取的原始数据）
+struct TaskRow {
+    id: String,
+    title: String,
+    description: String,
+    status: String,
+    priority: String,
+    project_id: Option<String>,
+    due_date: Option<String>,
+    due_tz: Option<String>,
+    start_date: Option<NaiveDate>,
+    estimate_minutes: Option<u32>,
+    tracked_seconds: i64,
+    reminder: Option<String>,
+    repeat_type: String,
+    favorite: i32,
+    sort_order: i32,
+    created_at: String,
+    updated_at: String,
+    completed_at: Option<String>,
+}
+
+/// 将数据库行转换为 Task 结构体，截止时间按用户时区 tz 显示
+fn row_to_task(row: TaskRow, subtasks: Vec<SubTask>, tags: Vec<String>, tz: Tz) -> Task {
+    let due = row
+        .due_date
+        .as_deref()
+        .and_then(|value| Due::from_db(value, row.due_tz.as_deref(), tz));
+    Task {
+        id: row.id,
+        title: row.title,
+        description: row.description,
+        status: TaskStatus::from_str(&row.status),
+        priority: Priority::from_str(&row.priority),
+        project_id: row.project_id,
+        tags,
+        subtasks,
+        due_date: due.as_ref().map(|d| d.display(tz)),
+        due,
+        start_date: row.start_date,
+        estimate_minutes: row.estimate_minutes,
+        tracked_seconds: row.tracked_seconds,
+        reminder: row.reminder,
+        repeat: RepeatType::from_str(&row.repeat_type),
+        favorite: row.favorite != 0,
+        sort_order: row.sort_order,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        completed_at: row.completed_at,
+    }
+}
+
+/// 更新任务字段
+#[allow(clippy::too_many_arguments)]
+pub fn update(
+    conn: &Connection,
+    id: &str,
+    title: Option<&str>,
+    description: Option<&str>,
+    status: Option<&str>,
+    priority: Option<&str>,
+    project_id: Option<Option<&str>>,
+    due: Option<Option<&Due>>,
+    start_date: Option<Option<NaiveDate>>,
+    estimate_minutes: Option<Option<u32>>,
+    reminder: Option<Option<&str>>,
+    repeat_type: Option<&str>,
+    tag_ids: Option<&[String]>,
+) -> Result<Task, String> {
+    transaction(conn, || {
+        let before = get_by_id(conn, id)?;
+
+        // 只更新给出的字段，合成一条 UPDATE；updated_at 每次修改只设置一次
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let due = due.map(|d| {
+            let (date, tz) = d.map(Due::to_db).unzip();
+            (date, tz.flatten())
+        });
+        let mut columns: Vec<(&str, &dyn ToSql)> = Vec::new();
+        if let Some(v) = &title {
+            columns.push(("title", v));
+        }
+        if let Some(v) = &description {
+            columns.push(("description", v));
+        }
+        if let Some(v) = &status {
+            columns.push(("status", v));
+        }
+        if let Some(v) = &priority {
+            columns.push(("priority", v));
+        }
+        if let Some(v) = &project_id {
+            columns.push(("project_id", v));
+        }
+        if let Some((due_date, due_tz)) = &due {
+            columns.push(("due_date", due_date));
+            columns.push(("due_tz", due_tz));
+        }
+        if let Some(v) = &start_date {
+            columns.push(("start_date", v));
+        }
+        if let Some(v) = &estimate_minutes {
+            columns.push(("estimate_minutes", v));
+        }
+        if let Some(v) = &reminder {
+            columns.push(("reminder", v));
+        }
+        if let Some(v) = &repeat_type {
+            columns.push(("repeat_type", v));
+        }
+        if !columns.is_empty() || tag_ids.is_some() {
+            columns.push(("updated_at", &now));
+        }
+        update_columns(conn, "tasks", id, &columns)?;
+
+        // 更新标签关联
+        if let Some(tags) = tag_ids {
+            conn.execute("DELETE FROM task_tags WHERE task_id = ?1", params![id])
+                .map_err(|e| e.to_string())?;
+            for tag_id in tags {
+                conn.execute(
+                    "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
+                    params![id, tag_id],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+        }
+
+        let after = get_by_id(conn, id)?;
+        task_event_repo::record_diff(conn, &before, &after)?;
+        Ok(after)
+    })
+}
+
+/// 切换任务完成状态
+pub fn toggle_status(conn: &Connection, id: &str) -> Result<Task, String> {
+    transaction(conn, || {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let current_status: String = conn
+            .query_row("SELECT status FROM tasks WHERE id = ?1", params![id], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+
+        let (kind, new_status) = if current_status == "completed" {
+            conn.execute(
+                "UPDATE tasks SET status = 'todo', completed_at = NULL, updated_at = ?1 WHERE id = ?2",
+                params![&now, id],
+            )
+            .map_err(|e| e.to_string())?;
+            ("reopened", "todo")
+        } else {
+            conn.execute(
+                "UPDATE tasks SET status = 'completed', completed_at = ?1, updated_at = ?1 WHERE id = ?2",
+                params![&now, id],
+            )
+            .map_err(|e| e.to_string())?;
+            ("completed", "completed")
+        };
+
+        task_event_repo::record(conn, id, kind, Some("status"), Some(&current_status), Some(new_status))?;
+        get_by_id(conn, id)
+    })
+}
+
+/// 切换收藏状态
+pub fn toggle_favorite(conn: &Connection, id: &str) -> Result<Task, String> {
+    transaction(conn, || {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        conn.execute(
+            "UPDATE tasks SET favorite = CASE WHEN favorite = 0 THEN 1 ELSE 0 END, updated_at = ?1 WHERE id = ?2",
+            params![&now, id],
+        )
+        .map_err(|e| e.to_string())?;
+
+        let task = get_by_id(conn, id)?;
+        let (old, new) = ((!task.favorite).to_string(), task.favorite.to_string());
+        task_event_repo::record(conn, id, "updated", Some("favorite"), Some(&old), Some(&new))?;
+        Ok(task)
+    })
+}
+
+/// 直接设置完成状态与完成时间（用于导入外部数据）
+pub fn set_completion(conn: &Connection, id: &str, completed_at: Option<&str>) -> Result<(), String> {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let status = if completed_at.is_some() { "completed" } else { "todo" };
+    conn.execute(
+        "UPDATE tasks SET status = ?1, completed_at = ?2, updated_at = ?3 WHERE id = ?4",
+        params![status, completed_at, &now, id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 删除任务
+pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM tasks WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 按快照整体写回任务（含子任务和标签），不存在则插入；已删除的项目和标签会被忽略
+pub fn restore(conn: &Connection, task: &Task) -> Result<(), String> {
+    transaction(conn, || {
+        let before = get_by_id(conn, &task.id).ok();
+        let (due_date, due_tz) = task.due.as_ref().map(Due::to_db).unzip();
+        conn.execute(
+            "INSERT INTO tasks (id, title, description, status, priority, project_id,
+             due_date, due_tz, start_date, estimate_minutes, reminder, repeat_type, favorite, sort_order,
+             created_at, updated_at, completed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, (SELECT id FROM projects WHERE id = ?6), ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14,
+             ?15, ?16, ?17)
+             ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title, description = excluded.description, status = excluded.status,
+                priority = excluded.priority, project_id = excluded.project_id, due_date = excluded.due_date,
+                due_tz = excluded.due_tz, start_date = excluded.start_date,
+                estimate_minutes = excluded.estimate_minutes, reminder = excluded.reminder, repeat_type = excluded.repeat_type,
+                favorite = excluded.favorite,
+                sort_order = excluded.sort_order, created_at = excluded.created_at,
+                updated_at = excluded.updated_at, completed_at = excluded.completed_at",
+            params![
+                task.id,
+                task.title,
+                task.description,
+                task.status.as_str(),
+                task.priority.as_str(),
+                task.project_id,
+                due_date,
+                due_tz.flatten(),
+                task.start_date,
+                task.estimate_minutes,
+                task.reminder,
+                task.repeat.as_str(),
+                task.favorite as i32,
+                task.sort_order,
+                task.created_at,
+                task.updated_at,
+                task.completed_at,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+
+        conn.execute("DELETE FROM subtasks WHERE task_id = ?1", params![task.id])
+            .map_err(|e| e.to_string())?;
+        for (i, sub) in task.subtasks.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO subtasks (id, task_id, title, completed, sort_order) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![sub.id, task.id, sub.title, sub.completed as i32, i as i32],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        conn.execute("DELETE FROM task_tags WHERE task_id = ?1", params![task.id])
+            .map_err(|e| e.to_string())?;
+        for tag_id in &task.tags {
+            conn.execute(
+                "INSERT OR IGNORE INTO task_tags (task_id, tag_id) SELECT ?1, id FROM tags WHERE id = ?2",
+                params![task.id, tag_id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        match before {
+            Some(before) => task_event_repo::record_diff(conn, &before, &get_by_id(conn, &task.id)?),
+            None => task_event_repo::record(conn, &task.id, "created", None, None, Some(&task.title)),
+        }
+    })
+}
+
+/// 重新排序任务
+pub fn reorder(conn: &Connection, ordered_ids: &[String]) -> Result<(), String> {
+    transaction(conn, || {
+        for (i, id) in ordered_ids.iter().enumerate() {
+            conn.execute(
+                "UPDATE tasks SET sort_order = ?1 WHERE id = ?2",
+                params![i as i32, id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    })
+}
+
+/// 获取任务统计数据。“今天”“本周”按用户时区下的日期计算：
+/// 今天到期的，以及 7 天内到期（含已过期）的未完成任务。
+/// 开始日期未到的任务默认只计入“已计划”
+pub fn get_counts(conn: &Connection) -> Result<TaskCounts, String> {
+    let tz = settings_repo::timezone(conn);
+    let today = chrono::Utc::now().with_timezone(&tz).date_naive();
+    let week_end = today + chrono::Duration::days(7);
+    let show_deferred = settings_repo::show_deferred(conn);
+    // 开始日期按 YYYY-MM-DD 存储，可直接按字符串比较
+    let visible = if show_deferred { "1" } else { "(start_date IS NULL OR start_date <= ?1)" };
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT due_date, due_tz, estimate_minutes FROM tasks
+             WHERE due_date IS NOT NULL AND status != 'completed' AND {}",
+            visible
+        ))
+        .map_err(|e| e.to_string())?;
+    let params: &[&dyn ToSql] = if show_deferred { &[] } else { &[&today] };
+    // （到期日期, 预估分钟）
+    let dates: Vec<(NaiveDate, u32)> = stmt
+        .query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<u32>>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .filter_map(|(date, due_tz, estimate)| {
+            let due = Due::from_db(&date, due_tz.as_deref(), tz)?;
+            Some((due.date_in(tz), estimate.unwrap_or(0)))
+        })
+        .collect();
+    let today_count = dates.iter().filter(|(d, _)| *d == today).count();
+    let week_count = dates.iter().filter(|(d, _)| *d <= week_end).count();
+    let today_estimate_minutes = dates.iter().filter(|(d, _)| *d == today).map(|(_, m)| m).sum();
+
+    let scheduled_count: usize = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tasks WHERE status != 'completed' AND start_date > ?1",
+            params![today],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let all_count: usize = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM tasks WHERE status != 'completed' AND {}", visible),
+            params,
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let completed_count: usize = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tasks WHERE status = 'completed'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let favorite_count: usize = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM tasks WHERE favorite = 1 AND status != 'completed' AND {}", visible),
+            params,
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(TaskCounts {
+        today_count,
+        week_count,
+        scheduled_count,
+        today_estimate_minutes,
+        all_count,
+        completed_count,
+        favorite_count,
+    })
+}
+
+// ========== 子任务操作 ==========
+
+/// 获取任务的所有子任务
+pub fn get_subtasks(conn: &Connection, task_id: &str) -> Result<Vec<SubTask>, String> {
+    let mut stmt = conn
+        .prepare("SELECT id, title, completed FROM subtasks WHERE task_id = ?1 ORDER BY sort_order ASC")
+        .map_err(|e| e.to_string())?;
+
+    let subtasks = stmt
+        .query_map(params![task_id], |row| {
+            Ok(SubTask {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                completed: row.get::<_, i32>(2)? != 0,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(subtasks)
+}
+
+/// 添加子任务
+pub fn add_subtask(conn: &Connection, id: &str, task_id: &str, title: &str) -> Result<SubTask, String> {
+    transaction(conn, || {
+        let max_order: i32 = conn
+            .query_row(
+                "SELECT COALESCE(MAX(sort_order), -1) FROM subtasks WHERE task_id = ?1",
+                params![task_id],
+                |row| row.get(0),
+            )
+            .unwrap_or(-1);
+
+        conn.execute(
+            "INSERT INTO subtasks (id, task_id, title, completed, sort_order) VALUES (?1, ?2, ?3, 0, ?4)",
+            params![id, task_id, title, max_order + 1],
+        )
+        .map_err(|e| e.to_string())?;
+        task_event_repo::record(conn, task_id, "subtask_added", Some(id), None, Some(title))?;
+
+        // 更新任务的 updated_at
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        conn.execute("UPDATE tasks SET updated_at = ?1 WHERE id = ?2", params![&now, task_id])
+            .map_err(|e| e.to_string())?;
+
+        Ok(SubTask {
+            id: id.to_string(),
+            title: title.to_string(),
+            completed: false,
+        })
+    })
+}
+
+/// 切换子任务完成状态
+pub fn toggle_subtask(conn: &Connection, task_id: &str, subtask_id: &str) -> Result<(), String> {
+    transaction(conn, || {
+        conn.execute(
+            "UPDATE subtasks SET completed = CASE WHEN completed = 0 THEN 1 ELSE 0 END WHERE id = ?1 AND task_id = ?2",
+            params![subtask_id, task_id],
+        )
+        .map_err(|e| e.to_string())?;
+        let (title, completed) = subtask_state(conn, task_id, subtask_id)?;
+        let kind = if completed { "subtask_completed" } else { "subtask_reopened" };
+        task_event_repo::record(conn, task_id, kind, Some(subtask_id), None, Some(&title))?;
+
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        conn.execute("UPDATE tasks SET updated_at = ?1 WHERE id = ?2", params![&now, task_id])
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    })
+}
+
+/// 更新子任务标题
+pub fn update_subtask(conn: &Connection, task_id: &str, subtask_id: &str, title: &str) -> Result<(), String> {
+    transaction(conn, || {
+        let (old_title, _) = subtask_state(conn, task_id, subtask_id)?;
+        conn.execute(
+            "UPDATE subtasks SET title = ?1 WHERE id = ?2 AND task_id = ?3",
+            params![title, subtask_id, task_id],
+        )
+        .map_err(|e| e.to_string())?;
+        if old_title != title {
+            task_event_repo::record(conn, task_id, "subtask_renamed", Some(subtask_id), Some(&old_title), Some(title))?;
+        }
+
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        conn.execute("UPDATE tasks SET updated_at = ?1 WHERE id = ?2", params![&now, task_id])
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    })
+}
+
+/// 删除子任务
+pub fn delete_subtask(conn: &Connection, task_id: &str, subtask_id: &str) -> Result<(), String> {
+    transaction(conn, || {
+        let (title, _) = subtask_state(conn, task_id, subtask_id)?;
+        conn.execute(
+            "DELETE FROM subtasks WHERE id = ?1 AND task_id = ?2",
+            params![subtask_id, task_id],
+        )
+        .map_err(|e| e.to_string())?;
+        task_event_repo::record(conn, task_id, "subtask_deleted", Some(subtask_id), Some(&title), None)?;
+
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        conn.execute("UPDATE tasks SET updated_at = ?1 WHERE id = ?2", params![&now, task_id])
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    })
+}
+
+/// 子任务的标题与完成状态
+fn subtask_state(conn: &Connection, task_id: &str, subtask_id: &str) -> Result<(String, bool), String> {
+    conn.query_row(
+        "SELECT title, completed FROM subtasks WHERE id = ?1 AND task_id = ?2",
+        params![subtask_id, task_id],
+        |row| Ok((row.get(0)?, row.get::<_, i32>(1)? != 0)),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// ========== 标签关联查询 ==========
+
+/// 获取任务关联的标签ID列表
+pub fn get_task_tags(conn: &Connection, task_id: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT tag_id FROM task_tags WHERE task_id = ?1")
+        .map_err(|e| e.to_string())?;
+
+    let tags = stmt
+        .query_map(params![task_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(tags)
+}
@@ -0,0 +1,265 @@
+use crate::db::connection::transaction;
+use crate::models::time_entry::{TimeEntry, TimeEntrySource, TimeTotal, TimeTotals};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+/// 计时时长（秒）：已结束的取记录值，正在计时的算到现在
+const ELAPSED_SECONDS: &str = "COALESCE(e.seconds, CAST(strftime('%s', 'now') AS INTEGER) - CAST(strftime('%s', e.started_at) AS INTEGER))";
+
+fn row_to_entry(row: &Row) -> rusqlite::Result<TimeEntry> {
+    Ok(TimeEntry {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        started_at: row.get(2)?,
+        ended_at: row.get(3)?,
+        seconds: row.get(4)?,
+        source: TimeEntrySource::from_str(&row.get::<_, String>(5)?),
+    })
+}
+
+/// 正在进行的计时
+pub fn running(conn: &Connection) -> Result<Option<TimeEntry>, String> {
+    conn.query_row(
+        "SELECT id, task_id, started_at, ended_at, seconds, source FROM time_entries WHERE ended_at IS NULL",
+        [],
+        row_to_entry,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// 开始为任务计时。已有计时在进行时先结束它，返回（被结束的计时，新计时）
+pub fn start(conn: &Connection, task_id: &str) -> Result<(Option<TimeEntry>, TimeEntry), String> {
+    transaction(conn, || {
+        let exists: bool = conn
+            .query_row("SELECT COUNT(*) > 0 FROM tasks WHERE id = ?1", params![task_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        if !exists {
+            return Err(format!("任务不存在: {}", task_id));
+        }
+
+        let stopped = stop(conn)?;
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        conn.execute(
+            "INSERT INTO time_entries (task_id, started_at) VALUES (?1, ?2)",
+            params![task_id, &now],
+        )
+        .map_err(|e| e.to_string())?;
+        let started = get_by_id(conn, conn.last_insert_rowid())?;
+        Ok((stopped, started))
+    })
+}
+
+/// 结束正在进行的计时，没有时返回 None
+pub fn stop(conn: &Connection) -> Result<Option<TimeEntry>, String> {
+    let Some(entry) = running(conn)? else {
+        return Ok(None);
+    };
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    conn.execute(
+        "UPDATE time_entries
+         SET ended_at = ?1,
+             seconds = MAX(0, CAST(strftime('%s', ?1) AS INTEGER) - CAST(strftime('%s', started_at) AS INTEGER))
+         WHERE id = ?2",
+        params![&now, entry.id],
+    )
+    .map_err(|e| e.to_string())?;
+    get_by_id(conn, entry.id).map(Some)
+}
+
+/// 记录一段已结束的计时（如完成的番茄钟），不影响正在进行的计时
+pub fn log(
+    conn: &Connection,
+    task_id: &str,
+    started_at: &str,
+    ended_at: &str,
+    seconds: i64,
+    source: TimeEntrySource,
+) -> Result<TimeEntry, String> {
+    conn.execute(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, seconds, source) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![task_id, started_at, ended_at, seconds, source.as_str()],
+    )
+    .map_err(|e| e.to_string())?;
+    get_by_id(conn, conn.last_insert_rowid())
+}
+
+/// 根据ID获取计时记录
+pub fn get_by_id(conn: &Connection, id: i64) -> Result<TimeEntry, String> {
+    conn.query_row(
+        "SELECT id, task_id, started_at, ended_at, seconds, source FROM time_entries WHERE id = ?1",
+        params![id],
+        row_to_entry,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// 获取任务的计时记录，最近的在前
+pub fn get_by_task(conn: &Connection, task_id: &str) -> Result<Vec<TimeEntry>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, task_id, started_at, ended_at, seconds, source
+             FROM time_entries WHERE task_id = ?1 ORDER BY started_at DESC, id DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let entries = stmt
+        .query_map(params![task_id], row_to_entry)
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(entries)
+}
+
+/// 按原ID写回计时记录（撤销删除任务时）
+pub fn restore(conn: &Connection, entry: &TimeEntry) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO time_entries (id, task_id, started_at, ended_at, seconds, source)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            entry.id,
+            entry.task_id,
+            entry.started_at,
+            entry.ended_at,
+            entry.seconds,
+            entry.source.as_str()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 删除计时记录
+pub fn delete(conn: &Connection, id: i64) -> Result<(), String> {
+    conn.execute("DELETE FROM time_entries WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 按任务、项目和标签汇总预估与实际用时，只列出有预估或有记录的
+pub fn totals(conn: &Connection) -> Result<TimeTotals, String> {
+    let per_task = format!(
+        "WITH per_task AS (
+             SELECT t.id, t.title, t.project_id,
+                    COALESCE(t.estimate_minutes, 0) AS estimate,
+                    COALESCE(SUM({}), 0) AS tracked
+             FROM tasks t LEFT JOIN time_entries e ON e.task_id = t.id
+             GROUP BY t.id
+         )",
+        ELAPSED_SECONDS
+    );
+    let query = |sql: &str| -> Result<Vec<TimeTotal>, String> {
+        let mut stmt = conn
+            .prepare(&format!("{} {}", per_task, sql))
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(TimeTotal {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    estimate_minutes: row.get(2)?,
+                    tracked_seconds: row.get(3)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    };
+
+    Ok(TimeTotals {
+        tasks: query(
+            "SELECT id, title, estimate, tracked FROM per_task
+             WHERE estimate > 0 OR tracked > 0
+             ORDER BY tracked DESC, estimate DESC",
+        )?,
+        projects: query(
+            "SELECT pt.project_id, COALESCE(p.name, '无项目'), SUM(pt.estimate), SUM(pt.tracked)
+             FROM per_task pt LEFT JOIN projects p ON p.id = pt.project_id
+             GROUP BY pt.project_id
+             HAVING SUM(pt.estimate) > 0 OR SUM(pt.tracked) > 0
+             ORDER BY SUM(pt.tracked) DESC, SUM(pt.estimate) DESC",
+        )?,
+        tags: query(
+            "SELECT g.id, g.name, SUM(pt.estimate), SUM(pt.tracked)
+             FROM per_task pt
+             JOIN task_tags tt ON tt.task_id = pt.id
+             JOIN tags g ON g.id = tt.tag_id
+             GROUP BY g.id
+             HAVING SUM(pt.estimate) > 0 OR SUM(pt.tracked) > 0
+             ORDER BY SUM(pt.tracked) DESC, SUM(pt.estimate) DESC",
+        )?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{connection, project_repo, tag_repo, task_repo};
+
+    fn create(conn: &Connection, id: &str, project: Option<&str>, estimate: Option<u32>, tags: &[&str]) {
+        let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+        task_repo::create(conn, id, id, "medium", project, None, None, estimate, "none", &tags).unwrap();
+    }
+
+    fn finished(conn: &Connection, task_id: &str, started_at: &str, ended_at: &str, seconds: i64) {
+        conn.execute(
+            "INSERT INTO time_entries (task_id, started_at, ended_at, seconds) VALUES (?1, ?2, ?3, ?4)",
+            params![task_id, started_at, ended_at, seconds],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn start_replaces_running_timer() {
+        let conn = connection::memory();
+        create(&conn, "a", None, None, &[]);
+        create(&conn, "b", None, None, &[]);
+        let (stopped, first) = start(&conn, "a").unwrap();
+        assert!(stopped.is_none());
+        let (stopped, second) = start(&conn, "b").unwrap();
+        let stopped = stopped.unwrap();
+        assert_eq!(stopped.id, first.id);
+        assert!(stopped.ended_at.is_some() && stopped.seconds.is_some());
+        assert_eq!(running(&conn).unwrap().unwrap().id, second.id);
+        assert!(start(&conn, "missing").is_err());
+        assert_eq!(running(&conn).unwrap().unwrap().id, second.id);
+    }
+
+    #[test]
+    fn totals_by_task_project_and_tag() {
+        let conn = connection::memory();
+        project_repo::create(&conn, "p1", "工作", "#3b82f6", "briefcase").unwrap();
+        tag_repo::create(&conn, "t1", "深度", "#ef4444").unwrap();
+        create(&conn, "a", Some("p1"), Some(30), &["t1"]);
+        create(&conn, "b", Some("p1"), None, &[]);
+        create(&conn, "c", None, Some(10), &["t1"]);
+        create(&conn, "d", None, None, &[]);
+        finished(&conn, "a", "2026-10-19T01:00:00Z", "2026-10-19T01:10:00Z", 600);
+        finished(&conn, "a", "2026-10-19T02:00:00Z", "2026-10-19T02:05:00Z", 300);
+        // 一小时前开始、仍在进行的计时算到现在
+        let hour_ago = (chrono::Utc::now() - chrono::Duration::hours(1))
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string();
+        conn.execute("INSERT INTO time_entries (task_id, started_at) VALUES ('b', ?1)", params![hour_ago])
+            .unwrap();
+
+        let totals = totals(&conn).unwrap();
+        let tasks: Vec<(&str, i64)> = totals
+            .tasks
+            .iter()
+            .map(|t| (t.id.as_deref().unwrap(), t.estimate_minutes))
+            .collect();
+        assert_eq!(tasks, vec![("b", 0), ("a", 30), ("c", 10)]);
+        assert!((3599..=3601).contains(&totals.tasks[0].tracked_seconds));
+        assert_eq!(totals.tasks[1].tracked_seconds, 900);
+
+        assert_eq!(totals.projects.len(), 2);
+        assert_eq!(totals.projects[0].id.as_deref(), Some("p1"));
+        assert_eq!(totals.projects[0].estimate_minutes, 30);
+        assert_eq!(totals.projects[1].id, None);
+        assert_eq!(totals.projects[1].name, "无项目");
+        assert_eq!(totals.projects[1].estimate_minutes, 10);
+
+        assert_eq!(totals.tags.len(), 1);
+        assert_eq!(totals.tags[0].name, "深度");
+        assert_eq!(totals.tags[0].estimate_minutes, 40);
+        assert_eq!(totals.tags[0].tracked_seconds, 900);
+    }
+}
@@ -0,0 +1,8 @@
+//! 干了么核心库：数据模型、数据库访问与业务逻辑，不依赖 Tauri
+
+pub mod db;
+pub mod models;
+pub mod services;
+mod store;
+
+pub use store::TaskStore;
@@ -0,0 +1,46 @@
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use crate::models::time_entry::TimeEntry;
+use serde::Serialize;
+
+/// 数据变更事件，由 TaskStore 在每次写操作后发出
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ChangeEvent {
+    TaskCreated(Task),
+    TaskUpdated(Task),
+    TaskDeleted(Deleted),
+    ProjectCreated(Project),
+    ProjectUpdated(Project),
+    ProjectDeleted(Deleted),
+    TagCreated(Tag),
+    TagUpdated(Tag),
+    TagDeleted(Deleted),
+    /// 开始或结束计时，载荷为正在进行的计时（已结束为 null）
+    TimerChanged(Option<TimeEntry>),
+}
+
+/// 删除事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct Deleted {
+    pub id: String,
+}
+
+impl ChangeEvent {
+    /// 事件名，前端按此监听
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeEvent::TaskCreated(_) => "task-created",
+            ChangeEvent::TaskUpdated(_) => "task-updated",
+            ChangeEvent::TaskDeleted(_) => "task-deleted",
+            ChangeEvent::ProjectCreated(_) => "project-created",
+            ChangeEvent::ProjectUpdated(_) => "project-updated",
+            ChangeEvent::ProjectDeleted(_) => "project-deleted",
+            ChangeEvent::TagCreated(_) => "tag-created",
+            ChangeEvent::TagUpdated(_) => "tag-updated",
+            ChangeEvent::TagDeleted(_) => "tag-deleted",
+            ChangeEvent::TimerChanged(_) => "timer-changed",
+        }
+    }
+}
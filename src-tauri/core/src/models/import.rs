@@ -0,0 +1,46 @@
+use super::task::{Priority, RepeatType};
+use serde::{Deserialize, Serialize};
+
+/// 导入预览中的子任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSubTask {
+    pub title: String,
+    pub completed: bool,
+}
+
+/// 导入预览中的任务（项目、标签以名称表示，提交时再匹配或创建）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportTask {
+    pub title: String,
+    pub description: String,
+    pub priority: Priority,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    pub due_date: Option<String>,
+    pub repeat: RepeatType,
+    pub completed: bool,
+    pub completed_at: Option<String>,
+    pub subtasks: Vec<ImportSubTask>,
+}
+
+/// 导入预览
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreview {
+    pub projects: Vec<String>,
+    pub tags: Vec<String>,
+    pub tasks: Vec<ImportTask>,
+    pub warnings: Vec<String>,
+}
+
+/// 导入结果统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub projects: usize,
+    pub tags: usize,
+    pub tasks: usize,
+    pub subtasks: usize,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// 修复的孤立数据条数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanRepair {
+    /// 指向已删除任务或标签的任务-标签关联
+    pub task_tags: usize,
+    /// 所属任务已不存在的子任务
+    pub subtasks: usize,
+    /// 所属任务已不存在的变更记录
+    pub task_events: usize,
+    /// 所属项目已删除、已移出项目的任务
+    pub task_projects: usize,
+    /// 所属任务已不存在的计时记录
+    #[serde(default)]
+    pub time_entries: usize,
+}
+
+impl OrphanRepair {
+    pub fn total(&self) -> usize {
+        self.task_tags + self.subtasks + self.task_events + self.task_projects + self.time_entries
+    }
+}
+
+/// 数据库维护结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceReport {
+    /// integrity_check 报告的问题，为空表示完好
+    pub integrity_errors: Vec<String>,
+    /// foreign_key_check 发现的外键问题（修复前）
+    pub foreign_key_errors: Vec<String>,
+    pub repaired: OrphanRepair,
+    /// 整理前后的数据库大小（字节）
+    pub size_before: u64,
+    pub size_after: u64,
+    pub checked_at: String,
+}
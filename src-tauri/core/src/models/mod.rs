@@ -0,0 +1,13 @@
+pub mod event;
+pub mod import;
+pub mod maintenance;
+pub mod pomodoro;
+pub mod project;
+pub mod seed;
+pub mod settings;
+pub mod stats;
+pub mod tag;
+pub mod task;
+pub mod task_event;
+pub mod time_entry;
+pub mod workspace;
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+/// 番茄钟时长设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PomodoroSettings {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// 每完成几轮专注后长休息一次
+    pub long_break_every: u32,
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_every: 4,
+        }
+    }
+}
+
+/// 番茄钟阶段
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PomodoroPhase {
+    Idle,
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PomodoroPhase::Idle => "未开始",
+            PomodoroPhase::Work => "专注",
+            PomodoroPhase::ShortBreak => "短休息",
+            PomodoroPhase::LongBreak => "长休息",
+        }
+    }
+}
+
+/// 番茄钟当前状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PomodoroStatus {
+    pub phase: PomodoroPhase,
+    /// 专注的任务，未关联任务时为空
+    pub task_id: Option<String>,
+    /// 本组已完成的专注轮数（长休息后归零）
+    pub round: u32,
+    /// 当前阶段剩余秒数
+    pub remaining_seconds: i64,
+    /// 当前阶段结束时间（UTC），暂停或未开始时为空
+    pub ends_at: Option<String>,
+    pub paused: bool,
+    pub settings: PomodoroSettings,
+}
@@ -0,0 +1,87 @@
+use super::task::{Priority, RepeatType};
+use serde::{Deserialize, Serialize};
+
+/// 首次启动时的初始数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SeedChoice {
+    /// 空白开始
+    Empty,
+    /// 内置示例数据（之后可一键删除）
+    Sample,
+    /// 从 JSON 模板包导入
+    Template { content: String },
+}
+
+impl SeedChoice {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeedChoice::Empty => "empty",
+            SeedChoice::Sample => "sample",
+            SeedChoice::Template { .. } => "template",
+        }
+    }
+}
+
+/// 模板包中的项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedProject {
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+}
+
+/// 模板包中的标签
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedTag {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+/// 模板包中的任务（项目、标签以名称表示）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedTask {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub priority: Option<Priority>,
+    pub project: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 截止日期相对导入当天的天数，0 为今天
+    pub due_in_days: Option<i64>,
+    /// 开始日期相对导入当天的天数
+    pub start_in_days: Option<i64>,
+    /// 预估用时（分钟）
+    pub estimate_minutes: Option<u32>,
+    pub repeat: Option<RepeatType>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub subtasks: Vec<String>,
+}
+
+/// 模板包（JSON），示例数据也使用同样的格式
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedPack {
+    #[serde(default)]
+    pub projects: Vec<SeedProject>,
+    #[serde(default)]
+    pub tags: Vec<SeedTag>,
+    #[serde(default)]
+    pub tasks: Vec<SeedTask>,
+}
+
+/// 初始数据状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupStatus {
+    /// 尚未选择初始数据（新数据库）
+    pub needs_setup: bool,
+    /// 是否还有未删除的示例数据
+    pub has_sample_data: bool,
+}
@@ -0,0 +1,55 @@
+use super::pomodoro::PomodoroSettings;
+use serde::{Deserialize, Serialize};
+
+/// 本地 HTTP API 默认端口
+pub const DEFAULT_API_PORT: u16 = 17890;
+
+/// 快速添加窗口的默认全局快捷键
+pub const DEFAULT_QUICK_ADD_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+
+/// 无操作多少分钟后自动锁定
+pub const DEFAULT_APP_LOCK_MINUTES: u32 = 5;
+
+/// 应用设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    pub is_dark: bool,
+    /// todo.txt 双向同步文件路径，为空表示未开启
+    pub todotxt_path: Option<String>,
+    /// 是否开启本地 HTTP API（默认关闭）
+    pub api_enabled: bool,
+    pub api_port: u16,
+    /// HTTP API 访问令牌
+    pub api_token: Option<String>,
+    /// 呼出快速添加窗口的全局快捷键
+    pub quick_add_shortcut: String,
+    /// 是否设置了应用锁密码
+    pub app_lock_enabled: bool,
+    /// 无操作自动锁定的分钟数
+    pub app_lock_minutes: u32,
+    /// 用户所在时区（IANA 名称），决定“今天”“本周”和显示的钟点
+    pub timezone: String,
+    /// 智能列表是否显示开始日期未到的任务
+    pub show_deferred: bool,
+    /// 番茄钟时长
+    pub pomodoro: PomodoroSettings,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            is_dark: false,
+            todotxt_path: None,
+            api_enabled: false,
+            api_port: DEFAULT_API_PORT,
+            api_token: None,
+            quick_add_shortcut: DEFAULT_QUICK_ADD_SHORTCUT.to_string(),
+            app_lock_enabled: false,
+            app_lock_minutes: DEFAULT_APP_LOCK_MINUTES,
+            timezone: "UTC".to_string(),
+            show_deferred: false,
+            pomodoro: PomodoroSettings::default(),
+        }
+    }
+}
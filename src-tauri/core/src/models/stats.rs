@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// 完成数的统计周期
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsPeriod {
+    Day,
+    /// 周从周一开始
+    Week,
+    Month,
+}
+
+/// 一个周期内完成的任务数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionPoint {
+    /// 周期第一天（YYYY-MM-DD，用户时区）
+    pub start: String,
+    pub completed: i64,
+}
+
+/// 按标签、项目或优先级分组的任务数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Distribution {
+    /// 标签、项目ID或优先级，未归入项目的任务为空
+    pub id: Option<String>,
+    pub name: String,
+    pub total: i64,
+    pub completed: i64,
+}
+
+/// 统计概览
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSummary {
+    pub total: i64,
+    pub completed: i64,
+    /// 完成率（0 到 1），没有任务时为 0
+    pub completion_rate: f64,
+    /// 截至今天（今天还没完成任务时截至昨天）连续有完成任务的天数
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    /// 从创建到完成的平均小时数，没有完成的任务时为空
+    pub average_lead_hours: Option<f64>,
+    /// 逾期的任务数：晚于截止时间完成，或已过截止时间仍未完成
+    pub overdue: i64,
+    /// 逾期任务占已到期任务（已完成或已过截止时间）的比例
+    pub overdue_rate: f64,
+    pub by_tag: Vec<Distribution>,
+    pub by_project: Vec<Distribution>,
+    pub by_priority: Vec<Distribution>,
+}
@@ -0,0 +1,413 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// 任务优先级
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    High,
+    Medium,
+    Low,
+}
+
+impl Priority {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "high" => Priority::High,
+            "low" => Priority::Low,
+            _ => Priority::Medium,
+        }
+    }
+}
+
+/// 任务状态
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Todo,
+    InProgress,
+    Completed,
+}
+
+impl TaskStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskStatus::Todo => "todo",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Completed => "completed",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "in_progress" => TaskStatus::InProgress,
+            "completed" => TaskStatus::Completed,
+            _ => TaskStatus::Todo,
+        }
+    }
+}
+
+/// 重复类型
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RepeatType {
+    Daily,
+    Weekly,
+    Monthly,
+    Custom,
+    None,
+}
+
+impl RepeatType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RepeatType::Daily => "daily",
+            RepeatType::Weekly => "weekly",
+            RepeatType::Monthly => "monthly",
+            RepeatType::Custom => "custom",
+            RepeatType::None => "none",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "daily" => RepeatType::Daily,
+            "weekly" => RepeatType::Weekly,
+            "monthly" => RepeatType::Monthly,
+            "custom" => RepeatType::Custom,
+            _ => RepeatType::None,
+        }
+    }
+}
+
+/// 截止时间：全天（只有日期）或某个时刻。
+/// 时刻以 UTC 保存并记下设定时的时区，出差或夏令时切换后仍指向同一时刻；
+/// 全天任务没有时区，在用户当前所在时区的这一天内都算当天
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Due {
+    AllDay { date: NaiveDate },
+    At { instant: DateTime<Utc>, tz: Tz },
+}
+
+impl Due {
+    /// 解析截止时间：YYYY-MM-DD 为全天；YYYY-MM-DD HH:MM 与 YYYY-MM-DDTHH:MM[:SS] 是 tz 下的钟点；
+    /// 带偏移的 RFC 3339 时间（如 2026-10-19T09:00:00Z）直接作为时刻
+    pub fn parse(input: &str, tz: Tz) -> Result<Due, String> {
+        let input = input.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+            return Ok(Due::AllDay { date });
+        }
+        if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+            return Ok(Due::At {
+                instant: dt.with_timezone(&Utc),
+                tz,
+            });
+        }
+        for fmt in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(input, fmt) {
+                return Due::at_local(naive, tz).ok_or_else(|| format!("无法识别的时间: {}", input));
+            }
+        }
+        Err(format!("无法识别的日期: {}", input))
+    }
+
+    /// tz 下的钟点。夏令时重复的钟点取较早的一个，跳过的钟点顺延一小时
+    pub fn at_local(naive: NaiveDateTime, tz: Tz) -> Option<Due> {
+        resolve_local(naive, tz).map(|instant| Due::At { instant, tz })
+    }
+
+    /// 从数据库读取：due_tz 为空时是全天日期，否则是 UTC 时刻。
+    /// 旧版本保存的本地钟点（没有时区）按 fallback 时区解释
+    pub fn from_db(value: &str, tz_name: Option<&str>, fallback: Tz) -> Option<Due> {
+        match tz_name {
+            Some(name) => Some(Due::At {
+                instant: DateTime::parse_from_rfc3339(value).ok()?.with_timezone(&Utc),
+                tz: name.parse().unwrap_or(fallback),
+            }),
+            None => Due::parse(value, fallback).ok(),
+        }
+    }
+
+    /// 写入数据库的 (due_date, due_tz)
+    pub fn to_db(&self) -> (String, Option<String>) {
+        match self {
+            Due::AllDay { date } => (date.format("%Y-%m-%d").to_string(), None),
+            Due::At { instant, tz } => (
+                instant.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                Some(tz.name().to_string()),
+            ),
+        }
+    }
+
+    /// 在 tz 下是哪一天
+    pub fn date_in(&self, tz: Tz) -> NaiveDate {
+        match self {
+            Due::AllDay { date } => *date,
+            Due::At { instant, .. } => instant.with_timezone(&tz).date_naive(),
+        }
+    }
+
+    /// tz 下的显示形式：YYYY-MM-DD 或 YYYY-MM-DDTHH:mm（与前端一致）
+    pub fn display(&self, tz: Tz) -> String {
+        match self {
+            Due::AllDay { date } => date.format("%Y-%m-%d").to_string(),
+            Due::At { instant, .. } => instant.with_timezone(&tz).format("%Y-%m-%dT%H:%M").to_string(),
+        }
+    }
+
+    /// 到期时刻，全天任务为 tz 下当天 23:59:59
+    pub fn deadline(&self, tz: Tz) -> DateTime<Utc> {
+        match self {
+            Due::AllDay { date } => {
+                let end = date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default());
+                resolve_local(end, tz).unwrap_or_else(|| Utc.from_utc_datetime(&end))
+            }
+            Due::At { instant, .. } => *instant,
+        }
+    }
+
+    /// 顺延若干天：时刻按设定时区的钟点顺延，跨夏令时后仍是同一钟点
+    pub fn shift_days(&self, days: i64) -> Option<Due> {
+        self.map_date(|date| date.checked_add_signed(Duration::days(days)))
+    }
+
+    /// 按设定时区的日期换算（如下个月的同一天），钟点保持不变
+    pub fn map_date(&self, f: impl FnOnce(NaiveDate) -> Option<NaiveDate>) -> Option<Due> {
+        match self {
+            Due::AllDay { date } => f(*date).map(|date| Due::AllDay { date }),
+            Due::At { instant, tz } => {
+                let local = instant.with_timezone(tz).naive_local();
+                Due::at_local(f(local.date())?.and_time(local.time()), *tz)
+            }
+        }
+    }
+}
+
+/// 把 tz 下的钟点换算为 UTC 时刻
+fn resolve_local(naive: NaiveDateTime, tz: Tz) -> Option<DateTime<Utc>> {
+    let local = match tz.from_local_datetime(&naive) {
+        LocalResult::Single(t) => t,
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        LocalResult::None => tz.from_local_datetime(&(naive + Duration::hours(1))).earliest()?,
+    };
+    Some(local.with_timezone(&Utc))
+}
+
+/// 外部导入的时间（完成时间等）统一为数据库使用的 UTC 字符串。
+/// 带偏移的按偏移换算；没有偏移的钟点按 tz 解释；只有日期时取 tz 下当天 0 点
+pub fn to_utc_timestamp(input: &str, tz: Tz) -> Option<String> {
+    let input = input.trim();
+    let instant = if let Ok(dt) = DateTime::parse_from_rfc3339(input)
+        .or_else(|_| DateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S%.f%z"))
+    {
+        dt.with_timezone(&Utc)
+    } else if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        resolve_local(date.and_time(NaiveTime::MIN), tz)?
+    } else {
+        let naive = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|fmt| NaiveDateTime::parse_from_str(input, fmt).ok())?;
+        resolve_local(naive, tz)?
+    };
+    Some(instant.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// 子任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubTask {
+    pub id: String,
+    pub title: String,
+    pub completed: bool,
+}
+
+/// 任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub status: TaskStatus,
+    pub priority: Priority,
+    pub project_id: Option<String>,
+    pub tags: Vec<String>,
+    pub subtasks: Vec<SubTask>,
+    pub due: Option<Due>,
+    /// 截止时间在用户时区下的显示形式（YYYY-MM-DD 或 YYYY-MM-DDTHH:mm），随 due 生成
+    pub due_date: Option<String>,
+    /// 开始日期（用户时区下的日期），之前任务暂不出现在智能列表中
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    /// 预估用时（分钟）
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    /// 已结束的计时合计（秒），不含正在进行的计时
+    #[serde(default)]
+    pub tracked_seconds: i64,
+    pub reminder: Option<String>,
+    pub repeat: RepeatType,
+    pub favorite: bool,
+    pub sort_order: i32,
+    pub created_at: String,
+    pub updated_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl Task {
+    /// 开始日期还没到（today 为用户时区下的今天）
+    pub fn is_deferred(&self, today: NaiveDate) -> bool {
+        self.start_date.is_some_and(|start| start > today)
+    }
+}
+
+/// 任务统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCounts {
+    pub today_count: usize,
+    pub week_count: usize,
+    pub scheduled_count: usize,
+    /// 今天到期的未完成任务的预估用时合计（分钟）
+    pub today_estimate_minutes: u32,
+    pub all_count: usize,
+    pub completed_count: usize,
+    pub favorite_count: usize,
+}
+
+/// 新建任务参数
+#[derive(Debug, Clone)]
+pub struct NewTask {
+    pub title: String,
+    pub priority: String,
+    pub project_id: Option<String>,
+    pub due: Option<Due>,
+    pub start_date: Option<NaiveDate>,
+    pub estimate_minutes: Option<u32>,
+    pub repeat_type: String,
+    pub tag_ids: Vec<String>,
+}
+
+impl NewTask {
+    /// 仅指定标题，其余取默认值
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            priority: Priority::Medium.as_str().to_string(),
+            project_id: None,
+            due: None,
+            start_date: None,
+            estimate_minutes: None,
+            repeat_type: RepeatType::None.as_str().to_string(),
+            tag_ids: Vec::new(),
+        }
+    }
+}
+
+/// 任务部分更新（None 表示不修改，Some(None) 表示清空）
+#[derive(Debug, Clone, Default)]
+pub struct TaskPatch {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub project_id: Option<Option<String>>,
+    pub due: Option<Option<Due>>,
+    pub start_date: Option<Option<NaiveDate>>,
+    pub estimate_minutes: Option<Option<u32>>,
+    pub reminder: Option<Option<String>>,
+    pub repeat_type: Option<String>,
+    pub tag_ids: Option<Vec<String>>,
+}
+
+/// 撤销/重做状态：下一步可撤销、可重做操作的说明
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoState {
+    pub undo: Option<String>,
+    pub redo: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEW_YORK: Tz = chrono_tz::America::New_York;
+
+    fn local(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn parse_due_formats() {
+        let shanghai = chrono_tz::Asia::Shanghai;
+        assert_eq!(
+            Due::parse("2026-10-19", shanghai).unwrap(),
+            Due::AllDay { date: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap() }
+        );
+        let at = Due::parse("2026-10-19 09:30", shanghai).unwrap();
+        assert_eq!(at.to_db(), ("2026-10-19T01:30:00Z".to_string(), Some("Asia/Shanghai".to_string())));
+        assert_eq!(Due::parse("2026-10-19T09:30", shanghai).unwrap(), at);
+        assert_eq!(Due::parse("2026-10-19T01:30:00Z", shanghai).unwrap(), at);
+        assert!(Due::parse("明天", shanghai).is_err());
+    }
+
+    #[test]
+    fn db_round_trip() {
+        let due = Due::parse("2026-10-19 09:30", NEW_YORK).unwrap();
+        let (value, tz) = due.to_db();
+        assert_eq!(Due::from_db(&value, tz.as_deref(), chrono_tz::UTC), Some(due));
+        // 旧版本保存的本地钟点按 fallback 时区解释
+        let legacy = Due::from_db("2026-10-19T09:30", None, NEW_YORK).unwrap();
+        assert_eq!(legacy.display(NEW_YORK), "2026-10-19T09:30");
+    }
+
+    #[test]
+    fn dst_gap_and_overlap() {
+        // 2026-03-08 02:30 在纽约不存在，顺延到 03:30 EDT
+        let skipped = Due::at_local(local("2026-03-08 02:30"), NEW_YORK).unwrap();
+        assert_eq!(skipped.to_db().0, "2026-03-08T07:30:00Z");
+        // 2026-11-01 01:30 出现两次，取较早的 EDT
+        let repeated = Due::at_local(local("2026-11-01 01:30"), NEW_YORK).unwrap();
+        assert_eq!(repeated.to_db().0, "2026-11-01T05:30:00Z");
+    }
+
+    #[test]
+    fn shift_keeps_wall_clock_across_dst() {
+        let due = Due::at_local(local("2026-03-07 09:00"), NEW_YORK).unwrap();
+        let next = due.shift_days(1).unwrap();
+        assert_eq!(next.display(NEW_YORK), "2026-03-08T09:00");
+        assert_eq!(next.to_db().0, "2026-03-08T13:00:00Z");
+        assert_eq!(due.to_db().0, "2026-03-07T14:00:00Z");
+    }
+
+    #[test]
+    fn date_and_deadline_in_other_zone() {
+        let due = Due::parse("2026-10-19T23:30:00Z", NEW_YORK).unwrap();
+        assert_eq!(due.date_in(NEW_YORK), NaiveDate::from_ymd_opt(2026, 10, 19).unwrap());
+        assert_eq!(due.date_in(chrono_tz::Asia::Tokyo), NaiveDate::from_ymd_opt(2026, 10, 20).unwrap());
+        let all_day = Due::parse("2026-10-19", NEW_YORK).unwrap();
+        assert_eq!(all_day.deadline(NEW_YORK).to_rfc3339(), "2026-10-20T03:59:59+00:00");
+    }
+
+    #[test]
+    fn utc_timestamps() {
+        assert_eq!(to_utc_timestamp("2026-10-19T09:00:00+08:00", NEW_YORK).as_deref(), Some("2026-10-19T01:00:00Z"));
+        assert_eq!(to_utc_timestamp("2026-10-19T09:00:00.000+0800", NEW_YORK).as_deref(), Some("2026-10-19T01:00:00Z"));
+        assert_eq!(to_utc_timestamp("2026-10-19", NEW_YORK).as_deref(), Some("2026-10-19T04:00:00Z"));
+        assert_eq!(to_utc_timestamp("2026-10-19 09:00", NEW_YORK).as_deref(), Some("2026-10-19T13:00:00Z"));
+        assert_eq!(to_utc_timestamp("昨天", NEW_YORK), None);
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// 任务变更记录（一条对应一个字段的一次变化）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskEvent {
+    pub id: i64,
+    pub task_id: String,
+    /// created / updated / completed / reopened / subtask_added / subtask_completed /
+    /// subtask_reopened / subtask_renamed / subtask_deleted
+    pub kind: String,
+    /// 变化的字段（updated 时为 title、due_date 等；子任务事件为子任务ID）
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: String,
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// 一段计时记录，ended_at 为空表示正在计时（同一时间最多一条）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntry {
+    pub id: i64,
+    pub task_id: String,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    /// 计时时长（秒），正在计时时为空
+    pub seconds: Option<i64>,
+    pub source: TimeEntrySource,
+}
+
+/// 计时记录的来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeEntrySource {
+    /// 手动开始、结束的计时
+    Timer,
+    /// 完成的番茄钟专注
+    Pomodoro,
+}
+
+impl TimeEntrySource {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimeEntrySource::Timer => "timer",
+            TimeEntrySource::Pomodoro => "pomodoro",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "pomodoro" => TimeEntrySource::Pomodoro,
+            _ => TimeEntrySource::Timer,
+        }
+    }
+}
+
+/// 预估与实际用时的汇总（按任务、项目或标签）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeTotal {
+    /// 任务、项目或标签ID，未归入项目的任务为空
+    pub id: Option<String>,
+    pub name: String,
+    pub estimate_minutes: i64,
+    /// 已记录的秒数，含正在计时的部分
+    pub tracked_seconds: i64,
+}
+
+/// 用时汇总
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeTotals {
+    pub tasks: Vec<TimeTotal>,
+    pub projects: Vec<TimeTotal>,
+    pub tags: Vec<TimeTotal>,
+}
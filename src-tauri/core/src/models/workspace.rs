@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// 默认工作区（沿用原来的 ganle_me.db）
+pub const DEFAULT_WORKSPACE_ID: &str = "default";
+pub const DEFAULT_WORKSPACE_FILE: &str = "ganle_me.db";
+
+/// 工作区：每个工作区使用独立的数据库文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    /// 相对于应用数据目录的数据库文件路径
+    pub file: String,
+    pub created_at: String,
+}
+
+/// 工作区列表及当前使用的工作区（保存在 workspaces.json）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceList {
+    pub current: String,
+    pub workspaces: Vec<Workspace>,
+}
+
+impl Default for WorkspaceList {
+    fn default() -> Self {
+        Self {
+            current: DEFAULT_WORKSPACE_ID.to_string(),
+            workspaces: vec![Workspace {
+                id: DEFAULT_WORKSPACE_ID.to_string(),
+                name: "默认".to_string(),
+                file: DEFAULT_WORKSPACE_FILE.to_string(),
+                created_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            }],
+        }
+    }
+}
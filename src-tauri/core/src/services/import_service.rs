@@ -0,0 +1,809 @@
+use crate::db::{project_repo, settings_repo, tag_repo, task_repo};
+use crate::models::import::{ImportPreview, ImportSubTask, ImportSummary, ImportTask};
+use crate::models::task::{to_utc_timestamp, Due, Priority, RepeatType};
+use crate::services::outline_service;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// 解析外部应用的导出文件，生成导入预览（不写入数据库）
+/// source: todoist_csv / todoist_json / mstodo / ticktick_csv / org / opml
+pub fn preview(source: &str, content: &str, file_name: Option<&str>) -> Result<ImportPreview, String> {
+    // 去掉 Excel 导出常见的 BOM
+    let content = content.trim_start_matches('\u{feff}');
+    let mut preview = match source {
+        "todoist_csv" => parse_todoist_csv(content, file_name)?,
+        "todoist_json" => parse_todoist_json(content)?,
+        "mstodo" => parse_mstodo(content)?,
+        "ticktick_csv" => parse_ticktick_csv(content)?,
+        "org" => outline_service::parse_org(content)?,
+        "opml" => outline_service::parse_opml(content)?,
+        _ => return Err(format!("未知导入来源: {}", source)),
+    };
+    check_completed_at(&mut preview);
+    collect_names(&mut preview);
+    Ok(preview)
+}
+
+/// 将预览内容写入数据库，同名项目/标签会复用已有的
+pub fn commit(conn: &Connection, preview: &ImportPreview) -> Result<ImportSummary, String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let mut summary = ImportSummary::default();
+    // 导入文件中的钟点按用户时区解释
+    let tz = settings_repo::timezone(&tx);
+
+    let mut project_ids: HashMap<String, String> = project_repo::get_all(&tx)?
+        .into_iter()
+        .map(|p| (p.name, p.id))
+        .collect();
+    let mut tag_ids: HashMap<String, String> = tag_repo::get_all(&tx)?
+        .into_iter()
+        .map(|t| (t.name, t.id))
+        .collect();
+
+    for task in &preview.tasks {
+        let project_id = match &task.project {
+            Some(name) => Some(match project_ids.get(name) {
+                Some(id) => id.clone(),
+                None => {
+                    let id = format!("proj-{}", Uuid::new_v4());
+                    project_repo::create(&tx, &id, name, "#228B22", "briefcase")?;
+                    project_ids.insert(name.clone(), id.clone());
+                    summary.projects += 1;
+                    id
+                }
+            }),
+            None => None,
+        };
+
+        let mut task_tags = Vec::new();
+        for name in &task.tags {
+            let id = match tag_ids.get(name) {
+                Some(id) => id.clone(),
+                None => {
+                    let id = format!("tag-{}", Uuid::new_v4());
+                    tag_repo::create(&tx, &id, name, "#3B82F6")?;
+                    tag_ids.insert(name.clone(), id.clone());
+                    summary.tags += 1;
+                    id
+                }
+            };
+            if !task_tags.contains(&id) {
+                task_tags.push(id);
+            }
+        }
+
+        let id = format!("task-{}", Uuid::new_v4());
+        task_repo::create(
+            &tx,
+            &id,
+            &task.title,
+            task.priority.as_str(),
+            project_id.as_deref(),
+            task.due_date.as_deref().and_then(|d| Due::parse(d, tz).ok()).as_ref(),
+            None,
+            None,
+            task.repeat.as_str(),
+            &task_tags,
+        )?;
+        if !task.description.is_empty() {
+            task_repo::update(
+                &tx,
+                &id,
+                None,
+                Some(&task.description),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )?;
+        }
+        if task.completed {
+            let completed_at = task
+                .completed_at
+                .as_deref()
+                .and_then(|t| to_utc_timestamp(t, tz))
+                .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string());
+            task_repo::set_completion(&tx, &id, Some(&completed_at))?;
+        }
+        summary.tasks += 1;
+
+        for sub in &task.subtasks {
+            let sub_id = format!("sub-{}", Uuid::new_v4());
+            task_repo::add_subtask(&tx, &sub_id, &id, &sub.title)?;
+            if sub.completed {
+                task_repo::toggle_subtask(&tx, &id, &sub_id)?;
+            }
+            summary.subtasks += 1;
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(summary)
+}
+
+// ========== Todoist ==========
+
+/// Todoist 项目模板 CSV（每个文件对应一个项目，以文件名作为项目名）
+fn parse_todoist_csv(content: &str, file_name: Option<&str>) -> Result<ImportPreview, String> {
+    let project = file_name.and_then(file_stem);
+    let mut preview = ImportPreview::default();
+
+    let mut reader = csv_reader(content);
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let type_col = column(&headers, "TYPE");
+    let content_col = column(&headers, "CONTENT").ok_or("缺少 CONTENT 列")?;
+    let description_col = column(&headers, "DESCRIPTION");
+    let priority_col = column(&headers, "PRIORITY");
+    let indent_col = column(&headers, "INDENT");
+    let date_col = column(&headers, "DATE");
+
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+        let get = |i: Option<usize>| i.and_then(|i| record.get(i)).unwrap_or("").trim();
+
+        // section / note 行不是任务
+        let kind = get(type_col);
+        if !kind.is_empty() && kind != "task" {
+            continue;
+        }
+        let (title, tags) = split_labels(get(Some(content_col)));
+        if title.is_empty() {
+            continue;
+        }
+
+        let indent: usize = get(indent_col).parse().unwrap_or(1);
+        if indent > 1 {
+            if let Some(parent) = preview.tasks.last_mut() {
+                parent.subtasks.push(ImportSubTask {
+                    title,
+                    completed: false,
+                });
+                continue;
+            }
+        }
+
+        let date = get(date_col);
+        let (due_date, repeat) = parse_todoist_date(date);
+        if !date.is_empty() && due_date.is_none() && repeat == RepeatType::None {
+            preview.warnings.push(format!("「{}」的日期无法识别: {}", title, date));
+        }
+
+        // CSV 中 1 为最高优先级，4 为默认
+        let priority = match get(priority_col) {
+            "1" => Priority::High,
+            "3" => Priority::Low,
+            _ => Priority::Medium,
+        };
+
+        preview.tasks.push(ImportTask {
+            title,
+            description: get(description_col).to_string(),
+            priority,
+            project: project.clone(),
+            tags,
+            due_date,
+            repeat,
+            completed: false,
+            completed_at: None,
+            subtasks: Vec::new(),
+        });
+    }
+
+    Ok(preview)
+}
+
+/// Todoist 完整备份 JSON（Sync API 格式：projects / items / labels）
+fn parse_todoist_json(content: &str) -> Result<ImportPreview, String> {
+    let root: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut preview = ImportPreview::default();
+
+    let projects: HashMap<String, String> = array(&root["projects"])
+        .iter()
+        .map(|p| (value_string(&p["id"]), str_field(p, "name")))
+        .collect();
+    let labels: HashMap<String, String> = array(&root["labels"])
+        .iter()
+        .map(|l| (value_string(&l["id"]), str_field(l, "name")))
+        .collect();
+
+    let items = array(&root["items"]);
+    let parents: HashMap<String, String> = items
+        .iter()
+        .filter(|i| !i["parent_id"].is_null())
+        .map(|i| (value_string(&i["id"]), value_string(&i["parent_id"])))
+        .collect();
+
+    // 先导入顶层任务
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for item in items.iter().filter(|i| i["parent_id"].is_null()) {
+        let title = str_field(item, "content");
+        if title.is_empty() {
+            continue;
+        }
+
+        let tags = array(&item["labels"])
+            .iter()
+            .map(|l| match l {
+                Value::String(name) => name.clone(),
+                other => labels.get(&value_string(other)).cloned().unwrap_or_default(),
+            })
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        let due = &item["due"];
+        let due_date = due["date"].as_str().and_then(|d| normalize_due(d, !d.contains('T')));
+        let repeat = if due["is_recurring"].as_bool().unwrap_or(false) {
+            parse_every(due["string"].as_str().unwrap_or(""))
+        } else {
+            RepeatType::None
+        };
+
+        // API 中 4 为最高优先级（p1），1 为默认
+        let priority = match item["priority"].as_i64() {
+            Some(4) => Priority::High,
+            Some(2) => Priority::Low,
+            _ => Priority::Medium,
+        };
+
+        index.insert(value_string(&item["id"]), preview.tasks.len());
+        preview.tasks.push(ImportTask {
+            title,
+            description: str_field(item, "description"),
+            priority,
+            project: projects.get(&value_string(&item["project_id"])).cloned(),
+            tags,
+            due_date,
+            repeat,
+            completed: is_checked(&item["checked"]),
+            completed_at: item["completed_at"].as_str().map(|s| s.to_string()),
+            subtasks: Vec::new(),
+        });
+    }
+
+    // 多层子任务统一挂到最顶层任务下
+    'items: for item in items.iter().filter(|i| !i["parent_id"].is_null()) {
+        let mut root_id = value_string(&item["parent_id"]);
+        let mut visited = HashSet::from([value_string(&item["id"])]);
+        while let Some(parent) = parents.get(&root_id) {
+            if !visited.insert(root_id.clone()) {
+                preview
+                    .warnings
+                    .push(format!("子任务「{}」的父任务循环引用，已跳过", str_field(item, "content")));
+                continue 'items;
+            }
+            root_id = parent.clone();
+        }
+        match index.get(&root_id) {
+            Some(&i) => preview.tasks[i].subtasks.push(ImportSubTask {
+                title: str_field(item, "content"),
+                completed: is_checked(&item["checked"]),
+            }),
+            None => preview
+                .warnings
+                .push(format!("子任务「{}」找不到父任务", str_field(item, "content"))),
+        }
+    }
+
+    Ok(preview)
+}
+
+/// 解析 Todoist 的日期列：具体日期或 "every ..." 重复规则
+fn parse_todoist_date(value: &str) -> (Option<String>, RepeatType) {
+    if value.is_empty() {
+        return (None, RepeatType::None);
+    }
+    let lower = value.to_lowercase();
+    if lower.starts_with("every") || lower.starts_with('每') {
+        return (None, parse_every(&lower));
+    }
+    (normalize_due(value, !value.contains(['T', ' '])), RepeatType::None)
+}
+
+/// 解析 "every day" / "every monday" / "每周" 等自然语言重复规则
+fn parse_every(value: &str) -> RepeatType {
+    const WEEKDAYS: [&str; 14] = [
+        "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+        "mon", "tue", "wed", "thu", "fri", "sat", "sun",
+    ];
+    let value = value.trim().to_lowercase();
+    match value.as_str() {
+        "" => RepeatType::None,
+        "daily" | "every day" | "every 1 day" | "每天" | "每日" => RepeatType::Daily,
+        "weekly" | "every week" | "every 1 week" | "每周" => RepeatType::Weekly,
+        "monthly" | "every month" | "every 1 month" | "每月" => RepeatType::Monthly,
+        other => match other.strip_prefix("every ") {
+            Some(day) if WEEKDAYS.contains(&day) => RepeatType::Weekly,
+            _ => RepeatType::Custom,
+        },
+    }
+}
+
+// ========== Microsoft To Do ==========
+
+/// Microsoft To Do 导出 JSON（Graph API 格式：清单数组，每个清单含 tasks）
+fn parse_mstodo(content: &str) -> Result<ImportPreview, String> {
+    let root: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let lists = if root.is_array() {
+        array(&root)
+    } else if root["lists"].is_array() {
+        array(&root["lists"])
+    } else {
+        array(&root["value"])
+    };
+    if lists.is_empty() {
+        return Err("未找到任何清单".to_string());
+    }
+
+    let mut preview = ImportPreview::default();
+    for list in lists {
+        let list_name = str_field(list, "displayName");
+        for item in array(&list["tasks"]) {
+            let title = str_field(item, "title");
+            if title.is_empty() {
+                continue;
+            }
+
+            let priority = match item["importance"].as_str() {
+                Some("high") => Priority::High,
+                Some("low") => Priority::Low,
+                _ => Priority::Medium,
+            };
+
+            // To Do 的截止时间只有日期有意义，时间部分固定为 00:00
+            let due_date = item["dueDateTime"]["dateTime"]
+                .as_str()
+                .and_then(|d| normalize_due(d.get(..10).unwrap_or(d), true));
+
+            let pattern = &item["recurrence"]["pattern"];
+            let repeat = match (pattern["type"].as_str(), pattern["interval"].as_i64().unwrap_or(1)) {
+                (None, _) => RepeatType::None,
+                (Some("daily"), 1) => RepeatType::Daily,
+                (Some("weekly"), 1) => RepeatType::Weekly,
+                (Some("absoluteMonthly"), 1) => RepeatType::Monthly,
+                _ => RepeatType::Custom,
+            };
+
+            let subtasks = array(&item["checklistItems"])
+                .iter()
+                .map(|c| ImportSubTask {
+                    title: str_field(c, "displayName"),
+                    completed: c["isChecked"].as_bool().unwrap_or(false),
+                })
+                .filter(|s| !s.title.is_empty())
+                .collect();
+
+            preview.tasks.push(ImportTask {
+                title,
+                description: item["body"]["content"].as_str().unwrap_or("").trim().to_string(),
+                priority,
+                project: Some(list_name.clone()).filter(|n| !n.is_empty()),
+                tags: array(&item["categories"])
+                    .iter()
+                    .filter_map(|c| c.as_str().map(|s| s.to_string()))
+                    .collect(),
+                due_date,
+                repeat,
+                completed: item["status"].as_str() == Some("completed"),
+                completed_at: mstodo_time(&item["completedDateTime"]),
+                subtasks,
+            });
+        }
+    }
+
+    Ok(preview)
+}
+
+// ========== 滴答清单 TickTick ==========
+
+/// 滴答清单 CSV 备份（表头前有若干行导出信息）
+fn parse_ticktick_csv(content: &str) -> Result<ImportPreview, String> {
+    let start = content
+        .split_inclusive('\n')
+        .scan(0usize, |offset, line| {
+            let current = *offset;
+            *offset += line.len();
+            Some((current, line))
+        })
+        .find(|(_, line)| line.contains("\"Title\"") || line.starts_with("Folder Name,"))
+        .map(|(offset, _)| offset)
+        .ok_or("未找到滴答清单 CSV 表头")?;
+
+    let mut reader = csv_reader(&content[start..]);
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let list_col = column(&headers, "List Name");
+    let title_col = column(&headers, "Title").ok_or("缺少 Title 列")?;
+    let tags_col = column(&headers, "Tags");
+    let content_col = column(&headers, "Content");
+    let due_col = column(&headers, "Due Date");
+    let all_day_col = column(&headers, "Is All Day");
+    let repeat_col = column(&headers, "Repeat");
+    let priority_col = column(&headers, "Priority");
+    let status_col = column(&headers, "Status");
+    let completed_col = column(&headers, "Completed Time");
+    let id_col = column(&headers, "taskId");
+    let parent_col = column(&headers, "parentId");
+
+    let mut preview = ImportPreview::default();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut children: Vec<(String, ImportSubTask)> = Vec::new();
+
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+        let get = |i: Option<usize>| i.and_then(|i| record.get(i)).unwrap_or("").trim();
+
+        let title = get(Some(title_col)).to_string();
+        if title.is_empty() {
+            continue;
+        }
+        // 状态：0 未完成，1/2 已完成
+        let completed = matches!(get(status_col), "1" | "2");
+
+        let parent = get(parent_col);
+        if !parent.is_empty() {
+            children.push((parent.to_string(), ImportSubTask { title, completed }));
+            continue;
+        }
+
+        // 清单类任务的检查项以 ▫（未完成）/ ▪（已完成）开头
+        let mut description = Vec::new();
+        let mut subtasks = Vec::new();
+        for line in get(content_col).lines() {
+            if let Some(item) = line.strip_prefix('▫') {
+                subtasks.push(ImportSubTask {
+                    title: item.trim().to_string(),
+                    completed: false,
+                });
+            } else if let Some(item) = line.strip_prefix('▪') {
+                subtasks.push(ImportSubTask {
+                    title: item.trim().to_string(),
+                    completed: true,
+                });
+            } else {
+                description.push(line);
+            }
+        }
+
+        // 优先级：0 无，1 低，3 中，5 高
+        let priority = match get(priority_col) {
+            "5" => Priority::High,
+            "1" => Priority::Low,
+            _ => Priority::Medium,
+        };
+
+        let tags = get(tags_col)
+            .split(',')
+            .map(|t| t.trim().trim_start_matches('#').to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        index.insert(get(id_col).to_string(), preview.tasks.len());
+        preview.tasks.push(ImportTask {
+            title,
+            description: description.join("\n").trim().to_string(),
+            priority,
+            project: Some(get(list_col).to_string()).filter(|n| !n.is_empty()),
+            tags,
+            due_date: normalize_due(get(due_col), get(all_day_col) == "true"),
+            repeat: parse_rrule(get(repeat_col)),
+            completed,
+            completed_at: Some(get(completed_col).to_string()).filter(|s| !s.is_empty()),
+            subtasks,
+        });
+    }
+
+    for (parent, sub) in children {
+        match index.get(&parent) {
+            Some(&i) => preview.tasks[i].subtasks.push(sub),
+            None => preview.warnings.push(format!("子任务「{}」找不到父任务", sub.title)),
+        }
+    }
+
+    Ok(preview)
+}
+
+/// 解析 iCalendar RRULE，仅间隔为 1 的日/周/月重复能精确映射
+fn parse_rrule(rule: &str) -> RepeatType {
+    let rule = rule.trim().trim_start_matches("RRULE:");
+    if rule.is_empty() {
+        return RepeatType::None;
+    }
+
+    let mut freq = "";
+    let mut interval = 1;
+    let mut multi_day = false;
+    for part in rule.split(';') {
+        match part.split_once('=') {
+            Some(("FREQ", v)) => freq = v,
+            Some(("INTERVAL", v)) => interval = v.parse().unwrap_or(1),
+            Some(("BYDAY", v)) => multi_day = v.contains(','),
+            _ => {}
+        }
+    }
+    if interval != 1 || multi_day {
+        return RepeatType::Custom;
+    }
+    match freq {
+        "DAILY" => RepeatType::Daily,
+        "WEEKLY" => RepeatType::Weekly,
+        "MONTHLY" => RepeatType::Monthly,
+        _ => RepeatType::Custom,
+    }
+}
+
+/// Graph API 的时间：dateTime 不带偏移，时区另由 timeZone 给出（导出时一般为 UTC）
+fn mstodo_time(value: &Value) -> Option<String> {
+    let date_time = value["dateTime"].as_str().map(str::trim).filter(|s| !s.is_empty())?;
+    match value["timeZone"].as_str() {
+        None | Some("UTC") | Some("") => Some(format!("{}Z", date_time.trim_end_matches('Z'))),
+        Some(_) => Some(date_time.to_string()),
+    }
+}
+
+// ========== 通用工具 ==========
+
+/// 无法识别的完成时间不导入（提交时按导入时刻计）
+fn check_completed_at(preview: &mut ImportPreview) {
+    for task in &mut preview.tasks {
+        if let Some(value) = &task.completed_at {
+            if to_utc_timestamp(value, chrono_tz::UTC).is_none() {
+                preview
+                    .warnings
+                    .push(format!("「{}」的完成时间无法识别: {}", task.title, value));
+                task.completed_at = None;
+            }
+        }
+    }
+}
+
+/// 汇总预览中涉及的项目名与标签名（去重并保持出现顺序）
+fn collect_names(preview: &mut ImportPreview) {
+    for task in &preview.tasks {
+        if let Some(project) = &task.project {
+            if !preview.projects.contains(project) {
+                preview.projects.push(project.clone());
+            }
+        }
+        for tag in &task.tags {
+            if !preview.tags.contains(tag) {
+                preview.tags.push(tag.clone());
+            }
+        }
+    }
+}
+
+/// 外部日期统一转换为本地时间的 YYYY-MM-DD 或 YYYY-MM-DDTHH:mm
+fn normalize_due(value: &str, all_day: bool) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.format("%Y-%m-%d").to_string());
+    }
+
+    let format = |n: NaiveDateTime| {
+        if all_day {
+            n.format("%Y-%m-%d").to_string()
+        } else {
+            n.format("%Y-%m-%dT%H:%M").to_string()
+        }
+    };
+
+    // 带时区偏移的时间先换算为本地时间
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%z"))
+    {
+        return Some(format(dt.with_timezone(&Local).naive_local()));
+    }
+    for fmt in [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(n) = NaiveDateTime::parse_from_str(value, fmt) {
+            return Some(format(n));
+        }
+    }
+    None
+}
+
+/// 拆出标题中的 @标签
+fn split_labels(content: &str) -> (String, Vec<String>) {
+    let mut words = Vec::new();
+    let mut labels = Vec::new();
+    for word in content.split_whitespace() {
+        match word.strip_prefix('@').filter(|l| !l.is_empty()) {
+            Some(label) => labels.push(label.to_string()),
+            None => words.push(word),
+        }
+    }
+    (words.join(" "), labels)
+}
+
+fn csv_reader(content: &str) -> csv::Reader<&[u8]> {
+    csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes())
+}
+
+fn column(headers: &csv::StringRecord, name: &str) -> Option<usize> {
+    headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name))
+}
+
+fn file_stem(file_name: &str) -> Option<String> {
+    std::path::Path::new(file_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn array(value: &Value) -> &[Value] {
+    value.as_array().map(|a| a.as_slice()).unwrap_or(&[])
+}
+
+fn str_field(value: &Value, key: &str) -> String {
+    value[key].as_str().unwrap_or("").trim().to_string()
+}
+
+/// ID 在不同版本中可能是字符串或数字
+fn value_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn is_checked(value: &Value) -> bool {
+    value.as_bool().unwrap_or_else(|| value.as_i64() == Some(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection;
+
+    #[test]
+    fn todoist_csv_with_subtasks_and_labels() {
+        let csv = "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,DATE\n\
+                   section,收件箱,,,,\n\
+                   task,写周报 @工作,本周进展,1,1,2026-10-23\n\
+                   task,整理数据,,4,2,\n\
+                   task,喝水,,4,1,every day\n";
+        let preview = preview("todoist_csv", csv, Some("工作.csv")).unwrap();
+        assert_eq!(preview.tasks.len(), 2);
+        let report = &preview.tasks[0];
+        assert_eq!(report.title, "写周报");
+        assert_eq!(report.tags, vec!["工作"]);
+        assert_eq!(report.priority, Priority::High);
+        assert_eq!(report.project.as_deref(), Some("工作"));
+        assert_eq!(report.due_date.as_deref(), Some("2026-10-23"));
+        assert_eq!(report.subtasks.len(), 1);
+        assert_eq!(preview.tasks[1].repeat, RepeatType::Daily);
+        assert_eq!(preview.projects, vec!["工作"]);
+    }
+
+    #[test]
+    fn todoist_json_parent_cycle_is_reported() {
+        let json = r#"{
+            "projects": [{"id": "p1", "name": "家务"}],
+            "items": [
+                {"id": "1", "content": "大扫除", "project_id": "p1", "parent_id": null, "priority": 4,
+                 "checked": true, "completed_at": "2026-10-18T08:30:00.000000Z"},
+                {"id": "2", "content": "擦窗", "parent_id": "1", "checked": false},
+                {"id": "3", "content": "扫地", "parent_id": "2", "checked": 1},
+                {"id": "4", "content": "自己", "parent_id": "4"},
+                {"id": "5", "content": "甲", "parent_id": "6"},
+                {"id": "6", "content": "乙", "parent_id": "5"}
+            ]
+        }"#;
+        let preview = preview("todoist_json", json, None).unwrap();
+        assert_eq!(preview.tasks.len(), 1);
+        let task = &preview.tasks[0];
+        assert_eq!(task.priority, Priority::High);
+        assert!(task.completed);
+        assert_eq!(task.subtasks.len(), 2);
+        assert!(task.subtasks[1].completed);
+        assert_eq!(preview.warnings.len(), 3);
+    }
+
+    #[test]
+    fn mstodo_lists() {
+        let json = r#"{"value": [{"displayName": "购物", "tasks": [
+            {"title": "买牛奶", "importance": "high", "status": "completed",
+             "completedDateTime": {"dateTime": "2026-10-18T00:00:00.0000000", "timeZone": "UTC"},
+             "dueDateTime": {"dateTime": "2026-10-20T00:00:00.0000000", "timeZone": "UTC"},
+             "recurrence": {"pattern": {"type": "weekly", "interval": 2}},
+             "checklistItems": [{"displayName": "全脂", "isChecked": true}]}
+        ]}]}"#;
+        let preview = preview("mstodo", json, None).unwrap();
+        let task = &preview.tasks[0];
+        assert_eq!(task.project.as_deref(), Some("购物"));
+        assert_eq!(task.due_date.as_deref(), Some("2026-10-20"));
+        assert_eq!(task.repeat, RepeatType::Custom);
+        assert_eq!(task.subtasks.len(), 1);
+        assert_eq!(
+            to_utc_timestamp(task.completed_at.as_deref().unwrap(), chrono_tz::Asia::Shanghai).as_deref(),
+            Some("2026-10-18T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn ticktick_csv_with_crlf_and_chinese_preamble() {
+        let csv = "\"日期: 2026-10-19\"\r\n\"版本 7.1\"\r\n\
+                   \"List Name\",\"Title\",\"Tags\",\"Content\",\"Due Date\",\"Is All Day\",\"Repeat\",\"Priority\",\"Status\",\"Completed Time\",\"taskId\",\"parentId\"\r\n\
+                   \"工作\",\"发版\",\"#发布, 紧急\",\"检查日志\n▫跑测试\n▪打标签\",\"2026-10-20\",\"true\",\"RRULE:FREQ=WEEKLY;INTERVAL=1\",\"5\",\"2\",\"2026-10-18T09:00:00+0800\",\"t1\",\"\"\r\n\
+                   \"工作\",\"写说明\",\"\",\"\",\"\",\"\",\"\",\"0\",\"0\",\"\",\"t2\",\"t1\"\r\n";
+        let preview = preview("ticktick_csv", csv, None).unwrap();
+        assert_eq!(preview.tasks.len(), 1);
+        let task = &preview.tasks[0];
+        assert_eq!(task.title, "发版");
+        assert_eq!(task.tags, vec!["发布", "紧急"]);
+        assert_eq!(task.description, "检查日志");
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.repeat, RepeatType::Weekly);
+        assert!(task.completed);
+        assert_eq!(task.subtasks.len(), 3);
+        assert_eq!(
+            to_utc_timestamp(task.completed_at.as_deref().unwrap(), chrono_tz::UTC).as_deref(),
+            Some("2026-10-18T01:00:00Z")
+        );
+    }
+
+    #[test]
+    fn rrule_mapping() {
+        assert_eq!(parse_rrule(""), RepeatType::None);
+        assert_eq!(parse_rrule("RRULE:FREQ=DAILY"), RepeatType::Daily);
+        assert_eq!(parse_rrule("FREQ=WEEKLY;BYDAY=MO,WE"), RepeatType::Custom);
+        assert_eq!(parse_rrule("FREQ=MONTHLY;INTERVAL=2"), RepeatType::Custom);
+    }
+
+    #[test]
+    fn commit_normalizes_completed_at() {
+        let conn = connection::memory();
+        settings_repo::update_setting(&conn, "timezone", "Asia/Shanghai").unwrap();
+        let mut preview = ImportPreview::default();
+        for (title, completed_at) in [("带偏移", "2026-10-18T09:00:00+0800"), ("只有日期", "2026-10-18")] {
+            preview.tasks.push(ImportTask {
+                title: title.to_string(),
+                description: String::new(),
+                priority: Priority::Medium,
+                project: Some("导入".to_string()),
+                tags: vec!["a".to_string()],
+                due_date: None,
+                repeat: RepeatType::None,
+                completed: true,
+                completed_at: Some(completed_at.to_string()),
+                subtasks: Vec::new(),
+            });
+        }
+        let summary = commit(&conn, &preview).unwrap();
+        assert_eq!((summary.tasks, summary.projects, summary.tags), (2, 1, 1));
+
+        let mut stored: Vec<(String, String)> = conn
+            .prepare("SELECT title, completed_at FROM tasks ORDER BY title")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        stored.sort();
+        assert_eq!(
+            stored,
+            vec![
+                ("只有日期".to_string(), "2026-10-17T16:00:00Z".to_string()),
+                ("带偏移".to_string(), "2026-10-18T01:00:00Z".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// 连续输错几次后开始限制重试
+const FREE_ATTEMPTS: u32 = 5;
+/// 首次限制的等待时间，之后每次翻倍
+const BASE_DELAY: Duration = Duration::from_secs(30);
+/// 最长等待时间
+const MAX_DELAY: Duration = Duration::from_secs(15 * 60);
+
+/// 生成 Argon2id 密码哈希（PHC 字符串，含随机盐与参数）
+pub fn hash_pin(pin: &str) -> Result<String, String> {
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes()).map_err(|e| e.to_string())?;
+    Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// 校验密码是否与保存的哈希一致
+pub fn verify_pin(stored: &str, pin: &str) -> bool {
+    PasswordHash::new(stored).is_ok_and(|hash| Argon2::default().verify_password(pin.as_bytes(), &hash).is_ok())
+}
+
+/// 连续输错 failures 次后需要等待的时间
+pub fn retry_delay(failures: u32) -> Duration {
+    if failures < FREE_ATTEMPTS {
+        return Duration::ZERO;
+    }
+    let doublings = (failures - FREE_ATTEMPTS).min(16);
+    (BASE_DELAY * 2u32.pow(doublings)).min(MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_and_verify() {
+        let stored = hash_pin("1234").unwrap();
+        assert!(stored.starts_with("$argon2id$"));
+        assert!(verify_pin(&stored, "1234"));
+        assert!(!verify_pin(&stored, "4321"));
+        assert!(!verify_pin("", "1234"));
+        assert_ne!(stored, hash_pin("1234").unwrap());
+    }
+
+    #[test]
+    fn retry_delay_backs_off() {
+        assert_eq!(retry_delay(4), Duration::ZERO);
+        assert_eq!(retry_delay(5), Duration::from_secs(30));
+        assert_eq!(retry_delay(6), Duration::from_secs(60));
+        assert_eq!(retry_delay(100), MAX_DELAY);
+    }
+}
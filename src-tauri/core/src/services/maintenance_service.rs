@@ -0,0 +1,109 @@
+use crate::db::connection::transaction;
+use crate::db::settings_repo;
+use crate::models::maintenance::{MaintenanceReport, OrphanRepair};
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+
+/// 上次维护时间的设置项
+const LAST_MAINTENANCE_KEY: &str = "last_maintenance_at";
+/// 距上次维护超过该天数时，启动时自动维护
+pub const MAINTENANCE_INTERVAL_DAYS: i64 = 7;
+
+/// 完整维护：完整性检查、外键检查、修复孤立数据，最后整理数据库
+pub fn run(conn: &Connection) -> Result<MaintenanceReport, String> {
+    let size_before = database_size(conn)?;
+    let integrity_errors = integrity_check(conn)?;
+    let foreign_key_errors = foreign_key_check(conn)?;
+    let repaired = repair_orphans(conn)?;
+
+    // 文件已损坏时 VACUUM 可能失败或丢失更多数据，只做检查和修复
+    if integrity_errors.is_empty() {
+        optimize(conn)?;
+    }
+
+    let checked_at = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    settings_repo::update_setting(conn, LAST_MAINTENANCE_KEY, &checked_at)?;
+
+    Ok(MaintenanceReport {
+        integrity_errors,
+        foreign_key_errors,
+        repaired,
+        size_before,
+        size_after: database_size(conn)?,
+        checked_at,
+    })
+}
+
+/// 距上次维护是否已超过间隔（从未维护过也算）
+pub fn is_due(conn: &Connection) -> bool {
+    let last = settings_repo::get_value(conn, LAST_MAINTENANCE_KEY)
+        .and_then(|v| DateTime::parse_from_rfc3339(&v).ok());
+    match last {
+        Some(last) => Utc::now().signed_duration_since(last).num_days() >= MAINTENANCE_INTERVAL_DAYS,
+        None => true,
+    }
+}
+
+/// PRAGMA integrity_check，返回发现的问题
+pub fn integrity_check(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check").map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?;
+    let messages = rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?;
+    Ok(messages.into_iter().filter(|m| m != "ok").collect())
+}
+
+/// PRAGMA foreign_key_check，返回“表 → 被引用表”形式的问题描述
+pub fn foreign_key_check(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check").map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            let table: String = row.get(0)?;
+            let rowid: Option<i64> = row.get(1)?;
+            let parent: String = row.get(2)?;
+            Ok(match rowid {
+                Some(rowid) => format!("{} 第 {} 行 → {}", table, rowid, parent),
+                None => format!("{} → {}", table, parent),
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+/// 清理孤立数据。早期版本未开启外键约束，删除任务、标签或项目时可能留下悬空引用
+pub fn repair_orphans(conn: &Connection) -> Result<OrphanRepair, String> {
+    transaction(conn, || {
+        let execute = |sql: &str| conn.execute(sql, []).map_err(|e| e.to_string());
+        Ok(OrphanRepair {
+            task_tags: execute(
+                "DELETE FROM task_tags
+                 WHERE task_id NOT IN (SELECT id FROM tasks) OR tag_id NOT IN (SELECT id FROM tags)",
+            )?,
+            subtasks: execute("DELETE FROM subtasks WHERE task_id NOT IN (SELECT id FROM tasks)")?,
+            task_events: execute("DELETE FROM task_events WHERE task_id NOT IN (SELECT id FROM tasks)")?,
+            task_projects: execute(
+                "UPDATE tasks SET project_id = NULL
+                 WHERE project_id IS NOT NULL AND project_id NOT IN (SELECT id FROM projects)",
+            )?,
+            time_entries: execute("DELETE FROM time_entries WHERE task_id NOT IN (SELECT id FROM tasks)")?,
+        })
+    })
+}
+
+/// 重建数据库文件回收空间，并更新查询优化器的统计信息
+pub fn optimize(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch("VACUUM; ANALYZE; PRAGMA wal_checkpoint(TRUNCATE);")
+        .map_err(|e| e.to_string())
+}
+
+/// 数据库占用的字节数
+fn database_size(conn: &Connection) -> Result<u64, String> {
+    conn.query_row(
+        "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        [],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|size| size as u64)
+    .map_err(|e| e.to_string())
+}
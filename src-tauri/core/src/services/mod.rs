@@ -0,0 +1,14 @@
+pub mod import_service;
+pub mod lock_service;
+pub mod maintenance_service;
+pub mod nlp;
+pub mod outline_service;
+pub mod pomodoro_service;
+pub mod reminder_service;
+pub mod repeat_service;
+pub mod seed_service;
+pub mod stats_service;
+pub mod task_service;
+pub mod todotxt_service;
+pub mod undo_service;
+pub mod workspace_service;
@@ -0,0 +1,709 @@
+//! 自然语言任务解析：从一句话中提取截止时间、重复规则、#标签、~项目、!优先级
+//!
+//! 例：“每周三下午3点 开周会 #会议 ~工作 !high”、“next friday 5pm review PR”
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// 解析结果，字段与 create_task 参数对应
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedTask {
+    pub title: String,
+    pub due_date: Option<String>,
+    pub priority: Option<String>,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    /// 对应 RepeatType：daily / weekly / monthly，三者表示不了的规则（每两周、每个工作日等）为 custom
+    pub repeat_type: Option<String>,
+    /// 完整的重复规则
+    pub recurrence: Option<Recurrence>,
+    /// 识别出的片段位置，供输入框高亮
+    pub spans: Vec<Span>,
+}
+
+/// 重复规则
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recurrence {
+    pub freq: Freq,
+    /// 间隔，如每 2 周为 2
+    pub interval: u32,
+    /// 限定星期几（1 = 周一 … 7 = 周日），为空表示不限
+    pub weekdays: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Freq {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// 识别出的片段，start/end 为 UTF-16 下标（与前端字符串下标一致）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub kind: SpanKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpanKind {
+    Date,
+    Time,
+    Recurrence,
+    Tag,
+    Project,
+    Priority,
+}
+
+/// 文本中识别出的片段
+enum Token {
+    Tag(String),
+    Project(String),
+    Priority(&'static str),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Recurrence(Recurrence),
+    /// 形似日期但无法表示的片段（如日期溢出），留在标题中，也不再被其他规则拆开识别
+    Kept,
+}
+
+impl Token {
+    fn kind(&self) -> Option<SpanKind> {
+        Some(match self {
+            Token::Tag(_) => SpanKind::Tag,
+            Token::Project(_) => SpanKind::Project,
+            Token::Priority(_) => SpanKind::Priority,
+            Token::Date(_) => SpanKind::Date,
+            Token::Time(_) => SpanKind::Time,
+            Token::Recurrence(_) => SpanKind::Recurrence,
+            Token::Kept => return None,
+        })
+    }
+}
+
+// ========== 正则 ==========
+
+const CN_NUM: &str = "[0-9零一二两三四五六七八九十]+";
+const CN_WEEKDAY: &str = "[一二三四五六日天1-7]";
+const EN_WEEKDAY_FULL: &str = "monday|tuesday|wednesday|thursday|friday|saturday|sunday";
+/// 缩写与普通单词（sun、sat、wed…）同形，只在 on/next/this/every 之后识别
+const EN_WEEKDAY_ABBR: &str = "mon|tues|tue|wed|thurs|thur|thu|fri|sat|sun";
+
+fn regex(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("合法的正则")
+}
+
+/// #标签 ~项目 !优先级（前面须为空白或行首）
+static MARKER_RE: LazyLock<Regex> = LazyLock::new(|| regex(r"(?:^|\s)([#~!])([^\s#~!]+)"));
+
+/// 每个工作日、every weekday
+static WORKDAY_RE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"(?i)每个?工作日|\bevery\s+weekday\b|\bweekdays\b"));
+
+/// 每周三、每星期一三五、every monday
+static REPEAT_WEEKDAY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    regex(&format!(
+        r"(?i)每个?(?:周|星期|礼拜)((?:{d}[、,，]?)*{d})|\bevery\s+({full}|{abbr})\b",
+        d = CN_WEEKDAY,
+        full = EN_WEEKDAY_FULL,
+        abbr = EN_WEEKDAY_ABBR
+    ))
+});
+
+/// 每天、每两周、每个月、every 2 weeks、daily
+static REPEAT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    regex(&format!(
+        r"(?i)每({n})?个?(天|日|周|星期|礼拜|月|年)|\bevery\s+(\d+\s+|other\s+)?(day|week|month|year)s?\b|\b(daily|weekly|monthly|yearly)\b",
+        n = CN_NUM
+    ))
+});
+
+/// 3天后、两周后、in 3 days、2 weeks later
+static OFFSET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    regex(&format!(
+        r"(?i)({n})\s*个?(天|日|周|星期|礼拜|月)[以之]?后|\bin\s+(\d+)\s+(day|week|month)s?\b|\b(\d+)\s+(day|week|month)s?\s+later\b",
+        n = CN_NUM
+    ))
+});
+
+/// 下周一、本周五、星期天、next friday、on monday、friday、this mon
+static WEEKDAY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    regex(&format!(
+        r"(?i)(下下|下个?|这个?|本)?(?:周|星期|礼拜)({d})|\b(?:(next|this|on)\s+)?({full})\b|\b(next|this|on)\s+({abbr})\b",
+        d = CN_WEEKDAY,
+        full = EN_WEEKDAY_FULL,
+        abbr = EN_WEEKDAY_ABBR
+    ))
+});
+
+/// 月底、下个月底、end of month
+static MONTH_END_RE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"(?i)(下个?)?月[底末]|\bend\s+of\s+(?:the\s+)?(next\s+)?month\b"));
+
+/// 相对日期
+static DAY_RE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"(?i)大后天|后天|明天|今天|今晚|\btoday\b|\btomorrow\b|\btonight\b"));
+
+/// 绝对日期：2024-01-22、1月22日
+static DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"(\d{4})-(\d{1,2})-(\d{1,2})|(\d{1,2})月(\d{1,2})[日号]?"));
+
+/// 时刻：下午3点、3点半、15:30、5pm、10:30am
+static TIME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"(?i)(?:(凌晨|早上|上午|中午|下午|傍晚|晚上)\s*)?(\d{1,2})(?:点(?:(半)|(\d{1,2})分?)?|[:：](\d{2}))(?:\s*(am|pm)\b)?|\b(\d{1,2})\s*(am|pm)\b",
+    )
+});
+
+/// 不带上下午的“N点”前面须是日期或时段词（明天3点、周五3点、1月2日3点）
+static HOUR_CONTEXT_RE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"(?:[天晚日号]|[周期拜][一二三四五六日天1-7])$"));
+
+// ========== 解析 ==========
+
+/// 以用户时区 tz 的当前时间解析
+pub fn parse(input: &str, tz: Tz) -> ParsedTask {
+    parse_at(input, Utc::now().with_timezone(&tz).naive_local())
+}
+
+/// 以指定时间为“现在”解析
+pub fn parse_at(input: &str, now: NaiveDateTime) -> ParsedTask {
+    let tokens = scan(input, now.date());
+    let tokens: Vec<(Range<usize>, Token)> = tokens
+        .into_iter()
+        .filter(|(_, token)| !matches!(token, Token::Kept))
+        .collect();
+
+    let mut parsed = ParsedTask::default();
+    let mut date = None;
+    let mut time = None;
+    for (range, token) in &tokens {
+        if let Some(kind) = token.kind() {
+            parsed.spans.push(Span {
+                start: utf16_offset(input, range.start),
+                end: utf16_offset(input, range.end),
+                kind,
+            });
+        }
+        match token {
+            Token::Tag(name) => {
+                if !parsed.tags.contains(name) {
+                    parsed.tags.push(name.clone());
+                }
+            }
+            Token::Project(name) => parsed.project = Some(name.clone()),
+            Token::Priority(p) => parsed.priority = Some(p.to_string()),
+            Token::Date(d) => date = date.or(Some(*d)),
+            Token::Time(t) => time = time.or(Some(*t)),
+            Token::Recurrence(r) => {
+                if parsed.recurrence.is_none() {
+                    parsed.recurrence = Some(r.clone());
+                }
+            }
+            Token::Kept => {}
+        }
+    }
+
+    if let Some(rule) = &parsed.recurrence {
+        parsed.repeat_type = Some(repeat_type_of(rule).to_string());
+        // 未写日期时从最近一次符合规则的日子开始
+        if date.is_none() && !rule.weekdays.is_empty() {
+            date = next_matching_weekday(now.date(), &rule.weekdays);
+        }
+    }
+
+    // 只写时刻时取最近的一次：今天已过则为明天
+    let date = date.or(time.and_then(|t| {
+        if t > now.time() {
+            Some(now.date())
+        } else {
+            now.date().succ_opt()
+        }
+    }));
+    parsed.due_date = date.map(|d| match time {
+        Some(t) => d.and_time(t).format("%Y-%m-%dT%H:%M").to_string(),
+        None => d.format("%Y-%m-%d").to_string(),
+    });
+
+    let ranges: Vec<Range<usize>> = tokens.into_iter().map(|(r, _)| r).collect();
+    parsed.title = strip_ranges(input, &ranges);
+    parsed
+}
+
+/// 找出所有可识别片段，按位置排序且互不重叠（先识别的优先）
+fn scan(input: &str, today: NaiveDate) -> Vec<(Range<usize>, Token)> {
+    let mut tokens: Vec<(Range<usize>, Token)> = Vec::new();
+
+    for caps in MARKER_RE.captures_iter(input) {
+        let (Some(sigil), Some(value)) = (caps.get(1), caps.get(2)) else {
+            continue;
+        };
+        let token = match sigil.as_str() {
+            "#" => Token::Tag(value.as_str().to_string()),
+            "~" => Token::Project(value.as_str().to_string()),
+            _ => match priority_of(value.as_str()) {
+                Some(p) => Token::Priority(p),
+                None => continue,
+            },
+        };
+        tokens.push((sigil.start()..value.end(), token));
+    }
+
+    // 重复规则要先于日期识别，否则“每周三”会被当成“周三”
+    for m in WORKDAY_RE.find_iter(input) {
+        let workdays = Recurrence {
+            freq: Freq::Weekly,
+            interval: 1,
+            weekdays: (1..=5).collect(),
+        };
+        push_free(&mut tokens, m.range(), Token::Recurrence(workdays));
+    }
+    collect(&mut tokens, &REPEAT_WEEKDAY_RE, input, |caps| {
+        let mut weekdays: Vec<u32> = match caps.get(1) {
+            Some(days) => days.as_str().chars().filter_map(cn_weekday).collect(),
+            None => en_weekday(caps.get(2)?.as_str()).into_iter().collect(),
+        };
+        weekdays.sort_unstable();
+        weekdays.dedup();
+        Some(Token::Recurrence(Recurrence {
+            freq: Freq::Weekly,
+            interval: 1,
+            weekdays,
+        }))
+    });
+    collect(&mut tokens, &REPEAT_RE, input, |caps| {
+        let (interval, unit) = if let Some(unit) = caps.get(2) {
+            let interval = match caps.get(1) {
+                Some(n) => cn_number(n.as_str())?,
+                None => 1,
+            };
+            (interval, unit.as_str())
+        } else if let Some(unit) = caps.get(4) {
+            let interval = match caps.get(3).map(|n| n.as_str().trim()) {
+                Some("other") => 2,
+                Some(n) => n.parse().ok()?,
+                None => 1,
+            };
+            (interval, unit.as_str())
+        } else {
+            (1, caps.get(5)?.as_str())
+        };
+        Some(Token::Recurrence(Recurrence {
+            freq: freq_of(unit)?,
+            interval: interval.max(1),
+            weekdays: Vec::new(),
+        }))
+    });
+
+    collect(&mut tokens, &OFFSET_RE, input, |caps| {
+        let (amount, unit) = match (caps.get(1), caps.get(3), caps.get(5)) {
+            (Some(n), _, _) => (cn_number(n.as_str())?, caps.get(2)?.as_str()),
+            (_, Some(n), _) => (n.as_str().parse().ok()?, caps.get(4)?.as_str()),
+            (_, _, Some(n)) => (n.as_str().parse().ok()?, caps.get(6)?.as_str()),
+            _ => return None,
+        };
+        // 超出可表示范围的日期不识别
+        let date = match freq_of(unit)? {
+            Freq::Daily => today.checked_add_signed(Duration::days(i64::from(amount))),
+            Freq::Weekly => today.checked_add_signed(Duration::weeks(i64::from(amount))),
+            Freq::Monthly => today.checked_add_months(Months::new(amount)),
+            Freq::Yearly => return None,
+        };
+        Some(date.map_or(Token::Kept, Token::Date))
+    });
+
+    collect(&mut tokens, &WEEKDAY_RE, input, |caps| {
+        let (modifier, weekday) = match (caps.get(2), caps.get(4)) {
+            (Some(day), _) => (caps.get(1), cn_weekday(day.as_str().chars().next()?)?),
+            (_, Some(day)) => (caps.get(3), en_weekday(day.as_str())?),
+            _ => (caps.get(5), en_weekday(caps.get(6)?.as_str())?),
+        };
+        let date = match modifier.map(|m| m.as_str().to_lowercase()) {
+            // 下周X：下一个自然周（周一开始）中的那天
+            Some(m) if m.starts_with("下下") => week_day(today, 2, weekday),
+            Some(m) if m.starts_with('下') || m == "next" => week_day(today, 1, weekday),
+            Some(m) if m == "on" => next_matching_weekday(today, &[weekday]),
+            Some(_) => week_day(today, 0, weekday),
+            None => next_matching_weekday(today, &[weekday]),
+        };
+        Some(date.map_or(Token::Kept, Token::Date))
+    });
+
+    collect(&mut tokens, &MONTH_END_RE, input, |caps| {
+        let next = caps.get(1).is_some() || caps.get(2).is_some();
+        let first = NaiveDate::from_ymd_opt(today.year(), today.month(), 1)?;
+        let following = first.checked_add_months(Months::new(if next { 2 } else { 1 }))?;
+        Some(Token::Date(following.pred_opt()?))
+    });
+
+    for m in DAY_RE.find_iter(input) {
+        let offset = match m.as_str().to_lowercase().as_str() {
+            "今天" | "今晚" | "today" | "tonight" => 0,
+            "明天" | "tomorrow" => 1,
+            "后天" => 2,
+            _ => 3,
+        };
+        if let Some(date) = today.checked_add_signed(Duration::days(offset)) {
+            push_free(&mut tokens, m.range(), Token::Date(date));
+        }
+    }
+
+    collect(&mut tokens, &DATE_RE, input, |caps| absolute_date(caps, today).map(Token::Date));
+    collect(&mut tokens, &TIME_RE, input, |caps| {
+        if !hour_in_context(input, caps) {
+            return None;
+        }
+        time_of_day(caps).map(Token::Time)
+    });
+
+    tokens.sort_by_key(|(r, _)| r.start);
+    tokens
+}
+
+/// 按正则逐个识别并加入不重叠的片段
+fn collect<F>(tokens: &mut Vec<(Range<usize>, Token)>, re: &Regex, input: &str, to_token: F)
+where
+    F: Fn(&Captures) -> Option<Token>,
+{
+    for caps in re.captures_iter(input) {
+        if let (Some(m), Some(token)) = (caps.get(0), to_token(&caps)) {
+            push_free(tokens, m.range(), token);
+        }
+    }
+}
+
+/// 与已有片段不重叠时才加入（如 #3点 不会再被当作时间）
+fn push_free(tokens: &mut Vec<(Range<usize>, Token)>, range: Range<usize>, token: Token) {
+    if range.is_empty() {
+        return;
+    }
+    let overlaps = tokens
+        .iter()
+        .any(|(r, _)| r.start < range.end && range.start < r.end);
+    if !overlaps {
+        tokens.push((range, token));
+    }
+}
+
+// ========== 辅助函数 ==========
+
+fn priority_of(value: &str) -> Option<&'static str> {
+    match value.to_lowercase().as_str() {
+        "high" | "h" | "高" | "1" => Some("high"),
+        "medium" | "m" | "中" | "2" => Some("medium"),
+        "low" | "l" | "低" | "3" => Some("low"),
+        _ => None,
+    }
+}
+
+fn freq_of(unit: &str) -> Option<Freq> {
+    match unit.to_lowercase().as_str() {
+        "天" | "日" | "day" | "daily" => Some(Freq::Daily),
+        "周" | "星期" | "礼拜" | "week" | "weekly" => Some(Freq::Weekly),
+        "月" | "month" | "monthly" => Some(Freq::Monthly),
+        "年" | "year" | "yearly" => Some(Freq::Yearly),
+        _ => None,
+    }
+}
+
+/// 重复规则对应的 RepeatType，daily / weekly / monthly 表示不了的为 custom
+fn repeat_type_of(rule: &Recurrence) -> &'static str {
+    match (rule.freq, rule.interval, rule.weekdays.len()) {
+        (Freq::Daily, 1, 0) => "daily",
+        (Freq::Weekly, 1, 0 | 1) => "weekly",
+        (Freq::Monthly, 1, 0) => "monthly",
+        _ => "custom",
+    }
+}
+
+/// 中文“N点”不带上下午时，只在紧跟日期词或位于句首、句末时当作时刻（“写3点建议”中不是）
+fn hour_in_context(input: &str, caps: &Captures) -> bool {
+    let Some(m) = caps.get(0) else {
+        return false;
+    };
+    let bare = caps.get(1).is_none() && caps.get(6).is_none() && m.as_str().contains('点');
+    if !bare {
+        return true;
+    }
+    let before = input[..m.start()].trim_end();
+    let after = input[m.end()..].trim_start();
+    before.is_empty() || after.is_empty() || HOUR_CONTEXT_RE.is_match(before)
+}
+
+/// 阿拉伯数字或中文数字（到九十九）
+fn cn_number(s: &str) -> Option<u32> {
+    if let Ok(n) = s.parse() {
+        return Some(n);
+    }
+    let digit = |c: char| match c {
+        '两' => Some(2),
+        _ => "零一二三四五六七八九".chars().position(|d| d == c).map(|n| n as u32),
+    };
+    let chars: Vec<char> = s.chars().collect();
+    match chars.as_slice() {
+        [c] if *c == '十' => Some(10),
+        [c] => digit(*c),
+        ['十', ones] => Some(10 + digit(*ones)?),
+        [tens, '十'] => Some(digit(*tens)? * 10),
+        [tens, '十', ones] => Some(digit(*tens)? * 10 + digit(*ones)?),
+        _ => None,
+    }
+}
+
+/// 周一 = 1 … 周日 = 7
+fn cn_weekday(c: char) -> Option<u32> {
+    match c {
+        '一' | '1' => Some(1),
+        '二' | '2' => Some(2),
+        '三' | '3' => Some(3),
+        '四' | '4' => Some(4),
+        '五' | '5' => Some(5),
+        '六' | '6' => Some(6),
+        '日' | '天' | '7' => Some(7),
+        _ => None,
+    }
+}
+
+fn en_weekday(s: &str) -> Option<u32> {
+    let s = s.to_lowercase();
+    // chrono 只认全称和三字母缩写
+    let s = match s.as_str() {
+        "tues" => "tue",
+        "thur" | "thurs" => "thu",
+        other => other,
+    };
+    s.parse::<Weekday>().ok().map(|w| w.number_from_monday())
+}
+
+/// 今天及以后第一个落在指定星期几的日期
+fn next_matching_weekday(today: NaiveDate, weekdays: &[u32]) -> Option<NaiveDate> {
+    (0..7)
+        .filter_map(|i| today.checked_add_signed(Duration::days(i)))
+        .find(|d| weekdays.contains(&d.weekday().number_from_monday()))
+}
+
+/// 往后第 weeks 个自然周中的星期几，超出可表示范围时为 None
+fn week_day(today: NaiveDate, weeks: i64, weekday: u32) -> Option<NaiveDate> {
+    today
+        .checked_sub_signed(Duration::days(i64::from(today.weekday().num_days_from_monday())))?
+        .checked_add_signed(Duration::weeks(weeks))?
+        .checked_add_signed(Duration::days(i64::from(weekday) - 1))
+}
+
+fn absolute_date(caps: &Captures, today: NaiveDate) -> Option<NaiveDate> {
+    let num = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
+    if let (Some(y), Some(m), Some(d)) = (num(1), num(2), num(3)) {
+        return NaiveDate::from_ymd_opt(y as i32, m, d);
+    }
+    let (m, d) = (num(4)?, num(5)?);
+    // 不写年份时取今天及以后最近的一个
+    let this_year = NaiveDate::from_ymd_opt(today.year(), m, d)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, m, d)
+    }
+}
+
+fn time_of_day(caps: &Captures) -> Option<NaiveTime> {
+    let text = |i: usize| caps.get(i).map(|m| m.as_str());
+    let (hour, minute, period, meridiem) = match text(7) {
+        Some(h) => (h.parse::<u32>().ok()?, 0, None, text(8)),
+        None => {
+            let minute = if text(3).is_some() {
+                30
+            } else {
+                text(4).or(text(5)).map(|m| m.parse::<u32>().ok()).unwrap_or(Some(0))?
+            };
+            (text(2)?.parse::<u32>().ok()?, minute, text(1), text(6))
+        }
+    };
+
+    let mut hour = hour;
+    match meridiem.map(|m| m.to_lowercase()) {
+        Some(m) if m == "pm" && hour < 12 => hour += 12,
+        Some(m) if m == "am" && hour == 12 => hour = 0,
+        _ => {}
+    }
+    if matches!(period, Some("下午" | "傍晚" | "晚上")) && hour < 12 {
+        hour += 12;
+    }
+    if period == Some("中午") && hour < 6 {
+        hour += 12;
+    }
+    // 不带时段的“3点”“3点半”通常指下午，凌晨要写明
+    let bare_hour = period.is_none() && meridiem.is_none() && text(7).is_none() && text(5).is_none();
+    if bare_hour && (1..=6).contains(&hour) {
+        hour += 12;
+    }
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// 字节下标转为 UTF-16 下标
+fn utf16_offset(input: &str, byte: usize) -> usize {
+    input[..byte].encode_utf16().count()
+}
+
+/// 去掉识别出的片段，剩余文字作为标题
+fn strip_ranges(input: &str, ranges: &[Range<usize>]) -> String {
+    let mut rest = String::with_capacity(input.len());
+    let mut pos = 0;
+    for range in ranges {
+        rest.push_str(&input[pos..range.start]);
+        rest.push(' ');
+        pos = range.end;
+    }
+    rest.push_str(&input[pos..]);
+    rest.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-10-19 周一 10:00
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+    }
+
+    fn due(input: &str) -> Option<String> {
+        parse_at(input, now()).due_date
+    }
+
+    #[test]
+    fn relative_days() {
+        assert_eq!(due("明天 交报告").as_deref(), Some("2026-10-20"));
+        assert_eq!(due("大后天 交报告").as_deref(), Some("2026-10-22"));
+        assert_eq!(due("3天后 交报告").as_deref(), Some("2026-10-22"));
+        assert_eq!(due("in 2 weeks ship it").as_deref(), Some("2026-11-02"));
+        assert_eq!(due("pay rent in 1 month").as_deref(), Some("2026-11-19"));
+        assert_eq!(parse_at("3天后 交报告", now()).title, "交报告");
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(due("friday review").as_deref(), Some("2026-10-23"));
+        assert_eq!(due("review on fri").as_deref(), Some("2026-10-23"));
+        assert_eq!(due("next friday review").as_deref(), Some("2026-10-30"));
+        assert_eq!(due("this sun hike").as_deref(), Some("2026-10-25"));
+        assert_eq!(due("周三 开会").as_deref(), Some("2026-10-21"));
+        assert_eq!(due("下周一 开会").as_deref(), Some("2026-10-26"));
+        assert_eq!(parse_at("next friday review", now()).title, "review");
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(due("call mom 3pm").as_deref(), Some("2026-10-19T15:00"));
+        assert_eq!(due("10:30am standup").as_deref(), Some("2026-10-19T10:30"));
+        // 今天已过的时刻顺延到明天
+        assert_eq!(due("9:30 standup").as_deref(), Some("2026-10-20T09:30"));
+        assert_eq!(due("next friday 5pm review PR").as_deref(), Some("2026-10-30T17:00"));
+
+        let parsed = parse_at("明天下午3点 开会", now());
+        assert_eq!(parsed.due_date.as_deref(), Some("2026-10-20T15:00"));
+        assert_eq!(parsed.title, "开会");
+        assert_eq!(due("明天3点半 开会").as_deref(), Some("2026-10-20T15:30"));
+        assert_eq!(due("明天8点 跑步").as_deref(), Some("2026-10-20T08:00"));
+        assert_eq!(due("明天凌晨3点 赶飞机").as_deref(), Some("2026-10-20T03:00"));
+        assert_eq!(due("明天 3:30 开会").as_deref(), Some("2026-10-20T03:30"));
+        assert_eq!(due("开会 晚上8点").as_deref(), Some("2026-10-19T20:00"));
+    }
+
+    #[test]
+    fn repeats() {
+        let parsed = parse_at("每周三下午3点 开周会 #会议 ~工作 !high", now());
+        assert_eq!(parsed.title, "开周会");
+        assert_eq!(parsed.repeat_type.as_deref(), Some("weekly"));
+        assert_eq!(parsed.due_date.as_deref(), Some("2026-10-21T15:00"));
+        assert_eq!(parsed.tags, vec!["会议"]);
+        assert_eq!(parsed.project.as_deref(), Some("工作"));
+        assert_eq!(parsed.priority.as_deref(), Some("high"));
+
+        let parsed = parse_at("every monday standup", now());
+        assert_eq!(parsed.repeat_type.as_deref(), Some("weekly"));
+        assert_eq!(parsed.due_date.as_deref(), Some("2026-10-19"));
+        assert_eq!(parse_at("每天 喝水", now()).repeat_type.as_deref(), Some("daily"));
+        assert_eq!(parse_at("pay rent monthly", now()).repeat_type.as_deref(), Some("monthly"));
+    }
+
+    #[test]
+    fn custom_repeats() {
+        let rule = |freq, interval, weekdays: &[u32]| Recurrence {
+            freq,
+            interval,
+            weekdays: weekdays.to_vec(),
+        };
+        let cases = [
+            ("every 2 weeks standup", "standup", rule(Freq::Weekly, 2, &[]), None),
+            ("每两周 复盘", "复盘", rule(Freq::Weekly, 2, &[]), None),
+            ("每个工作日 站会", "站会", rule(Freq::Weekly, 1, &[1, 2, 3, 4, 5]), Some("2026-10-19")),
+            ("every weekday standup", "standup", rule(Freq::Weekly, 1, &[1, 2, 3, 4, 5]), Some("2026-10-19")),
+            ("每周二四 跑步", "跑步", rule(Freq::Weekly, 1, &[2, 4]), Some("2026-10-20")),
+            ("每年 体检", "体检", rule(Freq::Yearly, 1, &[]), None),
+        ];
+        for (input, title, recurrence, due_date) in cases {
+            let parsed = parse_at(input, now());
+            assert_eq!(parsed.title, title, "{}", input);
+            assert_eq!(parsed.repeat_type.as_deref(), Some("custom"), "{}", input);
+            assert_eq!(parsed.recurrence, Some(recurrence), "{}", input);
+            assert_eq!(parsed.due_date.as_deref(), due_date, "{}", input);
+        }
+    }
+
+    #[test]
+    fn tags_and_priority() {
+        let parsed = parse_at("写周报 #工作 #周报 #工作 !低", now());
+        assert_eq!(parsed.title, "写周报");
+        assert_eq!(parsed.tags, vec!["工作", "周报"]);
+        assert_eq!(parsed.priority.as_deref(), Some("low"));
+        // 不认识的优先级原样保留
+        assert_eq!(parse_at("真的!吗 !urgent", now()).priority, None);
+    }
+
+    #[test]
+    fn overflow_keeps_text() {
+        for input in ["in 99999999 days ship it", "in 99999999 weeks ship it", "in 4000000000 months ship it"] {
+            let parsed = parse_at(input, now());
+            assert_eq!(parsed.due_date, None, "{}", input);
+            assert_eq!(parsed.title, input);
+        }
+        // 可表示的最后一周里，下周X已超出范围
+        let last_week = NaiveDate::MAX.and_hms_opt(0, 0, 0).unwrap();
+        let parsed = parse_at("下周一 开会", last_week);
+        assert_eq!(parsed.due_date, None);
+        assert_eq!(parsed.title, "下周一 开会");
+    }
+
+    #[test]
+    fn false_positives() {
+        let parsed = parse_at("buy sun cream", now());
+        assert_eq!(parsed.title, "buy sun cream");
+        assert_eq!(parsed.due_date, None);
+        assert_eq!(due("wed the photos"), None);
+
+        let parsed = parse_at("写3点建议", now());
+        assert_eq!(parsed.title, "写3点建议");
+        assert_eq!(parsed.due_date, None);
+    }
+
+    #[test]
+    fn spans_use_utf16_offsets() {
+        let parsed = parse_at("明天 交报告 #工作", now());
+        let kinds: Vec<(usize, usize, SpanKind)> = parsed.spans.iter().map(|s| (s.start, s.end, s.kind)).collect();
+        assert_eq!(kinds, vec![(0, 2, SpanKind::Date), (7, 10, SpanKind::Tag)]);
+    }
+}
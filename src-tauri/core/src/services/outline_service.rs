@@ -0,0 +1,622 @@
+use crate::db::{project_repo, tag_repo, task_repo};
+use crate::models::import::{ImportPreview, ImportSubTask, ImportTask};
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::{Priority, RepeatType, Task, TaskStatus};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+// ========== Org-mode ==========
+
+/// 导出为 org-mode：项目为一级标题，任务为二级标题，子任务为三级标题；
+/// 未归属项目的任务直接作为一级标题放在最前面
+pub fn export_org(conn: &Connection) -> Result<String, String> {
+    let (tasks, projects, tags) = load(conn)?;
+    let mut out = String::from("#+TITLE: 干了么\n\n");
+
+    for task in tasks.iter().filter(|t| t.project_id.is_none()) {
+        write_org_task(&mut out, task, 1, &tags);
+    }
+    for project in &projects {
+        out.push_str(&format!("* {}\n", project.name));
+        for task in tasks.iter().filter(|t| t.project_id.as_ref() == Some(&project.id)) {
+            write_org_task(&mut out, task, 2, &tags);
+        }
+    }
+
+    Ok(out)
+}
+
+fn write_org_task(out: &mut String, task: &Task, level: usize, tags: &[Tag]) {
+    let stars = "*".repeat(level);
+    let keyword = if task.status == TaskStatus::Completed { "DONE" } else { "TODO" };
+    let mut heading = format!("{} {} ", stars, keyword);
+    match task.priority {
+        Priority::High => heading.push_str("[#A] "),
+        Priority::Low => heading.push_str("[#C] "),
+        Priority::Medium => {}
+    }
+    heading.push_str(&task.title);
+
+    let names: Vec<String> = tags
+        .iter()
+        .filter(|t| task.tags.contains(&t.id))
+        .map(|t| t.name.split_whitespace().collect::<Vec<_>>().join("_"))
+        .collect();
+    if !names.is_empty() {
+        heading.push_str(&format!(" :{}:", names.join(":")));
+    }
+    out.push_str(&heading);
+    out.push('\n');
+
+    let mut planning = Vec::new();
+    if let Some(done) = task.completed_at.as_deref().and_then(utc_to_local) {
+        planning.push(format!("CLOSED: [{}]", done.format("%Y-%m-%d %a %H:%M")));
+    }
+    if let Some(due) = &task.due_date {
+        if let Some(stamp) = org_timestamp(due, &task.repeat) {
+            planning.push(format!("DEADLINE: <{}>", stamp));
+        }
+    }
+    if !planning.is_empty() {
+        out.push_str(&format!("  {}\n", planning.join(" ")));
+    }
+    for line in task.description.lines() {
+        out.push_str(&format!("  {}\n", line));
+    }
+
+    for sub in &task.subtasks {
+        let keyword = if sub.completed { "DONE" } else { "TODO" };
+        out.push_str(&format!("{}* {} {}\n", stars, keyword, sub.title));
+    }
+}
+
+/// 截止时间转为 org 时间戳，如 2026-10-19 Mon 15:00 +1w
+fn org_timestamp(due: &str, repeat: &RepeatType) -> Option<String> {
+    let (date, time) = split_due(due)?;
+    let mut stamp = date.format("%Y-%m-%d %a").to_string();
+    if let Some(time) = time {
+        stamp.push_str(&time.format(" %H:%M").to_string());
+    }
+    match repeat {
+        RepeatType::Daily => stamp.push_str(" +1d"),
+        RepeatType::Weekly => stamp.push_str(" +1w"),
+        RepeatType::Monthly => stamp.push_str(" +1m"),
+        RepeatType::Custom | RepeatType::None => {}
+    }
+    Some(stamp)
+}
+
+/// 解析 org-mode 大纲：带 TODO/DONE 的标题为任务，任务下的标题为子任务，
+/// 不在任务下且没有关键字的一级标题为项目
+pub fn parse_org(content: &str) -> Result<ImportPreview, String> {
+    let mut preview = ImportPreview::default();
+    let mut project: Option<String> = None;
+    // 当前任务在 preview.tasks 中的下标及其标题层级
+    let mut current: Option<(usize, usize)> = None;
+    let mut in_drawer = false;
+
+    for line in content.lines() {
+        if let Some((level, heading)) = parse_heading(line) {
+            in_drawer = false;
+            if let Some((index, task_level)) = current {
+                if level > task_level {
+                    preview.tasks[index].subtasks.push(ImportSubTask {
+                        title: heading.title,
+                        completed: heading.done,
+                    });
+                    continue;
+                }
+            }
+            current = None;
+
+            if heading.keyword.is_none() && level == 1 {
+                project = Some(heading.title);
+                continue;
+            }
+            current = Some((preview.tasks.len(), level));
+            preview.tasks.push(ImportTask {
+                title: heading.title,
+                description: String::new(),
+                priority: heading.priority.unwrap_or(Priority::Medium),
+                project: if level == 1 { None } else { project.clone() },
+                tags: heading.tags,
+                due_date: None,
+                repeat: RepeatType::None,
+                completed: heading.done,
+                completed_at: None,
+                subtasks: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some((index, _)) = current else {
+            continue;
+        };
+        let task = &mut preview.tasks[index];
+        let trimmed = line.trim();
+
+        // 跳过 :PROPERTIES: 等抽屉
+        if trimmed.starts_with(':') && trimmed.ends_with(':') && trimmed.len() > 1 {
+            in_drawer = trimmed != ":END:";
+            continue;
+        }
+        if in_drawer {
+            continue;
+        }
+
+        if trimmed.starts_with("DEADLINE:")
+            || trimmed.starts_with("SCHEDULED:")
+            || trimmed.starts_with("CLOSED:")
+        {
+            if let Some(stamp) = planning_value(trimmed, "DEADLINE:", '<', '>') {
+                let (due, repeat) = parse_org_timestamp(stamp);
+                task.due_date = due;
+                task.repeat = repeat;
+            }
+            if let Some(stamp) = planning_value(trimmed, "CLOSED:", '[', ']') {
+                task.completed_at = parse_org_timestamp(stamp).0.as_deref().and_then(local_to_utc);
+            }
+            continue;
+        }
+
+        if !task.description.is_empty() || !trimmed.is_empty() {
+            if !task.description.is_empty() {
+                task.description.push('\n');
+            }
+            task.description.push_str(trimmed);
+        }
+    }
+
+    for task in &mut preview.tasks {
+        task.description = task.description.trim_end().to_string();
+    }
+    Ok(preview)
+}
+
+struct OrgHeading {
+    keyword: Option<&'static str>,
+    done: bool,
+    priority: Option<Priority>,
+    title: String,
+    tags: Vec<String>,
+}
+
+fn parse_heading(line: &str) -> Option<(usize, OrgHeading)> {
+    let level = line.chars().take_while(|c| *c == '*').count();
+    if level == 0 {
+        return None;
+    }
+    let mut rest = line[level..].strip_prefix(' ')?.trim();
+
+    let mut keyword = None;
+    for k in ["TODO", "DONE"] {
+        if rest == k || rest.starts_with(&format!("{} ", k)) {
+            keyword = Some(k);
+            rest = rest[k.len()..].trim_start();
+            break;
+        }
+    }
+
+    let mut priority = None;
+    if let Some(after) = rest.strip_prefix("[#") {
+        let mut chars = after.chars();
+        if let (Some(letter), Some(']')) = (chars.next(), chars.next()) {
+            priority = Some(match letter {
+                'A' => Priority::High,
+                'B' => Priority::Medium,
+                _ => Priority::Low,
+            });
+            rest = chars.as_str().trim_start();
+        }
+    }
+
+    // 行尾 :tag1:tag2:
+    let mut tags = Vec::new();
+    if let Some((title, last)) = rest.rsplit_once(' ') {
+        if last.len() > 2 && last.starts_with(':') && last.ends_with(':') {
+            tags = last
+                .trim_matches(':')
+                .split(':')
+                .filter(|t| !t.is_empty())
+                .map(|t| t.replace('_', " "))
+                .collect();
+            rest = title.trim_end();
+        }
+    }
+
+    Some((
+        level,
+        OrgHeading {
+            keyword,
+            done: keyword == Some("DONE"),
+            priority,
+            title: rest.to_string(),
+            tags,
+        },
+    ))
+}
+
+/// 取出规划行中某个关键字后的时间戳内容
+fn planning_value<'a>(line: &'a str, key: &str, open: char, close: char) -> Option<&'a str> {
+    let after = &line[line.find(key)? + key.len()..];
+    let start = after.find(open)? + open.len_utf8();
+    let end = after[start..].find(close)? + start;
+    Some(&after[start..end])
+}
+
+/// 解析 2026-10-19 Mon 15:00 +1w 形式的时间戳
+fn parse_org_timestamp(stamp: &str) -> (Option<String>, RepeatType) {
+    let mut parts = stamp.split_whitespace();
+    let Some(date) = parts.next().and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()) else {
+        return (None, RepeatType::None);
+    };
+
+    let mut time = None;
+    let mut repeat = RepeatType::None;
+    for part in parts {
+        if let Ok(t) = NaiveTime::parse_from_str(part, "%H:%M") {
+            time = Some(t);
+        } else if let Some(interval) = part
+            .strip_prefix(".+")
+            .or_else(|| part.strip_prefix("++"))
+            .or_else(|| part.strip_prefix('+'))
+        {
+            repeat = match interval {
+                "1d" => RepeatType::Daily,
+                "1w" => RepeatType::Weekly,
+                "1m" => RepeatType::Monthly,
+                _ => RepeatType::Custom,
+            };
+        }
+    }
+
+    let due = match time {
+        Some(t) => date.and_time(t).format("%Y-%m-%dT%H:%M").to_string(),
+        None => date.format("%Y-%m-%d").to_string(),
+    };
+    (Some(due), repeat)
+}
+
+// ========== OPML ==========
+
+/// 导出为 OPML 2.0：项目节点带 type="project"，任务属性写在 outline 上
+pub fn export_opml(conn: &Connection) -> Result<String, String> {
+    let (tasks, projects, tags) = load(conn)?;
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n");
+    out.push_str("  <head>\n    <title>干了么</title>\n");
+    out.push_str(&format!(
+        "    <dateCreated>{}</dateCreated>\n  </head>\n  <body>\n",
+        chrono::Utc::now().to_rfc2822()
+    ));
+
+    for task in tasks.iter().filter(|t| t.project_id.is_none()) {
+        write_opml_task(&mut out, task, 2, &tags);
+    }
+    for project in &projects {
+        out.push_str(&format!(
+            "    <outline text=\"{}\" type=\"project\">\n",
+            escape(project.name.as_str())
+        ));
+        for task in tasks.iter().filter(|t| t.project_id.as_ref() == Some(&project.id)) {
+            write_opml_task(&mut out, task, 3, &tags);
+        }
+        out.push_str("    </outline>\n");
+    }
+
+    out.push_str("  </body>\n</opml>\n");
+    Ok(out)
+}
+
+fn write_opml_task(out: &mut String, task: &Task, depth: usize, tags: &[Tag]) {
+    let indent = "  ".repeat(depth);
+    let mut attrs = vec![
+        ("text", task.title.clone()),
+        ("priority", task.priority.as_str().to_string()),
+    ];
+    if !task.description.is_empty() {
+        attrs.push(("_note", task.description.clone()));
+    }
+    if task.status == TaskStatus::Completed {
+        attrs.push(("_complete", "true".to_string()));
+    }
+    if let Some(done) = &task.completed_at {
+        attrs.push(("completed", done.clone()));
+    }
+    if let Some(due) = &task.due_date {
+        attrs.push(("due", due.clone()));
+    }
+    if task.repeat != RepeatType::None {
+        attrs.push(("repeat", task.repeat.as_str().to_string()));
+    }
+    let names: Vec<&str> = tags
+        .iter()
+        .filter(|t| task.tags.contains(&t.id))
+        .map(|t| t.name.as_str())
+        .collect();
+    if !names.is_empty() {
+        attrs.push(("tags", names.join(",")));
+    }
+
+    let attrs: String = attrs
+        .iter()
+        // 属性值中的换行需写成字符引用，否则解析时会被规范化为空格
+        .map(|(k, v)| format!(" {}=\"{}\"", k, escape(v.as_str()).replace('\n', "&#10;")))
+        .collect();
+
+    if task.subtasks.is_empty() {
+        out.push_str(&format!("{}<outline{}/>\n", indent, attrs));
+        return;
+    }
+    out.push_str(&format!("{}<outline{}>\n", indent, attrs));
+    for sub in &task.subtasks {
+        let complete = if sub.completed { " _complete=\"true\"" } else { "" };
+        out.push_str(&format!(
+            "{}  <outline text=\"{}\"{}/>\n",
+            indent,
+            escape(sub.title.as_str()),
+            complete
+        ));
+    }
+    out.push_str(&format!("{}</outline>\n", indent));
+}
+
+/// OPML 节点
+#[derive(Default)]
+struct OutlineNode {
+    attrs: HashMap<String, String>,
+    children: Vec<OutlineNode>,
+}
+
+impl OutlineNode {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs.get(key).map(|s| s.as_str()).filter(|s| !s.is_empty())
+    }
+
+    /// 带 type="project" 或（外部文件）有三层结构的顶层节点视为项目
+    fn is_project(&self) -> bool {
+        match self.attr("type") {
+            Some(kind) => kind == "project",
+            None => self.children.iter().any(|c| !c.children.is_empty()),
+        }
+    }
+}
+
+/// 解析 OPML：顶层项目节点下的节点为任务，任务下所有层级的节点都作为子任务
+pub fn parse_opml(content: &str) -> Result<ImportPreview, String> {
+    let roots = read_outlines(content)?;
+    let mut preview = ImportPreview::default();
+
+    for node in &roots {
+        if node.is_project() {
+            let project = node.attr("text").map(|s| s.to_string());
+            for child in &node.children {
+                preview.tasks.push(opml_task(child, project.clone()));
+            }
+        } else {
+            preview.tasks.push(opml_task(node, None));
+        }
+    }
+
+    Ok(preview)
+}
+
+fn opml_task(node: &OutlineNode, project: Option<String>) -> ImportTask {
+    let mut subtasks = Vec::new();
+    flatten_subtasks(&node.children, &mut subtasks);
+
+    ImportTask {
+        title: node.attr("text").unwrap_or_default().to_string(),
+        description: node.attr("_note").unwrap_or_default().to_string(),
+        priority: Priority::from_str(node.attr("priority").unwrap_or_default()),
+        project,
+        tags: node
+            .attr("tags")
+            .unwrap_or_default()
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        due_date: node.attr("due").map(|s| s.to_string()),
+        repeat: RepeatType::from_str(node.attr("repeat").unwrap_or_default()),
+        completed: node.attr("_complete") == Some("true"),
+        completed_at: node.attr("completed").map(|s| s.to_string()),
+        subtasks,
+    }
+}
+
+fn flatten_subtasks(nodes: &[OutlineNode], out: &mut Vec<ImportSubTask>) {
+    for node in nodes {
+        if let Some(title) = node.attr("text") {
+            out.push(ImportSubTask {
+                title: title.to_string(),
+                completed: node.attr("_complete") == Some("true"),
+            });
+        }
+        flatten_subtasks(&node.children, out);
+    }
+}
+
+/// 读取 body 下的 outline 树
+fn read_outlines(content: &str) -> Result<Vec<OutlineNode>, String> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut stack: Vec<OutlineNode> = vec![OutlineNode::default()];
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) if e.name().as_ref() == b"outline" => {
+                stack.push(read_attrs(&e)?);
+            }
+            Event::Empty(e) if e.name().as_ref() == b"outline" => {
+                let node = read_attrs(&e)?;
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Event::End(e) if e.name().as_ref() == b"outline" && stack.len() > 1 => {
+                let node = stack.pop().unwrap_or_default();
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err("OPML 结构不完整".to_string());
+    }
+    Ok(stack.pop().map(|root| root.children).unwrap_or_default())
+}
+
+fn read_attrs(e: &BytesStart) -> Result<OutlineNode, String> {
+    let mut node = OutlineNode::default();
+    for attr in e.attributes() {
+        let attr = attr.map_err(|e| e.to_string())?;
+        let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+        let value = attr.unescape_value().map_err(|e| e.to_string())?;
+        node.attrs.insert(key, value.to_string());
+    }
+    Ok(node)
+}
+
+// ========== 通用工具 ==========
+
+/// 导出所需的全部数据
+type Snapshot = (Vec<Task>, Vec<Project>, Vec<Tag>);
+
+fn load(conn: &Connection) -> Result<Snapshot, String> {
+    Ok((
+        task_repo::get_all(conn)?,
+        project_repo::get_all(conn)?,
+        tag_repo::get_all(conn)?,
+    ))
+}
+
+/// 拆分 YYYY-MM-DD / YYYY-MM-DDTHH:mm 形式的截止时间
+fn split_due(due: &str) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    match due.split_once('T') {
+        Some((date, time)) => Some((
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?,
+            NaiveTime::parse_from_str(time, "%H:%M")
+                .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+                .ok(),
+        )),
+        None => Some((NaiveDate::parse_from_str(due, "%Y-%m-%d").ok()?, None)),
+    }
+}
+
+fn utc_to_local(value: &str) -> Option<NaiveDateTime> {
+    let utc = chrono::DateTime::parse_from_rfc3339(value).ok()?;
+    Some(utc.with_timezone(&chrono::Local).naive_local())
+}
+
+/// 本地时间（YYYY-MM-DD 或 YYYY-MM-DDTHH:mm）转为数据库使用的 UTC 字符串
+fn local_to_utc(value: &str) -> Option<String> {
+    let (date, time) = split_due(value)?;
+    let naive = date.and_time(time.unwrap_or_default());
+    let local = chrono::Local.from_local_datetime(&naive).single()?;
+    Some(
+        local
+            .with_timezone(&chrono::Utc)
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection;
+    use crate::models::task::Due;
+
+    /// 一个项目下的带标签、子任务的重复任务，加一个已完成的未归属任务
+    fn sample() -> Connection {
+        let conn = connection::memory();
+        project_repo::create(&conn, "p1", "工作", "#3b82f6", "briefcase").unwrap();
+        tag_repo::create(&conn, "t1", "深度 工作", "#ef4444").unwrap();
+        let due = Due::AllDay { date: NaiveDate::from_ymd_opt(2026, 10, 23).unwrap() };
+        task_repo::create(&conn, "a", "写周报", "high", Some("p1"), Some(&due), None, None, "weekly", &["t1".to_string()])
+            .unwrap();
+        task_repo::update(
+            &conn, "a", None, Some("第一行\n第二行"), None, None, None, None, None, None, None, None, None,
+        )
+        .unwrap();
+        task_repo::add_subtask(&conn, "s1", "a", "收集数据").unwrap();
+        task_repo::toggle_subtask(&conn, "a", "s1").unwrap();
+        task_repo::create(&conn, "b", "买牛奶", "low", None, None, None, None, "none", &[]).unwrap();
+        task_repo::set_completion(&conn, "b", Some("2026-10-18T02:30:00Z")).unwrap();
+        conn
+    }
+
+    fn assert_round_trip(preview: &ImportPreview) {
+        assert_eq!(preview.tasks.len(), 2);
+        let milk = &preview.tasks[0];
+        assert_eq!(milk.title, "买牛奶");
+        assert_eq!(milk.priority, Priority::Low);
+        assert_eq!(milk.project, None);
+        assert!(milk.completed);
+        assert_eq!(milk.completed_at.as_deref(), Some("2026-10-18T02:30:00Z"));
+
+        let report = &preview.tasks[1];
+        assert_eq!(report.title, "写周报");
+        assert_eq!(report.description, "第一行\n第二行");
+        assert_eq!(report.priority, Priority::High);
+        assert_eq!(report.project.as_deref(), Some("工作"));
+        assert_eq!(report.tags, vec!["深度 工作"]);
+        assert_eq!(report.due_date.as_deref(), Some("2026-10-23"));
+        assert_eq!(report.repeat, RepeatType::Weekly);
+        assert!(!report.completed);
+        assert_eq!(report.subtasks.len(), 1);
+        assert_eq!(report.subtasks[0].title, "收集数据");
+        assert!(report.subtasks[0].completed);
+    }
+
+    #[test]
+    fn org_round_trip() {
+        let org = export_org(&sample()).unwrap();
+        assert!(org.contains("** TODO [#A] 写周报 :深度_工作:\n  DEADLINE: <2026-10-23 Fri +1w>\n"));
+        assert_round_trip(&parse_org(&org).unwrap());
+    }
+
+    #[test]
+    fn opml_round_trip() {
+        let opml = export_opml(&sample()).unwrap();
+        assert_round_trip(&parse_opml(&opml).unwrap());
+    }
+
+    #[test]
+    fn org_skips_drawers_and_reads_timed_repeats() {
+        let org = "* 生活\n** TODO 浇花\n   :PROPERTIES:\n   :ID: 1\n   :END:\n   SCHEDULED: <2026-10-19 Mon> DEADLINE: <2026-10-19 Mon 18:30 .+2d>\n   记得换水\n";
+        let preview = parse_org(org).unwrap();
+        let task = &preview.tasks[0];
+        assert_eq!(task.project.as_deref(), Some("生活"));
+        assert_eq!(task.due_date.as_deref(), Some("2026-10-19T18:30"));
+        assert_eq!(task.repeat, RepeatType::Custom);
+        assert_eq!(task.description, "记得换水");
+    }
+
+    #[test]
+    fn opml_from_other_apps() {
+        let opml = r#"<opml version="2.0"><body>
+            <outline text="旅行">
+              <outline text="订机票"><outline text="比价"><outline text="看里程"/></outline></outline>
+              <outline text="收拾行李"/>
+            </outline>
+            <outline text="散步"/>
+          </body></opml>"#;
+        let preview = parse_opml(opml).unwrap();
+        let titles: Vec<&str> = preview.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["订机票", "收拾行李", "散步"]);
+        assert_eq!(preview.tasks[0].project.as_deref(), Some("旅行"));
+        assert_eq!(preview.tasks[0].subtasks.len(), 2);
+        assert_eq!(preview.tasks[2].project, None);
+        assert!(parse_opml("<opml><body><outline text=\"a\">").is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::models::pomodoro::{PomodoroPhase, PomodoroSettings, PomodoroStatus};
+use chrono::{DateTime, Duration, Utc};
+
+/// 完成的一轮专注
+#[derive(Debug, Clone)]
+pub struct FocusSession {
+    pub task_id: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// 专注时长（秒），不含暂停的时间
+    pub seconds: i64,
+}
+
+/// 一次阶段切换
+#[derive(Debug, Clone)]
+pub struct PhaseChange {
+    pub finished: PomodoroPhase,
+    pub next: PomodoroPhase,
+    /// 到时完成的专注，跳过的不算
+    pub session: Option<FocusSession>,
+}
+
+/// 番茄钟状态机：专注与休息交替，每完成若干轮专注长休息一次。
+/// 当前时间由调用方传入，由应用每秒驱动
+#[derive(Debug, Clone)]
+pub struct Pomodoro {
+    settings: PomodoroSettings,
+    phase: PomodoroPhase,
+    task_id: Option<String>,
+    round: u32,
+    /// 当前阶段开始时间
+    started_at: DateTime<Utc>,
+    /// 当前阶段结束时间，暂停或未开始时为空
+    ends_at: Option<DateTime<Utc>>,
+    /// 暂停时剩余的秒数
+    paused_remaining: Option<i64>,
+}
+
+impl Default for Pomodoro {
+    fn default() -> Self {
+        Self {
+            settings: PomodoroSettings::default(),
+            phase: PomodoroPhase::Idle,
+            task_id: None,
+            round: 0,
+            started_at: Utc::now(),
+            ends_at: None,
+            paused_remaining: None,
+        }
+    }
+}
+
+impl Pomodoro {
+    pub fn phase(&self) -> PomodoroPhase {
+        self.phase
+    }
+
+    pub fn task_id(&self) -> Option<&str> {
+        self.task_id.as_deref()
+    }
+
+    /// 开始一轮专注。从未开始状态开始时轮数归零，进行中重新开始则保留轮数
+    pub fn start(&mut self, settings: PomodoroSettings, task_id: Option<String>, now: DateTime<Utc>) {
+        if self.phase == PomodoroPhase::Idle {
+            self.round = 0;
+        }
+        self.settings = settings;
+        self.task_id = task_id;
+        self.enter(PomodoroPhase::Work, now);
+    }
+
+    /// 暂停当前阶段，返回状态是否发生变化
+    pub fn pause(&mut self, now: DateTime<Utc>) -> bool {
+        let Some(ends_at) = self.ends_at.take() else {
+            return false;
+        };
+        self.paused_remaining = Some((ends_at - now).num_seconds().max(0));
+        true
+    }
+
+    /// 继续暂停的阶段，返回状态是否发生变化
+    pub fn resume(&mut self, now: DateTime<Utc>) -> bool {
+        let Some(remaining) = self.paused_remaining.take() else {
+            return false;
+        };
+        self.ends_at = Some(now + Duration::seconds(remaining));
+        true
+    }
+
+    /// 结束番茄钟，回到未开始状态
+    pub fn stop(&mut self) {
+        self.phase = PomodoroPhase::Idle;
+        self.task_id = None;
+        self.round = 0;
+        self.ends_at = None;
+        self.paused_remaining = None;
+    }
+
+    /// 跳过当前阶段，跳过的专注不记录也不计入轮数
+    pub fn skip(&mut self, now: DateTime<Utc>) -> Option<PhaseChange> {
+        if self.phase == PomodoroPhase::Idle {
+            return None;
+        }
+        Some(self.advance(now, false))
+    }
+
+    /// 当前阶段到时则进入下一阶段。下一阶段从到时的那一刻算起，驱动稍有延迟也不会累积误差
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Option<PhaseChange> {
+        match self.ends_at {
+            Some(ends_at) if now >= ends_at => Some(self.advance(ends_at, true)),
+            _ => None,
+        }
+    }
+
+    /// 当前阶段剩余秒数
+    pub fn remaining_seconds(&self, now: DateTime<Utc>) -> i64 {
+        match (self.ends_at, self.paused_remaining) {
+            (Some(ends_at), _) => (ends_at - now).num_seconds().max(0),
+            (None, Some(remaining)) => remaining,
+            (None, None) => 0,
+        }
+    }
+
+    pub fn status(&self, now: DateTime<Utc>) -> PomodoroStatus {
+        PomodoroStatus {
+            phase: self.phase,
+            task_id: self.task_id.clone(),
+            round: self.round,
+            remaining_seconds: self.remaining_seconds(now),
+            ends_at: self
+                .ends_at
+                .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+            paused: self.paused_remaining.is_some(),
+            settings: self.settings.clone(),
+        }
+    }
+
+    fn advance(&mut self, now: DateTime<Utc>, completed: bool) -> PhaseChange {
+        let finished = self.phase;
+        let mut session = None;
+        let next = if finished == PomodoroPhase::Work {
+            if completed {
+                self.round += 1;
+                session = Some(FocusSession {
+                    task_id: self.task_id.clone(),
+                    started_at: self.started_at,
+                    ended_at: now,
+                    seconds: i64::from(self.settings.work_minutes) * 60,
+                });
+            }
+            if completed && self.round >= self.settings.long_break_every.max(1) {
+                PomodoroPhase::LongBreak
+            } else {
+                PomodoroPhase::ShortBreak
+            }
+        } else {
+            if finished == PomodoroPhase::LongBreak {
+                self.round = 0;
+            }
+            PomodoroPhase::Work
+        };
+        self.enter(next, now);
+        PhaseChange { finished, next, session }
+    }
+
+    fn enter(&mut self, phase: PomodoroPhase, now: DateTime<Utc>) {
+        let minutes = match phase {
+            PomodoroPhase::Idle => 0,
+            PomodoroPhase::Work => self.settings.work_minutes,
+            PomodoroPhase::ShortBreak => self.settings.short_break_minutes,
+            PomodoroPhase::LongBreak => self.settings.long_break_minutes,
+        };
+        self.phase = phase;
+        self.started_at = now;
+        self.paused_remaining = None;
+        self.ends_at = Some(now + Duration::minutes(i64::from(minutes)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    fn settings() -> PomodoroSettings {
+        PomodoroSettings {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_every: 2,
+        }
+    }
+
+    #[test]
+    fn cycles_through_breaks() {
+        let mut pomodoro = Pomodoro::default();
+        pomodoro.start(settings(), Some("task-1".to_string()), at(0));
+        assert!(pomodoro.tick(at(24)).is_none());
+
+        // 驱动晚了 1 分钟，下一阶段仍从到时的那一刻算起
+        let change = pomodoro.tick(at(26)).unwrap();
+        assert_eq!((change.finished, change.next), (PomodoroPhase::Work, PomodoroPhase::ShortBreak));
+        let session = change.session.unwrap();
+        assert_eq!(session.task_id.as_deref(), Some("task-1"));
+        assert_eq!((session.started_at, session.ended_at, session.seconds), (at(0), at(25), 1500));
+        assert_eq!(pomodoro.remaining_seconds(at(26)), 4 * 60);
+
+        assert_eq!(pomodoro.tick(at(30)).unwrap().next, PomodoroPhase::Work);
+        assert_eq!(pomodoro.tick(at(55)).unwrap().next, PomodoroPhase::LongBreak);
+        assert_eq!(pomodoro.status(at(55)).round, 2);
+        assert_eq!(pomodoro.tick(at(70)).unwrap().next, PomodoroPhase::Work);
+        assert_eq!(pomodoro.status(at(70)).round, 0);
+    }
+
+    #[test]
+    fn pause_and_resume_keep_remaining_time() {
+        let mut pomodoro = Pomodoro::default();
+        pomodoro.start(settings(), None, at(0));
+        assert!(pomodoro.pause(at(10)));
+        assert!(!pomodoro.pause(at(11)));
+        let status = pomodoro.status(at(30));
+        assert!(status.paused && status.ends_at.is_none());
+        assert_eq!(status.remaining_seconds, 15 * 60);
+        assert!(pomodoro.tick(at(40)).is_none());
+
+        assert!(pomodoro.resume(at(40)));
+        assert!(!pomodoro.resume(at(41)));
+        assert!(pomodoro.tick(at(54)).is_none());
+        assert_eq!(pomodoro.tick(at(55)).unwrap().finished, PomodoroPhase::Work);
+    }
+
+    #[test]
+    fn skipped_work_is_not_recorded() {
+        let mut pomodoro = Pomodoro::default();
+        assert!(pomodoro.skip(at(0)).is_none());
+        pomodoro.start(settings(), None, at(0));
+        let change = pomodoro.skip(at(3)).unwrap();
+        assert_eq!(change.next, PomodoroPhase::ShortBreak);
+        assert!(change.session.is_none());
+        assert_eq!(pomodoro.status(at(3)).round, 0);
+
+        pomodoro.stop();
+        assert_eq!(pomodoro.phase(), PomodoroPhase::Idle);
+        assert!(pomodoro.tick(at(100)).is_none());
+        assert_eq!(pomodoro.remaining_seconds(at(100)), 0);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::db::{settings_repo, task_repo};
+use crate::models::task::{Task, TaskStatus};
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+/// 已提醒的任务ID集合（避免重复提醒）
+static REMINDED_IDS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// 检查是否有需要提醒的任务
+/// 返回第一个需要提醒的任务（截止前5分钟内）
+pub fn check_reminders(conn: &Connection) -> Result<Option<Task>, String> {
+    let tasks = task_repo::get_all(conn)?;
+    let tz = settings_repo::timezone(conn);
+    let now = chrono::Utc::now();
+    let five_minutes = chrono::Duration::minutes(5);
+
+    let mut reminded = REMINDED_IDS.lock().map_err(|e| e.to_string())?;
+
+    for task in &tasks {
+        if task.status == TaskStatus::Completed {
+            continue;
+        }
+        if reminded.contains(&task.id) {
+            continue;
+        }
+
+        // 全天任务在用户时区下当天结束时到期
+        let due_time = match &task.due {
+            Some(due) => due.deadline(tz),
+            None => continue,
+        };
+
+        let diff = due_time - now;
+
+        // 剩余 ≤ 5分钟且未过期
+        if diff > chrono::Duration::zero() && diff <= five_minutes {
+            reminded.insert(task.id.clone());
+            return Ok(Some(task.clone()));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,133 @@
+use crate::db::task_repo;
+use crate::models::task::{RepeatType, Task};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+use uuid::Uuid;
+
+/// 完成重复任务时，生成下一个任务。截止时间与开始日期一起顺延；
+/// 两者都没有时无法推算下一次，不生成
+pub fn create_next_repeat_task(conn: &Connection, task: &Task) -> Result<Option<Task>, String> {
+    let supported = matches!(task.repeat, RepeatType::Daily | RepeatType::Weekly | RepeatType::Monthly);
+    if !supported || (task.due.is_none() && task.start_date.is_none()) {
+        return Ok(None);
+    }
+
+    // 根据重复类型计算下一个截止时间（带钟点的按设定时区顺延，跨夏令时仍是同一钟点）
+    let next_due = match &task.due {
+        Some(due) => Some(
+            match task.repeat {
+                RepeatType::Daily => due.shift_days(1),
+                RepeatType::Weekly => due.shift_days(7),
+                RepeatType::Monthly => due.map_date(next_month),
+                _ => None,
+            }
+            .ok_or("无法计算下一个截止时间")?,
+        ),
+        None => None,
+    };
+    let next_start = match task.start_date {
+        Some(start) => Some(next_date(&task.repeat, start).ok_or("无法计算下一个开始日期")?),
+        None => None,
+    };
+
+    let new_id = format!("task-{}", Uuid::new_v4());
+
+    // 创建新任务
+    let new_task = task_repo::create(
+        conn,
+        &new_id,
+        &task.title,
+        task.priority.as_str(),
+        task.project_id.as_deref(),
+        next_due.as_ref(),
+        next_start,
+        task.estimate_minutes,
+        task.repeat.as_str(),
+        &task.tags,
+    )?;
+
+    Ok(Some(new_task))
+}
+
+/// 按重复类型推算下一个日期
+fn next_date(repeat: &RepeatType, date: NaiveDate) -> Option<NaiveDate> {
+    match repeat {
+        RepeatType::Daily => date.checked_add_signed(chrono::Duration::days(1)),
+        RepeatType::Weekly => date.checked_add_signed(chrono::Duration::days(7)),
+        RepeatType::Monthly => next_month(date),
+        _ => None,
+    }
+}
+
+/// 下个月的同一天，该月没有这一天时顺延 30 天
+fn next_month(date: NaiveDate) -> Option<NaiveDate> {
+    // 月份加1
+    let month = date.month();
+    let year = date.year();
+    let (new_year, new_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(new_year, new_month, date.day())
+        .or_else(|| date.checked_add_signed(chrono::Duration::days(30)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection;
+    use crate::models::task::Due;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn create(conn: &Connection, due: Option<&Due>, start: Option<NaiveDate>, repeat: RepeatType) -> Task {
+        let id = format!("task-{}", Uuid::new_v4());
+        task_repo::create(conn, &id, "周报", "high", None, due, start, Some(30), repeat.as_str(), &[]).unwrap()
+    }
+
+    #[test]
+    fn next_month_falls_back_for_short_months() {
+        assert_eq!(next_month(date(2026, 10, 19)), Some(date(2026, 11, 19)));
+        assert_eq!(next_month(date(2026, 12, 31)), Some(date(2027, 1, 31)));
+        assert_eq!(next_month(date(2026, 1, 31)), Some(date(2026, 3, 2)));
+    }
+
+    #[test]
+    fn shifts_due_and_start_together() {
+        let conn = connection::memory();
+        let due = Due::AllDay { date: date(2026, 10, 23) };
+        let task = create(&conn, Some(&due), Some(date(2026, 10, 19)), RepeatType::Weekly);
+        let next = create_next_repeat_task(&conn, &task).unwrap().unwrap();
+        assert_ne!(next.id, task.id);
+        assert_eq!(next.due, Some(Due::AllDay { date: date(2026, 10, 30) }));
+        assert_eq!(next.start_date, Some(date(2026, 10, 26)));
+        assert_eq!(next.estimate_minutes, Some(30));
+        assert_eq!(next.repeat, RepeatType::Weekly);
+    }
+
+    #[test]
+    fn monthly_keeps_wall_clock() {
+        let conn = connection::memory();
+        let tz = chrono_tz::America::New_York;
+        let due = Due::parse("2026-10-19 09:00", tz).unwrap();
+        let task = create(&conn, Some(&due), None, RepeatType::Monthly);
+        let next = create_next_repeat_task(&conn, &task).unwrap().unwrap();
+        // 11 月已回到标准时间，钟点仍是 09:00
+        assert_eq!(next.due.unwrap().display(tz), "2026-11-19T09:00");
+        assert_eq!(next.start_date, None);
+    }
+
+    #[test]
+    fn skips_without_dates_or_rule() {
+        let conn = connection::memory();
+        let undated = create(&conn, None, None, RepeatType::Daily);
+        assert!(create_next_repeat_task(&conn, &undated).unwrap().is_none());
+        let due = Due::AllDay { date: date(2026, 10, 23) };
+        let custom = create(&conn, Some(&due), None, RepeatType::Custom);
+        assert!(create_next_repeat_task(&conn, &custom).unwrap().is_none());
+        assert_eq!(task_repo::get_all(&conn).unwrap().len(), 2);
+    }
+}
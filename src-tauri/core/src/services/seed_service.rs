@@ -0,0 +1,255 @@
+use crate::db::connection::transaction;
+use crate::db::{project_repo, settings_repo, tag_repo, task_repo};
+use crate::models::import::ImportSummary;
+use crate::models::seed::{SeedChoice, SeedPack, SetupStatus};
+use crate::models::task::Due;
+use crate::services::task_service;
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// 记录所选初始数据的设置项，未设置表示尚未完成首次启动选择
+const SEED_CHOICE_KEY: &str = "seed_choice";
+
+/// 内置示例数据
+const SAMPLE_DATA: &str = include_str!("sample_data.json");
+
+/// 旧版本无条件插入的示例数据（固定 ID 与名称）
+const LEGACY_SAMPLE: &[(&str, &str, &str)] = &[
+    ("project", "proj-1", "工作项目"),
+    ("project", "proj-2", "技术学习"),
+    ("project", "proj-3", "个人生活"),
+    ("tag", "tag-1", "重要"),
+    ("tag", "tag-2", "会议"),
+    ("tag", "tag-3", "学习"),
+    ("task", "1", "完成项目提案文档"),
+    ("task", "2", "团队周会准备"),
+    ("task", "3", "阅读 Vue 3 官方文档"),
+];
+
+/// 是否需要首次启动选择：没有选择过，且数据库中还没有任何数据
+pub fn setup_status(conn: &Connection) -> Result<SetupStatus, String> {
+    let chosen = settings_repo::get_value(conn, SEED_CHOICE_KEY).is_some();
+    Ok(SetupStatus {
+        needs_setup: !chosen && is_empty(conn)?,
+        has_sample_data: count(
+            conn,
+            "SELECT COUNT(*) FROM sample_items s WHERE
+                (s.kind = 'task' AND EXISTS (SELECT 1 FROM tasks WHERE id = s.item_id))
+                OR (s.kind = 'project' AND EXISTS (SELECT 1 FROM projects WHERE id = s.item_id))
+                OR (s.kind = 'tag' AND EXISTS (SELECT 1 FROM tags WHERE id = s.item_id))",
+        )? > 0,
+    })
+}
+
+/// 按选择写入初始数据
+pub fn seed(conn: &Connection, choice: &SeedChoice) -> Result<ImportSummary, String> {
+    let summary = match choice {
+        SeedChoice::Empty => ImportSummary::default(),
+        SeedChoice::Sample => apply(conn, &parse_pack(SAMPLE_DATA)?, true)?,
+        SeedChoice::Template { content } => apply(conn, &parse_pack(content)?, false)?,
+    };
+    settings_repo::update_setting(conn, SEED_CHOICE_KEY, choice.as_str())?;
+    Ok(summary)
+}
+
+/// 解析 JSON 模板包
+pub fn parse_pack(content: &str) -> Result<SeedPack, String> {
+    serde_json::from_str(content).map_err(|e| format!("模板格式错误: {}", e))
+}
+
+/// 写入模板包。同名的项目和标签直接沿用；sample 为 true 时登记为示例数据
+pub fn apply(conn: &Connection, pack: &SeedPack, sample: bool) -> Result<ImportSummary, String> {
+    transaction(conn, || {
+        let mut summary = ImportSummary::default();
+        let mut project_ids: HashMap<String, String> = project_repo::get_all(conn)?
+            .into_iter()
+            .map(|p| (p.name, p.id))
+            .collect();
+        let mut tag_ids: HashMap<String, String> = tag_repo::get_all(conn)?
+            .into_iter()
+            .map(|t| (t.name, t.id))
+            .collect();
+
+        // 任务中引用、但未在列表中声明的项目和标签以默认样式创建
+        let projects = pack
+            .projects
+            .iter()
+            .map(|p| (p.name.as_str(), p.color.as_deref(), p.icon.as_deref()))
+            .chain(pack.tasks.iter().filter_map(|t| t.project.as_deref()).map(|name| (name, None, None)));
+        for (name, color, icon) in projects {
+            if project_ids.contains_key(name) {
+                continue;
+            }
+            let id = format!("proj-{}", Uuid::new_v4());
+            project_repo::create(conn, &id, name, color.unwrap_or("#228B22"), icon.unwrap_or("briefcase"))?;
+            if sample {
+                mark_sample(conn, "project", &id)?;
+            }
+            project_ids.insert(name.to_string(), id);
+            summary.projects += 1;
+        }
+
+        let tags = pack
+            .tags
+            .iter()
+            .map(|t| (t.name.as_str(), t.color.as_deref()))
+            .chain(pack.tasks.iter().flat_map(|t| t.tags.iter()).map(|name| (name.as_str(), None)));
+        for (name, color) in tags {
+            if tag_ids.contains_key(name) {
+                continue;
+            }
+            let id = format!("tag-{}", Uuid::new_v4());
+            tag_repo::create(conn, &id, name, color.unwrap_or("#3B82F6"))?;
+            if sample {
+                mark_sample(conn, "tag", &id)?;
+            }
+            tag_ids.insert(name.to_string(), id);
+            summary.tags += 1;
+        }
+
+        let today = task_service::today(settings_repo::timezone(conn));
+        for task in &pack.tasks {
+            let id = format!("task-{}", Uuid::new_v4());
+            let project_id = task.project.as_ref().and_then(|name| project_ids.get(name));
+            let tag_ids: Vec<String> = task.tags.iter().filter_map(|name| tag_ids.get(name).cloned()).collect();
+            let due = offset_date(today, task.due_in_days, &task.title)?.map(|date| Due::AllDay { date });
+            task_repo::create(
+                conn,
+                &id,
+                &task.title,
+                task.priority.as_ref().map_or("medium", |p| p.as_str()),
+                project_id.map(String::as_str),
+                due.as_ref(),
+                offset_date(today, task.start_in_days, &task.title)?,
+                task.estimate_minutes,
+                task.repeat.as_ref().map_or("none", |r| r.as_str()),
+                &tag_ids,
+            )?;
+            if !task.description.is_empty() {
+                task_repo::update(conn, &id, None, Some(&task.description), None, None, None, None, None, None, None, None, None)?;
+            }
+            if task.favorite {
+                task_repo::toggle_favorite(conn, &id)?;
+            }
+            for title in &task.subtasks {
+                task_repo::add_subtask(conn, &format!("sub-{}", Uuid::new_v4()), &id, title)?;
+                summary.subtasks += 1;
+            }
+            if sample {
+                mark_sample(conn, "task", &id)?;
+            }
+            summary.tasks += 1;
+        }
+        Ok(summary)
+    })
+}
+
+/// 删除全部示例数据（任务连同子任务一起删除），返回删除的任务ID
+pub fn remove_sample_data(conn: &Connection) -> Result<Vec<String>, String> {
+    transaction(conn, || {
+        let tasks = sample_ids(conn, "task")?;
+        for id in &tasks {
+            task_repo::delete(conn, id)?;
+        }
+        for id in sample_ids(conn, "project")? {
+            project_repo::delete(conn, &id)?;
+        }
+        for id in sample_ids(conn, "tag")? {
+            tag_repo::delete(conn, &id)?;
+        }
+        conn.execute("DELETE FROM sample_items", []).map_err(|e| e.to_string())?;
+        Ok(tasks)
+    })
+}
+
+/// 旧版本数据库：把仍保持原样的内置示例登记为示例数据，并视为已完成首次启动选择
+pub fn mark_legacy_sample_data(conn: &Connection) -> Result<(), String> {
+    if settings_repo::get_value(conn, SEED_CHOICE_KEY).is_some() || is_empty(conn)? {
+        return Ok(());
+    }
+    transaction(conn, || {
+        for (kind, id, name) in LEGACY_SAMPLE {
+            let sql = match *kind {
+                "project" => "SELECT COUNT(*) FROM projects WHERE id = ?1 AND name = ?2",
+                "tag" => "SELECT COUNT(*) FROM tags WHERE id = ?1 AND name = ?2",
+                _ => "SELECT COUNT(*) FROM tasks WHERE id = ?1 AND title = ?2",
+            };
+            let exists: i64 = conn
+                .query_row(sql, params![id, name], |row| row.get(0))
+                .map_err(|e| e.to_string())?;
+            if exists > 0 {
+                mark_sample(conn, kind, id)?;
+            }
+        }
+        settings_repo::update_setting(conn, SEED_CHOICE_KEY, "legacy")
+    })
+}
+
+/// 导入当天加上模板中的相对天数，超出日期范围时报错
+fn offset_date(today: NaiveDate, days: Option<i64>, title: &str) -> Result<Option<NaiveDate>, String> {
+    days.map(|days| {
+        Duration::try_days(days)
+            .and_then(|offset| today.checked_add_signed(offset))
+            .ok_or_else(|| format!("任务「{}」的相对天数超出范围: {}", title, days))
+    })
+    .transpose()
+}
+
+fn mark_sample(conn: &Connection, kind: &str, id: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR IGNORE INTO sample_items (kind, item_id) VALUES (?1, ?2)",
+        params![kind, id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn sample_ids(conn: &Connection, kind: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT item_id FROM sample_items WHERE kind = ?1")
+        .map_err(|e| e.to_string())?;
+    let ids = stmt
+        .query_map(params![kind], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(ids)
+}
+
+fn is_empty(conn: &Connection) -> Result<bool, String> {
+    Ok(count(conn, "SELECT (SELECT COUNT(*) FROM tasks) + (SELECT COUNT(*) FROM projects) + (SELECT COUNT(*) FROM tags)")? == 0)
+}
+
+fn count(conn: &Connection, sql: &str) -> Result<i64, String> {
+    conn.query_row(sql, [], |row| row.get(0)).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection;
+
+    #[test]
+    fn sample_pack_applies() {
+        let conn = connection::memory();
+        let summary = apply(&conn, &parse_pack(SAMPLE_DATA).unwrap(), true).unwrap();
+        assert_eq!(summary.projects, 3);
+        assert_eq!(task_repo::get_all(&conn).unwrap().len(), summary.tasks);
+        assert_eq!(remove_sample_data(&conn).unwrap().len(), summary.tasks);
+        assert!(is_empty(&conn).unwrap());
+    }
+
+    #[test]
+    fn out_of_range_offsets_are_rejected() {
+        let conn = connection::memory();
+        for field in ["dueInDays", "startInDays"] {
+            for days in ["9223372036854775807", "-400000000"] {
+                let pack = parse_pack(&format!(r#"{{"tasks": [{{"title": "远方", "{}": {}}}]}}"#, field, days)).unwrap();
+                assert!(apply(&conn, &pack, false).is_err(), "{} {}", field, days);
+            }
+        }
+        assert!(is_empty(&conn).unwrap());
+    }
+}
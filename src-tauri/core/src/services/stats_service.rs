@@ -0,0 +1,303 @@
+use crate::db::settings_repo;
+use crate::models::stats::{CompletionPoint, Distribution, StatsPeriod, StatsSummary};
+use chrono::{Offset, Utc};
+use rusqlite::{params, Connection, Row};
+
+/// 完成数序列最多的周期数
+const MAX_PERIODS: u32 = 366;
+
+/// 把 UTC 时间换算为用户时区的 SQLite 修饰符（如 "+28800 seconds"）。
+/// 按当前的 UTC 偏移换算，跨夏令时的早期记录可能差一小时
+fn local_offset(conn: &Connection) -> String {
+    let tz = settings_repo::timezone(conn);
+    let offset = Utc::now().with_timezone(&tz).offset().fix().local_minus_utc();
+    format!("{:+} seconds", offset)
+}
+
+/// 最近 count 个周期（含本周期）每个周期完成的任务数，按时间先后排列，没有完成的周期为 0
+pub fn completion_series(conn: &Connection, period: StatsPeriod, count: u32) -> Result<Vec<CompletionPoint>, String> {
+    // 本周期的第一天、上一周期的第一天、某一天所在周期的第一天
+    let (first, previous, bucket) = match period {
+        StatsPeriod::Day => ("date('now', ?1)", "date(start, '-1 day')", "date(completed_at, ?1)"),
+        StatsPeriod::Week => (
+            "date('now', ?1, 'weekday 0', '-6 days')",
+            "date(start, '-7 days')",
+            "date(completed_at, ?1, 'weekday 0', '-6 days')",
+        ),
+        StatsPeriod::Month => (
+            "date('now', ?1, 'start of month')",
+            "date(start, '-1 month')",
+            "date(completed_at, ?1, 'start of month')",
+        ),
+    };
+    let sql = format!(
+        "WITH RECURSIVE periods(n, start) AS (
+             SELECT 1, {first}
+             UNION ALL
+             SELECT n + 1, {previous} FROM periods WHERE n < ?2
+         ),
+         done AS (
+             SELECT {bucket} AS start, COUNT(*) AS completed
+             FROM tasks
+             WHERE status = 'completed' AND completed_at IS NOT NULL
+             GROUP BY 1
+         )
+         SELECT p.start, COALESCE(d.completed, 0)
+         FROM periods p LEFT JOIN done d ON d.start = p.start
+         ORDER BY p.start"
+    );
+
+    let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![local_offset(conn), count.clamp(1, MAX_PERIODS)], |row| {
+            Ok(CompletionPoint {
+                start: row.get(0)?,
+                completed: row.get(1)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+/// 统计概览：完成率、连续完成天数、平均耗时、逾期率与各分组的任务数
+pub fn summary(conn: &Connection) -> Result<StatsSummary, String> {
+    let offset = local_offset(conn);
+
+    let (total, completed, average_lead_hours): (i64, i64, Option<f64>) = conn
+        .query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(status = 'completed'), 0),
+                    AVG(CASE WHEN status = 'completed' AND completed_at IS NOT NULL
+                             THEN (julianday(completed_at) - julianday(created_at)) * 24 END)
+             FROM tasks",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // 连续完成天数：相邻日期减去行号后相同的为同一段连续日期
+    let (longest_streak, current_streak): (i64, i64) = conn
+        .query_row(
+            "WITH days AS (
+                 SELECT DISTINCT date(completed_at, ?1) AS day
+                 FROM tasks
+                 WHERE status = 'completed' AND completed_at IS NOT NULL
+             ),
+             runs AS (
+                 SELECT MAX(day) AS last_day, COUNT(*) AS len
+                 FROM (SELECT day, julianday(day) - ROW_NUMBER() OVER (ORDER BY day) AS grp FROM days)
+                 GROUP BY grp
+             )
+             SELECT COALESCE(MAX(len), 0),
+                    COALESCE(MAX(CASE WHEN last_day >= date('now', ?1, '-1 day') THEN len END), 0)
+             FROM runs",
+            params![&offset],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // 全天任务按用户时区的日期比较，带钟点的按 UTC 时刻比较
+    let (overdue, due_count): (i64, i64) = conn
+        .query_row(
+            "SELECT COALESCE(SUM(overdue), 0), COUNT(*)
+             FROM (
+                 SELECT CASE
+                            WHEN status = 'completed' AND due_tz IS NULL THEN date(completed_at, ?1) > due_date
+                            WHEN status = 'completed' THEN julianday(completed_at) > julianday(due_date)
+                            ELSE 1
+                        END AS overdue
+                 FROM tasks
+                 WHERE due_date IS NOT NULL
+                   AND (status = 'completed'
+                        OR (due_tz IS NULL AND due_date < date('now', ?1))
+                        OR (due_tz IS NOT NULL AND julianday(due_date) < julianday('now')))
+             )",
+            params![&offset],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(StatsSummary {
+        total,
+        completed,
+        completion_rate: ratio(completed, total),
+        current_streak,
+        longest_streak,
+        average_lead_hours,
+        overdue,
+        overdue_rate: ratio(overdue, due_count),
+        by_tag: distribution(
+            conn,
+            "SELECT g.id, g.name, COUNT(*), SUM(t.status = 'completed')
+             FROM tags g
+             JOIN task_tags tt ON tt.tag_id = g.id
+             JOIN tasks t ON t.id = tt.task_id
+             GROUP BY g.id
+             ORDER BY COUNT(*) DESC, g.name",
+        )?,
+        by_project: distribution(
+            conn,
+            "SELECT t.project_id, COALESCE(p.name, '无项目'), COUNT(*), SUM(t.status = 'completed')
+             FROM tasks t LEFT JOIN projects p ON p.id = t.project_id
+             GROUP BY t.project_id
+             ORDER BY COUNT(*) DESC",
+        )?,
+        by_priority: distribution(
+            conn,
+            "SELECT priority,
+                    CASE priority WHEN 'high' THEN '高' WHEN 'low' THEN '低' ELSE '中' END,
+                    COUNT(*), SUM(status = 'completed')
+             FROM tasks
+             GROUP BY priority
+             ORDER BY CASE priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END",
+        )?,
+    })
+}
+
+fn distribution(conn: &Connection, sql: &str) -> Result<Vec<Distribution>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row: &Row| {
+            Ok(Distribution {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                total: row.get(2)?,
+                completed: row.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+fn ratio(part: i64, whole: i64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{connection, project_repo, task_repo};
+    use crate::models::task::Due;
+    use chrono::{Datelike, Duration, NaiveDate, NaiveTime, TimeZone};
+    use chrono_tz::Tz;
+
+    const SHANGHAI: Tz = chrono_tz::Asia::Shanghai;
+
+    fn today() -> NaiveDate {
+        Utc::now().with_timezone(&SHANGHAI).date_naive()
+    }
+
+    /// 上海时区下 days_ago 天前 hour 点完成的任务（创建于完成前两小时）
+    fn completed(conn: &Connection, id: &str, days_ago: i64, hour: u32, due: Option<NaiveDate>) {
+        let due = due.map(|date| Due::AllDay { date });
+        task_repo::create(conn, id, id, "high", None, due.as_ref(), None, None, "none", &[]).unwrap();
+        let local = (today() - Duration::days(days_ago)).and_time(NaiveTime::from_hms_opt(hour, 0, 0).unwrap());
+        let done = SHANGHAI.from_local_datetime(&local).unwrap().with_timezone(&Utc);
+        let format = |t: chrono::DateTime<Utc>| t.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        task_repo::set_completion(conn, id, Some(&format(done))).unwrap();
+        conn.execute(
+            "UPDATE tasks SET created_at = ?1 WHERE id = ?2",
+            params![format(done - Duration::hours(2)), id],
+        )
+        .unwrap();
+    }
+
+    fn memory() -> Connection {
+        let conn = connection::memory();
+        settings_repo::update_setting(&conn, "timezone", "Asia/Shanghai").unwrap();
+        conn
+    }
+
+    #[test]
+    fn daily_series_buckets_by_local_date() {
+        let conn = memory();
+        // 早上 7 点在 UTC 仍是前一天，应计入当地的这一天
+        completed(&conn, "a", 0, 7, None);
+        completed(&conn, "b", 0, 20, None);
+        completed(&conn, "c", 2, 7, None);
+        completed(&conn, "old", 30, 12, None);
+        task_repo::create(&conn, "open", "open", "low", None, None, None, None, "none", &[]).unwrap();
+
+        let series = completion_series(&conn, StatsPeriod::Day, 3).unwrap();
+        let points: Vec<(String, i64)> = series.into_iter().map(|p| (p.start, p.completed)).collect();
+        let day = |n: i64| (today() - Duration::days(n)).format("%Y-%m-%d").to_string();
+        assert_eq!(points, vec![(day(2), 1), (day(1), 0), (day(0), 2)]);
+    }
+
+    #[test]
+    fn weekly_and_monthly_series_start_on_period_boundaries() {
+        let conn = memory();
+        completed(&conn, "a", 0, 12, None);
+        completed(&conn, "b", 7, 12, None);
+
+        let monday = today() - Duration::days(today().weekday().num_days_from_monday() as i64);
+        let weeks = completion_series(&conn, StatsPeriod::Week, 2).unwrap();
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[1].start, monday.format("%Y-%m-%d").to_string());
+        assert_eq!(weeks[0].start, (monday - Duration::days(7)).format("%Y-%m-%d").to_string());
+        assert_eq!((weeks[0].completed, weeks[1].completed), (1, 1));
+
+        let months = completion_series(&conn, StatsPeriod::Month, 1).unwrap();
+        assert_eq!(months[0].start, today().with_day(1).unwrap().format("%Y-%m-%d").to_string());
+        // 请求的周期数至少为 1
+        assert_eq!(completion_series(&conn, StatsPeriod::Day, 0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn streaks() {
+        let conn = memory();
+        for (id, days_ago) in [("a", 9), ("b", 8), ("c", 7), ("d", 1), ("e", 1)] {
+            completed(&conn, id, days_ago, 9, None);
+        }
+        let summary = summary(&conn).unwrap();
+        assert_eq!(summary.longest_streak, 3);
+        // 今天还没完成任务，截至昨天连续 1 天
+        assert_eq!(summary.current_streak, 1);
+
+        completed(&conn, "f", 0, 9, None);
+        assert_eq!(super::summary(&conn).unwrap().current_streak, 2);
+        completed(&conn, "g", 20, 9, None);
+        let summary = super::summary(&conn).unwrap();
+        assert_eq!((summary.longest_streak, summary.current_streak), (3, 2));
+    }
+
+    #[test]
+    fn summary_rates_and_distributions() {
+        let conn = memory();
+        assert_eq!(summary(&conn).unwrap().completion_rate, 0.0);
+
+        let yesterday = today() - Duration::days(1);
+        let tomorrow = today() + Duration::days(1);
+        project_repo::create(&conn, "p1", "工作", "#3b82f6", "briefcase").unwrap();
+        completed(&conn, "late", 0, 9, Some(yesterday));
+        completed(&conn, "early", 0, 9, Some(tomorrow));
+        let overdue = Due::AllDay { date: yesterday };
+        task_repo::create(&conn, "overdue", "overdue", "low", Some("p1"), Some(&overdue), None, None, "none", &[])
+            .unwrap();
+        let pending = Due::AllDay { date: tomorrow };
+        task_repo::create(&conn, "pending", "pending", "medium", None, Some(&pending), None, None, "none", &[])
+            .unwrap();
+
+        let summary = summary(&conn).unwrap();
+        assert_eq!((summary.total, summary.completed), (4, 2));
+        assert_eq!(summary.completion_rate, 0.5);
+        assert_eq!(summary.average_lead_hours.map(|h| h.round()), Some(2.0));
+        assert_eq!(summary.overdue, 2);
+        assert!((summary.overdue_rate - 2.0 / 3.0).abs() < 1e-9);
+
+        let priorities: Vec<(&str, i64, i64)> = summary
+            .by_priority
+            .iter()
+            .map(|d| (d.name.as_str(), d.total, d.completed))
+            .collect();
+        assert_eq!(priorities, vec![("高", 2, 2), ("中", 1, 0), ("低", 1, 0)]);
+        assert_eq!(summary.by_project[0].name, "无项目");
+        assert_eq!(summary.by_project[0].total, 3);
+        assert_eq!(summary.by_project[1].id.as_deref(), Some("p1"));
+        assert!(summary.by_tag.is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::db::connection::transaction;
+use crate::db::{settings_repo, task_repo, time_entry_repo};
+use crate::models::task::{Due, RepeatType, Task, TaskStatus};
+use crate::services::repeat_service;
+use chrono::Utc;
+use chrono_tz::Tz;
+use rusqlite::Connection;
+
+/// 根据视图类型过滤任务
+pub fn get_filtered_tasks(
+    conn: &Connection,
+    view_type: &str,
+    view_id: &str,
+    search: &str,
+) -> Result<Vec<Task>, String> {
+    let all_tasks = task_repo::get_all(conn)?;
+
+    let mut result = all_tasks;
+
+    // 按搜索关键词过滤
+    if !search.is_empty() {
+        let query = search.to_lowercase();
+        result.retain(|t| {
+            t.title.to_lowercase().contains(&query) || t.description.to_lowercase().contains(&query)
+        });
+    }
+
+    // 按视图类型过滤
+    match view_type {
+        "smart" => {
+            // “今天”“本周”按用户时区下的日期计算
+            let tz = settings_repo::timezone(conn);
+            let today = today(tz);
+            // 开始日期未到的任务默认只出现在“已计划”中
+            if view_id != "scheduled" && view_id != "completed" && !settings_repo::show_deferred(conn) {
+                result.retain(|t| !t.is_deferred(today));
+            }
+            match view_id {
+                "today" => {
+                    result.retain(|t| t.due.as_ref().is_some_and(|d| d.date_in(tz) == today));
+                }
+                "week" => {
+                    let week_end = today + chrono::Duration::days(7);
+                    result.retain(|t| t.due.as_ref().is_some_and(|d| d.date_in(tz) <= week_end));
+                }
+                "all" => {}
+                "completed" => {
+                    result.retain(|t| t.status == TaskStatus::Completed);
+                }
+                "favorite" => {
+                    result.retain(|t| t.favorite);
+                }
+                "scheduled" => {
+                    result.retain(|t| t.status != TaskStatus::Completed && t.is_deferred(today));
+                    result.sort_by_key(|t| t.start_date);
+                }
+                _ => {}
+            }
+        }
+        "project" => {
+            result.retain(|t| t.project_id.as_deref() == Some(view_id));
+        }
+        "tag" => {
+            result.retain(|t| t.tags.contains(&view_id.to_string()));
+        }
+        _ => {}
+    }
+
+    Ok(result)
+}
+
+/// 切换任务完成状态，刚完成的重复任务会生成下一个（一并返回）
+pub fn toggle_status(conn: &Connection, id: &str) -> Result<(Task, Option<Task>), String> {
+    transaction(conn, || {
+        let mut task = task_repo::toggle_status(conn, id)?;
+
+        // 完成任务时结束它正在进行的计时
+        if task.status == TaskStatus::Completed
+            && time_entry_repo::running(conn)?.is_some_and(|entry| entry.task_id == task.id)
+        {
+            time_entry_repo::stop(conn)?;
+            task = task_repo::get_by_id(conn, id)?;
+        }
+
+        let mut next = None;
+        if task.status == TaskStatus::Completed && task.repeat != RepeatType::None {
+            // 下一个任务生成失败（如截止日期无法解析）不影响完成本身
+            next = repeat_service::create_next_repeat_task(conn, &task).ok().flatten();
+        }
+
+        Ok((task, next))
+    })
+}
+
+/// 用户时区下的今天
+pub fn today(tz: Tz) -> chrono::NaiveDate {
+    Utc::now().with_timezone(&tz).date_naive()
+}
+
+/// 解析用户输入的开始日期（today/明天/YYYY-MM-DD），只取日期
+pub fn normalize_start(input: &str, tz: Tz) -> Result<chrono::NaiveDate, String> {
+    match normalize_due(input, tz)? {
+        Due::AllDay { date } => Ok(date),
+        Due::At { .. } => Err(format!("开始日期只能是日期: {}", input)),
+    }
+}
+
+/// 解析用户输入的截止日期（today/明天/YYYY-MM-DD [HH:MM]），钟点按用户时区 tz 解释
+pub fn normalize_due(input: &str, tz: Tz) -> Result<Due, String> {
+    let today = today(tz);
+    let date = match input.trim() {
+        "today" | "今天" => Some(today),
+        "tomorrow" | "明天" => Some(today + chrono::Duration::days(1)),
+        "后天" => Some(today + chrono::Duration::days(2)),
+        _ => None,
+    };
+    match date {
+        Some(date) => Ok(Due::AllDay { date }),
+        None => Due::parse(input, tz),
+    }
+}
@@ -0,0 +1,572 @@
+use crate::db::connection::transaction;
+use crate::db::{project_repo, settings_repo, tag_repo, task_repo};
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::{to_utc_timestamp, Due, Priority, RepeatType, Task, TaskStatus};
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// todo.txt 中解析出的一行任务
+#[derive(Debug, Clone, Default)]
+pub struct TodoTxtItem {
+    pub id: Option<String>,
+    pub title: String,
+    pub completed: bool,
+    pub completion_date: Option<String>,
+    pub creation_date: Option<String>,
+    pub priority: Option<Priority>,
+    pub projects: Vec<String>,
+    pub contexts: Vec<String>,
+    pub due: Option<String>,
+    /// 开始日期（todo.txt 惯用的 t: 阈值日期）
+    pub threshold: Option<String>,
+    pub rec: Option<String>,
+}
+
+/// 解析单行 todo.txt，空行返回 None
+pub fn parse_line(line: &str) -> Option<TodoTxtItem> {
+    let mut tokens = line.split_whitespace().peekable();
+    tokens.peek()?;
+
+    let mut item = TodoTxtItem::default();
+
+    // 完成标记：x 完成日期 [创建日期]
+    if tokens.peek() == Some(&"x") {
+        tokens.next();
+        item.completed = true;
+        if let Some(d) = tokens.peek().filter(|t| is_date(t)) {
+            item.completion_date = Some(d.to_string());
+            tokens.next();
+        }
+    } else if let Some(p) = tokens.peek().and_then(|t| parse_priority(t)) {
+        item.priority = Some(p);
+        tokens.next();
+    }
+
+    if let Some(d) = tokens.peek().filter(|t| is_date(t)) {
+        item.creation_date = Some(d.to_string());
+        tokens.next();
+    }
+
+    let mut words = Vec::new();
+    for token in tokens {
+        if let Some(name) = token.strip_prefix('+').filter(|n| !n.is_empty()) {
+            item.projects.push(name.to_string());
+        } else if let Some(name) = token.strip_prefix('@').filter(|n| !n.is_empty()) {
+            item.contexts.push(name.to_string());
+        } else if let Some((key, value)) = split_key_value(token) {
+            match key {
+                "id" => item.id = Some(value.to_string()),
+                "due" => item.due = Some(value.to_string()),
+                "t" => item.threshold = Some(value.to_string()),
+                "rec" => item.rec = Some(value.to_string()),
+                // 完成任务会把优先级写成 pri:A
+                "pri" => item.priority = parse_priority(&format!("({})", value)),
+                _ => words.push(token),
+            }
+        } else {
+            words.push(token);
+        }
+    }
+    item.title = words.join(" ");
+
+    Some(item)
+}
+
+/// 将任务格式化为一行 todo.txt，完成与创建日期取 tz 下的日期
+pub fn format_task(task: &Task, projects: &[Project], tags: &[Tag], tz: Tz) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let priority = priority_letter(&task.priority);
+
+    if task.status == TaskStatus::Completed {
+        parts.push("x".to_string());
+        if let Some(done) = &task.completed_at {
+            parts.push(local_date(done, tz));
+        }
+    } else {
+        parts.push(format!("({})", priority));
+    }
+    parts.push(local_date(&task.created_at, tz));
+    parts.push(task.title.clone());
+
+    if let Some(project) = task
+        .project_id
+        .as_ref()
+        .and_then(|id| projects.iter().find(|p| &p.id == id))
+    {
+        parts.push(format!("+{}", to_token(&project.name)));
+    }
+    for tag in tags.iter().filter(|t| task.tags.contains(&t.id)) {
+        parts.push(format!("@{}", to_token(&tag.name)));
+    }
+    if let Some(due) = &task.due_date {
+        parts.push(format!("due:{}", due));
+    }
+    if let Some(start) = &task.start_date {
+        parts.push(format!("t:{}", start));
+    }
+    if let Some(rec) = repeat_to_rec(&task.repeat) {
+        parts.push(format!("rec:{}", rec));
+    }
+    if task.status == TaskStatus::Completed {
+        parts.push(format!("pri:{}", priority));
+    }
+    parts.push(format!("id:{}", task.id));
+
+    parts.join(" ")
+}
+
+/// 导出全部任务为 todo.txt 文本
+pub fn export(conn: &Connection) -> Result<String, String> {
+    let tasks = task_repo::get_all(conn)?;
+    let projects = project_repo::get_all(conn)?;
+    let tags = tag_repo::get_all(conn)?;
+    let tz = settings_repo::timezone(conn);
+
+    let mut content = String::new();
+    for task in &tasks {
+        content.push_str(&format_task(task, &projects, &tags, tz));
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+/// 导入 todo.txt 文本，带 id 的行更新已有任务（只改有变化的字段），其余新建。
+/// 行中没有 rec: 时保留任务原有的重复规则（自定义重复无法写成 rec:）。
+/// 返回文件中出现的全部任务ID
+pub fn import(conn: &Connection, content: &str) -> Result<Vec<String>, String> {
+    transaction(conn, || {
+        let mut projects = project_repo::get_all(conn)?;
+        let mut tags = tag_repo::get_all(conn)?;
+        let mut ids = Vec::new();
+        // 文件中的钟点按用户时区解释
+        let tz = settings_repo::timezone(conn);
+
+        for item in content.lines().filter_map(parse_line) {
+            if item.title.is_empty() {
+                continue;
+            }
+
+            let project_id = match item.projects.first() {
+                Some(name) => Some(resolve_project(conn, &mut projects, name)?),
+                None => None,
+            };
+            let mut tag_ids = Vec::new();
+            for name in &item.contexts {
+                tag_ids.push(resolve_tag(conn, &mut tags, name)?);
+            }
+
+            let priority = item.priority.clone().unwrap_or(Priority::Medium);
+            let repeat = item.rec.as_deref().map(rec_to_repeat);
+            let due = item
+                .due
+                .as_deref()
+                .filter(|d| is_due(d))
+                .and_then(|d| Due::parse(d, tz).ok());
+            let start_date = item
+                .threshold
+                .as_deref()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+
+            let existing = match &item.id {
+                Some(id) => task_repo::get_by_id(conn, id).ok(),
+                None => None,
+            };
+
+            let task = match existing {
+                Some(task) => {
+                    // 截止时间按文件中的写法（tz 下的显示形式）比较，避免仅因时区不同而改写
+                    let due_changed = task.due_date != due.as_ref().map(|d| d.display(tz));
+                    let mut old_tags = task.tags.clone();
+                    let mut new_tags = tag_ids.clone();
+                    old_tags.sort();
+                    new_tags.sort();
+                    let title = (task.title != item.title).then_some(item.title.as_str());
+                    let priority = (task.priority != priority).then_some(priority.as_str());
+                    let project_id = (task.project_id != project_id).then_some(project_id.as_deref());
+                    let due = due_changed.then_some(due.as_ref());
+                    let start_date = (task.start_date != start_date).then_some(start_date);
+                    let repeat = repeat.as_ref().filter(|r| **r != task.repeat).map(RepeatType::as_str);
+                    let tags = (old_tags != new_tags).then_some(tag_ids.as_slice());
+
+                    let unchanged = title.is_none()
+                        && priority.is_none()
+                        && project_id.is_none()
+                        && due.is_none()
+                        && start_date.is_none()
+                        && repeat.is_none()
+                        && tags.is_none();
+                    if unchanged {
+                        task
+                    } else {
+                        task_repo::update(
+                            conn,
+                            &task.id,
+                            title,
+                            None,
+                            None,
+                            priority,
+                            project_id,
+                            due,
+                            start_date,
+                            None,
+                            None,
+                            repeat,
+                            tags,
+                        )?
+                    }
+                }
+                None => {
+                    let id = item
+                        .id
+                        .clone()
+                        .unwrap_or_else(|| format!("task-{}", Uuid::new_v4()));
+                    task_repo::create(
+                        conn,
+                        &id,
+                        &item.title,
+                        priority.as_str(),
+                        project_id.as_deref(),
+                        due.as_ref(),
+                        start_date,
+                        None,
+                        repeat.unwrap_or(RepeatType::None).as_str(),
+                        &tag_ids,
+                    )?
+                }
+            };
+
+            // 同步完成状态，保留文件中的完成日期（按用户时区当天 0 点）
+            let was_completed = task.status == TaskStatus::Completed;
+            if item.completed && !was_completed {
+                let completed_at = item
+                    .completion_date
+                    .as_deref()
+                    .and_then(|d| to_utc_timestamp(d, tz))
+                    .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string());
+                task_repo::set_completion(conn, &task.id, Some(&completed_at))?;
+            } else if !item.completed && was_completed {
+                task_repo::set_completion(conn, &task.id, None)?;
+            }
+
+            ids.push(task.id);
+        }
+
+        Ok(ids)
+    })
+}
+
+/// todo.txt 文件双向同步状态
+pub struct TodoTxtSync {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+    last_written: Option<String>,
+    known_ids: HashSet<String>,
+}
+
+impl TodoTxtSync {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            last_modified: None,
+            last_written: None,
+            known_ids: HashSet::new(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 文件有外部修改时读出其内容（不访问数据库）
+    pub fn poll(&mut self) -> Result<Option<String>, String> {
+        let modified = modified_time(&self.path);
+        if modified.is_none() || modified == self.last_modified {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&self.path).map_err(|e| e.to_string())?;
+        self.last_modified = modified;
+        if self.last_written.as_deref() == Some(content.as_str()) {
+            return Ok(None);
+        }
+        Ok(Some(content))
+    }
+
+    /// 上次同步后从文件中删掉的任务ID。
+    /// 文件为空或有解析不出标题的行时（可能正写到一半）不判断删除
+    pub fn removed_ids(&self, content: &str) -> Vec<String> {
+        let items: Option<Vec<TodoTxtItem>> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| parse_line(line).filter(|item| !item.title.is_empty()))
+            .collect();
+        let Some(items) = items.filter(|items| !items.is_empty()) else {
+            return Vec::new();
+        };
+        let ids: HashSet<String> = items.into_iter().filter_map(|item| item.id).collect();
+        self.known_ids.difference(&ids).cloned().collect()
+    }
+
+    /// 把数据库导出的内容写回文件（内容没变时不写）
+    pub fn write_back(&mut self, exported: String) -> Result<(), String> {
+        if self.last_written.as_deref() != Some(exported.as_str()) {
+            std::fs::write(&self.path, &exported).map_err(|e| e.to_string())?;
+        }
+        self.known_ids = exported
+            .lines()
+            .filter_map(parse_line)
+            .filter_map(|item| item.id)
+            .collect();
+        self.last_written = Some(exported);
+        self.last_modified = modified_time(&self.path);
+        Ok(())
+    }
+}
+
+/// 按名称查找项目，不存在则创建
+fn resolve_project(conn: &Connection, projects: &mut Vec<Project>, token: &str) -> Result<String, String> {
+    if let Some(p) = projects.iter().find(|p| to_token(&p.name) == token) {
+        return Ok(p.id.clone());
+    }
+    let id = format!("proj-{}", Uuid::new_v4());
+    let project = project_repo::create(conn, &id, &from_token(token), "#228B22", "briefcase")?;
+    projects.push(project);
+    Ok(id)
+}
+
+/// 按名称查找标签，不存在则创建
+fn resolve_tag(conn: &Connection, tags: &mut Vec<Tag>, token: &str) -> Result<String, String> {
+    if let Some(t) = tags.iter().find(|t| to_token(&t.name) == token) {
+        return Ok(t.id.clone());
+    }
+    let id = format!("tag-{}", Uuid::new_v4());
+    let tag = tag_repo::create(conn, &id, &from_token(token), "#3B82F6")?;
+    tags.push(tag);
+    Ok(id)
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// 名称中的空格在 todo.txt 中以下划线表示
+fn to_token(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+fn from_token(token: &str) -> String {
+    token.replace('_', " ")
+}
+
+fn priority_letter(priority: &Priority) -> char {
+    match priority {
+        Priority::High => 'A',
+        Priority::Medium => 'B',
+        Priority::Low => 'C',
+    }
+}
+
+/// (A) 为高，(B) 为中，(C) 及以后为低
+fn parse_priority(token: &str) -> Option<Priority> {
+    let letter = token.strip_prefix('(')?.strip_suffix(')')?;
+    let mut chars = letter.chars();
+    let c = chars.next().filter(|c| c.is_ascii_uppercase())?;
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(match c {
+        'A' => Priority::High,
+        'B' => Priority::Medium,
+        _ => Priority::Low,
+    })
+}
+
+fn repeat_to_rec(repeat: &RepeatType) -> Option<&'static str> {
+    match repeat {
+        RepeatType::Daily => Some("1d"),
+        RepeatType::Weekly => Some("1w"),
+        RepeatType::Monthly => Some("1m"),
+        RepeatType::Custom | RepeatType::None => None,
+    }
+}
+
+/// rec:1d / rec:+1w / rec:m 等，无法精确表示的间隔归为自定义
+fn rec_to_repeat(rec: &str) -> RepeatType {
+    let rec = rec.trim_start_matches('+');
+    let unit = match rec.chars().last() {
+        Some(c) => c,
+        None => return RepeatType::Custom,
+    };
+    let count = &rec[..rec.len() - unit.len_utf8()];
+    if !(count.is_empty() || count == "1") {
+        return RepeatType::Custom;
+    }
+    match unit {
+        'd' => RepeatType::Daily,
+        'w' => RepeatType::Weekly,
+        'm' => RepeatType::Monthly,
+        _ => RepeatType::Custom,
+    }
+}
+
+fn split_key_value(token: &str) -> Option<(&str, &str)> {
+    let (key, value) = token.split_once(':')?;
+    if key.is_empty() || value.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    // 排除 http://... 这类链接
+    if value.starts_with("//") {
+        return None;
+    }
+    Some((key, value))
+}
+
+fn is_date(token: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(token, "%Y-%m-%d").is_ok()
+}
+
+/// due 支持 YYYY-MM-DD 与 YYYY-MM-DDTHH:mm
+fn is_due(value: &str) -> bool {
+    is_date(value) || chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").is_ok()
+}
+
+/// UTC 时间在 tz 下的日期（YYYY-MM-DD）
+fn local_date(value: &str, tz: Tz) -> String {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(dt) => dt.with_timezone(&tz).format("%Y-%m-%d").to_string(),
+        Err(_) => value.get(..10).unwrap_or(value).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection;
+    use crate::store::TaskStore;
+
+    #[test]
+    fn parse_line_fields() {
+        let item = parse_line("(A) 2026-10-01 写周报 +工作 @办公室 due:2026-10-23 t:2026-10-20 rec:+1w id:t1 http://a.b")
+            .unwrap();
+        assert_eq!(item.priority, Some(Priority::High));
+        assert_eq!(item.creation_date.as_deref(), Some("2026-10-01"));
+        assert_eq!(item.title, "写周报 http://a.b");
+        assert_eq!(item.projects, vec!["工作"]);
+        assert_eq!(item.contexts, vec!["办公室"]);
+        assert_eq!(item.due.as_deref(), Some("2026-10-23"));
+        assert_eq!(item.threshold.as_deref(), Some("2026-10-20"));
+        assert_eq!(item.id.as_deref(), Some("t1"));
+        assert_eq!(rec_to_repeat(item.rec.as_deref().unwrap()), RepeatType::Weekly);
+
+        let done = parse_line("x 2026-10-18 2026-10-01 买菜 pri:C").unwrap();
+        assert!(done.completed);
+        assert_eq!(done.completion_date.as_deref(), Some("2026-10-18"));
+        assert_eq!(done.creation_date.as_deref(), Some("2026-10-01"));
+        assert_eq!(done.priority, Some(Priority::Low));
+        assert!(parse_line("   ").is_none());
+        assert_eq!(rec_to_repeat("2d"), RepeatType::Custom);
+    }
+
+    #[test]
+    fn import_export_round_trip() {
+        let conn = connection::memory();
+        settings_repo::update_setting(&conn, "timezone", "Asia/Shanghai").unwrap();
+        let ids = import(
+            &conn,
+            "(A) 写周报 +工作_周报 @办公室 due:2026-10-23T09:30 rec:1w id:t1\nx 2026-10-18 买菜 id:t2\n",
+        )
+        .unwrap();
+        assert_eq!(ids, vec!["t1", "t2"]);
+
+        // 完成日期按用户时区当天 0 点保存
+        let done = task_repo::get_by_id(&conn, "t2").unwrap();
+        assert_eq!(done.completed_at.as_deref(), Some("2026-10-17T16:00:00Z"));
+
+        let exported = export(&conn).unwrap();
+        let lines: Vec<TodoTxtItem> = exported.lines().filter_map(parse_line).collect();
+        let report = lines.iter().find(|i| i.id.as_deref() == Some("t1")).unwrap();
+        assert_eq!(report.title, "写周报");
+        assert_eq!(report.projects, vec!["工作_周报"]);
+        assert_eq!(report.contexts, vec!["办公室"]);
+        assert_eq!(report.due.as_deref(), Some("2026-10-23T09:30"));
+        assert_eq!(report.rec.as_deref(), Some("1w"));
+        let groceries = lines.iter().find(|i| i.id.as_deref() == Some("t2")).unwrap();
+        assert!(groceries.completed);
+        assert_eq!(groceries.completion_date.as_deref(), Some("2026-10-18"));
+        assert_eq!(project_repo::get_all(&conn).unwrap()[0].name, "工作 周报");
+    }
+
+    #[test]
+    fn reimport_only_touches_changed_tasks() {
+        let conn = connection::memory();
+        let due = Due::AllDay { date: NaiveDate::from_ymd_opt(2026, 10, 23).unwrap() };
+        task_repo::create(&conn, "t1", "复盘", "high", None, Some(&due), None, None, "custom", &[]).unwrap();
+        task_repo::create(&conn, "t2", "买菜", "low", None, None, None, None, "weekly", &[]).unwrap();
+        conn.execute_batch("UPDATE tasks SET updated_at = '2026-01-01T00:00:00Z'").unwrap();
+
+        // 自定义重复不会写成 rec:，导回时保留
+        let exported = export(&conn).unwrap();
+        assert!(!exported.lines().next().unwrap().contains("rec:"));
+        import(&conn, &exported).unwrap();
+        for task in task_repo::get_all(&conn).unwrap() {
+            assert_eq!(task.updated_at, "2026-01-01T00:00:00Z", "{}", task.id);
+        }
+        assert_eq!(task_repo::get_by_id(&conn, "t1").unwrap().repeat, RepeatType::Custom);
+
+        let edited = exported.replace("复盘", "月度复盘");
+        import(&conn, &edited).unwrap();
+        let review = task_repo::get_by_id(&conn, "t1").unwrap();
+        assert_eq!(review.title, "月度复盘");
+        assert_eq!(review.repeat, RepeatType::Custom);
+        assert_ne!(review.updated_at, "2026-01-01T00:00:00Z");
+        assert_eq!(task_repo::get_by_id(&conn, "t2").unwrap().updated_at, "2026-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn removed_ids_need_a_complete_file() {
+        let mut sync = TodoTxtSync::new(PathBuf::from("todo.txt"));
+        sync.known_ids = ["t1", "t2"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(sync.removed_ids("(A) 写周报 id:t1\n"), vec!["t2"]);
+        assert!(sync.removed_ids("").is_empty());
+        assert!(sync.removed_ids("\n\n").is_empty());
+        // 只有完成标记没有标题的行视为写到一半
+        assert!(sync.removed_ids("(A) 写周报 id:t1\nx 2026-10-18\n").is_empty());
+    }
+
+    #[test]
+    fn sync_deletes_removed_lines_with_undo() {
+        let dir = std::env::temp_dir().join(format!("ganle-todotxt-{}", Uuid::new_v4()));
+        let store = TaskStore::open(&dir.join("tasks.db")).unwrap();
+        let path = dir.join("todo.txt");
+        std::fs::write(&path, "写周报 id:t1\n买菜 id:t2\n").unwrap();
+
+        let mut sync = TodoTxtSync::new(path.clone());
+        assert!(store.sync_todotxt(&mut sync).unwrap());
+        assert_eq!(store.all_tasks().unwrap().len(), 2);
+
+        // 等文件修改时间变化后再改
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        std::fs::write(&path, "写周报 id:t1\n").unwrap();
+        assert!(store.sync_todotxt(&mut sync).unwrap());
+        assert!(store.task("t2").is_err());
+
+        assert_eq!(store.undo().unwrap().as_deref(), Some("同步删除任务"));
+        assert_eq!(store.task("t2").unwrap().title, "买菜");
+        store.sync_todotxt(&mut sync).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("id:t2"));
+
+        // 清空的文件不会删除任务
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        std::fs::write(&path, "").unwrap();
+        store.sync_todotxt(&mut sync).unwrap();
+        assert_eq!(store.all_tasks().unwrap().len(), 2);
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::db::connection::transaction;
+use crate::db::{task_event_repo, task_repo, time_entry_repo};
+use crate::models::event::{ChangeEvent, Deleted};
+use crate::models::task::Task;
+use crate::models::task_event::TaskEvent;
+use crate::models::time_entry::TimeEntry;
+use rusqlite::Connection;
+use std::collections::VecDeque;
+
+/// 最多保留的撤销步数
+pub const HISTORY_LIMIT: usize = 100;
+
+/// 恢复操作：把任务写回某个快照，恢复删除的任务，或删除它
+#[derive(Debug, Clone)]
+pub enum Op {
+    Put(Box<Task>),
+    Restore(Box<RemovedTask>),
+    Remove(String),
+}
+
+/// 删除前的任务及随之级联删除的计时与变更记录
+#[derive(Debug, Clone)]
+pub struct RemovedTask {
+    pub task: Task,
+    pub time_entries: Vec<TimeEntry>,
+    pub events: Vec<TaskEvent>,
+}
+
+/// 删除任务，返回可用于撤销的快照。任务正在计时时先结束计时
+pub fn remove_task(conn: &Connection, id: &str) -> Result<RemovedTask, String> {
+    transaction(conn, || {
+        let task = task_repo::get_by_id(conn, id)?;
+        if time_entry_repo::running(conn)?.is_some_and(|e| e.task_id == id) {
+            time_entry_repo::stop(conn)?;
+        }
+        let removed = RemovedTask {
+            task,
+            time_entries: time_entry_repo::get_by_task(conn, id)?,
+            events: task_event_repo::get_by_task(conn, id)?,
+        };
+        task_repo::delete(conn, id)?;
+        Ok(removed)
+    })
+}
+
+/// 一步可撤销的操作，undo/redo 各自整体执行
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub label: String,
+    undo: Vec<Op>,
+    redo: Vec<Op>,
+}
+
+impl Entry {
+    /// before 为操作前各任务的状态，after 为操作后的状态
+    pub fn new(label: &str, before: Vec<Op>, after: Vec<Op>) -> Self {
+        Self {
+            label: label.to_string(),
+            undo: before,
+            redo: after,
+        }
+    }
+}
+
+/// 本次会话的操作记录
+#[derive(Debug)]
+pub struct Journal {
+    done: VecDeque<Entry>,
+    undone: Vec<Entry>,
+    limit: usize,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self::new(HISTORY_LIMIT)
+    }
+}
+
+impl Journal {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            done: VecDeque::new(),
+            undone: Vec::new(),
+            limit,
+        }
+    }
+
+    /// 记录新操作，超出上限时丢弃最早的记录；新操作会清空重做栈
+    pub fn record(&mut self, entry: Entry) {
+        self.undone.clear();
+        self.done.push_back(entry);
+        while self.done.len() > self.limit {
+            self.done.pop_front();
+        }
+    }
+
+    /// 清空记录（批量导入等无法撤销的操作之后调用，避免写回过期快照）
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+
+    /// 项目删除后，把记录中的任务快照同步移出该项目（与数据库的 ON DELETE SET NULL 一致），
+    /// 避免撤销时写回指向已删除项目的引用
+    pub fn forget_project(&mut self, id: &str) {
+        for task in self.snapshots_mut() {
+            if task.project_id.as_deref() == Some(id) {
+                task.project_id = None;
+            }
+        }
+    }
+
+    /// 标签删除后，从记录中的任务快照里移除该标签
+    pub fn forget_tag(&mut self, id: &str) {
+        for task in self.snapshots_mut() {
+            task.tags.retain(|tag| tag != id);
+        }
+    }
+
+    pub fn undo_label(&self) -> Option<&str> {
+        self.done.back().map(|e| e.label.as_str())
+    }
+
+    pub fn redo_label(&self) -> Option<&str> {
+        self.undone.last().map(|e| e.label.as_str())
+    }
+
+    /// 撤销、重做记录中保存的全部任务快照
+    fn snapshots_mut(&mut self) -> impl Iterator<Item = &mut Task> {
+        self.done
+            .iter_mut()
+            .chain(self.undone.iter_mut())
+            .flat_map(|entry| entry.undo.iter_mut().chain(entry.redo.iter_mut()))
+            .filter_map(|op| match op {
+                Op::Put(task) => Some(&mut **task),
+                Op::Restore(removed) => Some(&mut removed.task),
+                Op::Remove(_) => None,
+            })
+    }
+
+    /// 撤销最近一步，返回其说明和产生的变更事件
+    pub fn undo(&mut self, conn: &Connection) -> Result<Option<(String, Vec<ChangeEvent>)>, String> {
+        let Some(entry) = self.done.pop_back() else {
+            return Ok(None);
+        };
+        match apply(conn, &entry.undo) {
+            Ok(events) => {
+                let label = entry.label.clone();
+                self.undone.push(entry);
+                Ok(Some((label, events)))
+            }
+            Err(e) => {
+                self.done.push_back(entry);
+                Err(e)
+            }
+        }
+    }
+
+    /// 重做最近撤销的一步
+    pub fn redo(&mut self, conn: &Connection) -> Result<Option<(String, Vec<ChangeEvent>)>, String> {
+        let Some(entry) = self.undone.pop() else {
+            return Ok(None);
+        };
+        match apply(conn, &entry.redo) {
+            Ok(events) => {
+                let label = entry.label.clone();
+                self.done.push_back(entry);
+                Ok(Some((label, events)))
+            }
+            Err(e) => {
+                self.undone.push(entry);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// 在一个事务中执行一组恢复操作
+fn apply(conn: &Connection, ops: &[Op]) -> Result<Vec<ChangeEvent>, String> {
+    transaction(conn, || {
+        let mut events = Vec::with_capacity(ops.len());
+        for op in ops {
+            match op {
+                Op::Put(task) => {
+                    let existed = task_repo::get_by_id(conn, &task.id).is_ok();
+                    task_repo::restore(conn, task)?;
+                    let task = task_repo::get_by_id(conn, &task.id)?;
+                    events.push(if existed {
+                        ChangeEvent::TaskUpdated(task)
+                    } else {
+                        ChangeEvent::TaskCreated(task)
+                    });
+                }
+                Op::Restore(removed) => {
+                    task_repo::restore(conn, &removed.task)?;
+                    for entry in &removed.time_entries {
+                        time_entry_repo::restore(conn, entry)?;
+                    }
+                    // 写回任务时产生的“创建”记录由原有记录替换
+                    task_event_repo::restore(conn, &removed.task.id, &removed.events)?;
+                    events.push(ChangeEvent::TaskCreated(task_repo::get_by_id(conn, &removed.task.id)?));
+                }
+                Op::Remove(id) => {
+                    if task_repo::get_by_id(conn, id).is_ok() {
+                        task_repo::delete(conn, id)?;
+                        events.push(ChangeEvent::TaskDeleted(Deleted { id: id.clone() }));
+                    }
+                }
+            }
+        }
+        Ok(events)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection;
+    use crate::models::time_entry::TimeEntrySource;
+
+    fn create(conn: &Connection, id: &str) {
+        task_repo::create(conn, id, id, "medium", None, None, None, None, "none", &[]).unwrap();
+    }
+
+    fn count(conn: &Connection, table: &str, id: &str) -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {} WHERE task_id = ?1", table), [id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn undo_delete_restores_cascaded_rows() {
+        let conn = connection::memory();
+        create(&conn, "t1");
+        task_event_repo::record(&conn, "t1", "created", None, None, None).unwrap();
+        time_entry_repo::log(&conn, "t1", "2026-10-19T01:00:00Z", "2026-10-19T01:25:00Z", 1500, TimeEntrySource::Pomodoro)
+            .unwrap();
+        time_entry_repo::start(&conn, "t1").unwrap();
+
+        let removed = remove_task(&conn, "t1").unwrap();
+        assert_eq!(removed.time_entries.len(), 2);
+        assert!(removed.time_entries.iter().all(|e| e.ended_at.is_some()));
+        assert!(time_entry_repo::running(&conn).unwrap().is_none());
+        assert_eq!(count(&conn, "time_entries", "t1"), 0);
+
+        let mut journal = Journal::default();
+        journal.record(Entry::new(
+            "删除任务",
+            vec![Op::Restore(Box::new(removed))],
+            vec![Op::Remove("t1".to_string())],
+        ));
+        let (label, events) = journal.undo(&conn).unwrap().unwrap();
+        assert_eq!(label, "删除任务");
+        assert!(matches!(&events[..], [ChangeEvent::TaskCreated(t)] if t.id == "t1"));
+        assert_eq!(count(&conn, "time_entries", "t1"), 2);
+        assert_eq!(count(&conn, "task_events", "t1"), 2);
+
+        journal.redo(&conn).unwrap().unwrap();
+        assert!(task_repo::get_by_id(&conn, "t1").is_err());
+        journal.undo(&conn).unwrap().unwrap();
+        assert_eq!(count(&conn, "time_entries", "t1"), 2);
+        assert_eq!(journal.redo_label(), Some("删除任务"));
+    }
+
+    #[test]
+    fn undo_put_and_history_limit() {
+        let conn = connection::memory();
+        create(&conn, "t1");
+        let before = task_repo::get_by_id(&conn, "t1").unwrap();
+        let mut after = before.clone();
+        after.title = "改过".to_string();
+        task_repo::restore(&conn, &after).unwrap();
+
+        let mut journal = Journal::new(2);
+        for _ in 0..3 {
+            journal.record(Entry::new(
+                "修改任务",
+                vec![Op::Put(Box::new(before.clone()))],
+                vec![Op::Put(Box::new(after.clone()))],
+            ));
+        }
+        journal.undo(&conn).unwrap().unwrap();
+        assert_eq!(task_repo::get_by_id(&conn, "t1").unwrap().title, "t1");
+        journal.undo(&conn).unwrap().unwrap();
+        // 超出上限的最早一步已丢弃
+        assert!(journal.undo(&conn).unwrap().is_none());
+
+        journal.redo(&conn).unwrap().unwrap();
+        assert_eq!(task_repo::get_by_id(&conn, "t1").unwrap().title, "改过");
+        // 新操作清空重做栈
+        journal.record(Entry::new("新建任务", vec![Op::Remove("t2".to_string())], vec![]));
+        assert_eq!(journal.redo_label(), None);
+    }
+
+    #[test]
+    fn deleted_project_and_tag_are_dropped_from_snapshots() {
+        use crate::db::{project_repo, tag_repo};
+        let conn = connection::memory();
+        project_repo::create(&conn, "p1", "项目", "#228B22", "briefcase").unwrap();
+        tag_repo::create(&conn, "g1", "标签", "#3B82F6").unwrap();
+        task_repo::create(&conn, "t1", "t1", "medium", Some("p1"), None, None, None, "none", &["g1".to_string()])
+            .unwrap();
+        let before = task_repo::get_by_id(&conn, "t1").unwrap();
+        let mut journal = Journal::default();
+        journal.record(Entry::new(
+            "修改任务",
+            vec![Op::Put(Box::new(before.clone()))],
+            vec![Op::Put(Box::new(before))],
+        ));
+
+        project_repo::delete(&conn, "p1").unwrap();
+        tag_repo::delete(&conn, "g1").unwrap();
+        journal.forget_project("p1");
+        journal.forget_tag("g1");
+        // 之后出现同 ID 的项目和标签，撤销也不会把任务挂回去
+        project_repo::create(&conn, "p1", "新项目", "#228B22", "briefcase").unwrap();
+        tag_repo::create(&conn, "g1", "新标签", "#3B82F6").unwrap();
+
+        journal.undo(&conn).unwrap().unwrap();
+        let task = task_repo::get_by_id(&conn, "t1").unwrap();
+        assert_eq!(task.project_id, None);
+        assert!(task.tags.is_empty());
+        journal.redo(&conn).unwrap().unwrap();
+        assert_eq!(task_repo::get_by_id(&conn, "t1").unwrap().project_id, None);
+    }
+}
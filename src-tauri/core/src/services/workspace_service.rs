@@ -0,0 +1,148 @@
+use crate::models::workspace::{Workspace, WorkspaceList};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// 工作区列表文件名
+const REGISTRY_FILE: &str = "workspaces.json";
+/// 新建工作区的数据库文件所在子目录
+const WORKSPACE_DIR: &str = "workspaces";
+
+/// 工作区登记表：读写应用数据目录下的 workspaces.json
+#[derive(Debug)]
+pub struct WorkspaceRegistry {
+    dir: PathBuf,
+    list: WorkspaceList,
+}
+
+impl WorkspaceRegistry {
+    /// 读取登记表，文件不存在时只有默认工作区
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(REGISTRY_FILE);
+        let list: WorkspaceList = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| format!("工作区列表格式错误: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => WorkspaceList::default(),
+            Err(e) => return Err(e.to_string()),
+        };
+        if list.workspaces.is_empty() {
+            return Err("工作区列表为空".to_string());
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            list,
+        })
+    }
+
+    pub fn workspaces(&self) -> &[Workspace] {
+        &self.list.workspaces
+    }
+
+    /// 当前工作区（登记表被手动改坏时退回第一个）
+    pub fn current(&self) -> &Workspace {
+        self.find(&self.list.current).unwrap_or(&self.list.workspaces[0])
+    }
+
+    pub fn find(&self, id: &str) -> Result<&Workspace, String> {
+        self.list
+            .workspaces
+            .iter()
+            .find(|w| w.id == id)
+            .ok_or_else(|| format!("工作区不存在: {}", id))
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Result<&Workspace, String> {
+        self.list
+            .workspaces
+            .iter()
+            .find(|w| w.name == name)
+            .ok_or_else(|| format!("工作区不存在: {}", name))
+    }
+
+    /// 工作区数据库文件的完整路径
+    pub fn path_of(&self, workspace: &Workspace) -> PathBuf {
+        self.dir.join(&workspace.file)
+    }
+
+    pub fn current_path(&self) -> PathBuf {
+        self.path_of(self.current())
+    }
+
+    /// 新建工作区（数据库文件在首次打开时创建）
+    pub fn create(&mut self, name: &str) -> Result<Workspace, String> {
+        let name = self.check_name(name, None)?;
+        let id = format!("ws-{}", Uuid::new_v4());
+        let workspace = Workspace {
+            file: format!("{}/{}.db", WORKSPACE_DIR, id),
+            id,
+            name,
+            created_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        };
+        self.list.workspaces.push(workspace.clone());
+        self.save()?;
+        Ok(workspace)
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) -> Result<Workspace, String> {
+        let name = self.check_name(name, Some(id))?;
+        let workspace = self
+            .list
+            .workspaces
+            .iter_mut()
+            .find(|w| w.id == id)
+            .ok_or_else(|| format!("工作区不存在: {}", id))?;
+        workspace.name = name;
+        let workspace = workspace.clone();
+        self.save()?;
+        Ok(workspace)
+    }
+
+    pub fn set_current(&mut self, id: &str) -> Result<(), String> {
+        self.find(id)?;
+        self.list.current = id.to_string();
+        self.save()
+    }
+
+    /// 删除工作区及其数据库文件（不能删除当前工作区）
+    pub fn delete(&mut self, id: &str) -> Result<(), String> {
+        if self.current().id == id {
+            return Err("不能删除当前使用的工作区".to_string());
+        }
+        let workspace = self.find(id)?.clone();
+        self.list.workspaces.retain(|w| w.id != id);
+        self.save()?;
+
+        let path = self.path_of(&workspace);
+        for suffix in ["", "-wal", "-shm", ".pending"] {
+            let mut name = path.as_os_str().to_owned();
+            name.push(suffix);
+            let _ = std::fs::remove_file(PathBuf::from(name));
+        }
+        Ok(())
+    }
+
+    /// 名称去掉首尾空白后不能为空，也不能与其他工作区重复
+    fn check_name(&self, name: &str, except: Option<&str>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("工作区名称不能为空".to_string());
+        }
+        if self
+            .list
+            .workspaces
+            .iter()
+            .any(|w| w.name == name && Some(w.id.as_str()) != except)
+        {
+            return Err(format!("工作区“{}”已存在", name));
+        }
+        Ok(name.to_string())
+    }
+
+    fn save(&self) -> Result<(), String> {
+        std::fs::create_dir_all(self.dir.join(WORKSPACE_DIR)).map_err(|e| e.to_string())?;
+        let content = serde_json::to_string_pretty(&self.list).map_err(|e| e.to_string())?;
+        // 先写临时文件再替换，避免写到一半损坏登记表
+        let path = self.dir.join(REGISTRY_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, content).map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
+    }
+}
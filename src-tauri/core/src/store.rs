@@ -0,0 +1,814 @@
+use crate::db::connection::{self, Database};
+use crate::db::{project_repo, settings_repo, tag_repo, task_event_repo, task_repo, time_entry_repo};
+use crate::models::event::{ChangeEvent, Deleted};
+use crate::models::import::{ImportPreview, ImportSummary};
+use crate::models::maintenance::MaintenanceReport;
+use crate::models::project::Project;
+use crate::models::seed::{SeedChoice, SetupStatus};
+use crate::models::settings::AppSettings;
+use crate::models::stats::{CompletionPoint, StatsPeriod, StatsSummary};
+use crate::models::tag::Tag;
+use crate::models::task::{Due, NewTask, SubTask, Task, TaskCounts, TaskPatch, TaskStatus, UndoState};
+use crate::models::task_event::TaskEvent;
+use crate::models::pomodoro::PomodoroSettings;
+use crate::models::time_entry::{TimeEntry, TimeEntrySource, TimeTotals};
+use crate::services::undo_service::{self, Entry, Journal, Op};
+use crate::services::{
+    import_service, lock_service, maintenance_service, nlp, outline_service, pomodoro_service::FocusSession,
+    reminder_service, seed_service, stats_service, task_service,
+    todotxt_service::{self, TodoTxtSync},
+};
+use chrono_tz::Tz;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// 默认项目颜色与图标（与前端新建项目一致）
+const DEFAULT_PROJECT_COLOR: &str = "#228B22";
+const DEFAULT_PROJECT_ICON: &str = "briefcase";
+/// 默认标签颜色
+const DEFAULT_TAG_COLOR: &str = "#3B82F6";
+
+/// 变更事件监听器
+type Listener = Box<dyn Fn(&ChangeEvent) + Send + Sync>;
+
+/// 任务数据存储：桌面端、命令行与 HTTP API 共用的统一入口。
+/// 可在多个线程间共享，查询走只读连接并发执行，写入串行
+pub struct TaskStore {
+    db: Database,
+    listeners: Vec<Listener>,
+    journal: Mutex<Journal>,
+}
+
+/// 批量操作前的数据ID快照，用于之后比对出新增和删除
+struct Snapshot {
+    tasks: HashSet<String>,
+    projects: HashSet<String>,
+    tags: HashSet<String>,
+}
+
+impl TaskStore {
+    /// 打开数据库（不存在则创建）并执行迁移
+    pub fn open(db_path: &Path) -> Result<Self, String> {
+        Self::open_with_key(db_path, None)
+    }
+
+    /// 用密码打开加密的数据库；有待替换的加密/解密迁移文件时先完成替换
+    pub fn open_with_key(db_path: &Path, key: Option<&str>) -> Result<Self, String> {
+        if let Some(dir) = db_path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        connection::apply_pending(db_path)?;
+        let db = Database::new(db_path, key).map_err(|e| e.to_string())?;
+        db.run_migrations().map_err(|e| e.to_string())?;
+        Ok(Self {
+            db,
+            listeners: Vec::new(),
+            journal: Mutex::new(Journal::default()),
+        })
+    }
+
+    /// 订阅变更事件（每次写操作成功后同步回调）
+    pub fn subscribe(&mut self, listener: impl Fn(&ChangeEvent) + Send + Sync + 'static) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// 用只读连接执行查询，供尚未封装的服务直接使用
+    pub fn read<T>(&self, f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, String> {
+        self.db.read(f)
+    }
+
+    /// 独占写连接执行操作，供尚未封装的服务直接使用
+    pub fn write<T>(&self, f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, String> {
+        self.db.write(f)
+    }
+
+    // ========== 任务操作 ==========
+
+    /// 获取所有任务
+    pub fn all_tasks(&self) -> Result<Vec<Task>, String> {
+        self.read(task_repo::get_all)
+    }
+
+    /// 按视图和关键词过滤任务
+    pub fn filtered_tasks(&self, view_type: &str, view_id: &str, search: &str) -> Result<Vec<Task>, String> {
+        self.read(|conn| task_service::get_filtered_tasks(conn, view_type, view_id, search))
+    }
+
+    /// 获取单个任务
+    pub fn task(&self, id: &str) -> Result<Task, String> {
+        self.read(|conn| task_repo::get_by_id(conn, id))
+    }
+
+    /// 创建任务
+    pub fn create_task(&self, new: &NewTask) -> Result<Task, String> {
+        let id = format!("task-{}", Uuid::new_v4());
+        let task = self.write(|conn| {
+            task_repo::create(
+                conn,
+                &id,
+                &new.title,
+                &new.priority,
+                new.project_id.as_deref(),
+                new.due.as_ref(),
+                new.start_date,
+                new.estimate_minutes,
+                &new.repeat_type,
+                &new.tag_ids,
+            )
+        })?;
+        self.record("新建任务", vec![Op::Remove(id)], vec![Op::Put(Box::new(task.clone()))]);
+        self.emit(ChangeEvent::TaskCreated(task.clone()));
+        Ok(task)
+    }
+
+    /// 用一句自然语言创建任务（如“明天下午3点 开周会 #会议 !high”）
+    pub fn quick_add(&self, text: &str) -> Result<Task, String> {
+        let tz = self.timezone();
+        let parsed = nlp::parse(text, tz);
+        if parsed.title.trim().is_empty() {
+            return Err("任务标题不能为空".to_string());
+        }
+
+        let mut new = NewTask::new(parsed.title);
+        new.due = parsed.due_date.map(|d| Due::parse(&d, tz)).transpose()?;
+        if let Some(priority) = parsed.priority {
+            new.priority = priority;
+        }
+        if let Some(repeat_type) = parsed.repeat_type {
+            new.repeat_type = repeat_type;
+        }
+        if let Some(project) = parsed.project {
+            new.project_id = Some(self.find_or_create_project(&project)?.id);
+        }
+        for tag in &parsed.tags {
+            new.tag_ids.push(self.find_or_create_tag(tag)?.id);
+        }
+        self.create_task(&new)
+    }
+
+    /// 部分更新任务
+    pub fn update_task(&self, id: &str, patch: &TaskPatch) -> Result<Task, String> {
+        let (before, task) = self.write(|conn| {
+            let before = task_repo::get_by_id(conn, id)?;
+            let task = task_repo::update(
+                conn,
+                id,
+                patch.title.as_deref(),
+                patch.description.as_deref(),
+                patch.status.as_deref(),
+                patch.priority.as_deref(),
+                patch.project_id.as_ref().map(|p| p.as_deref()),
+                patch.due.as_ref().map(|d| d.as_ref()),
+                patch.start_date,
+                patch.estimate_minutes,
+                patch.reminder.as_ref().map(|r| r.as_deref()),
+                patch.repeat_type.as_deref(),
+                patch.tag_ids.as_deref(),
+            )?;
+            Ok((before, task))
+        })?;
+        self.record("修改任务", vec![Op::Put(Box::new(before))], vec![Op::Put(Box::new(task.clone()))]);
+        self.emit(ChangeEvent::TaskUpdated(task.clone()));
+        Ok(task)
+    }
+
+    /// 切换完成状态（重复任务完成时生成下一个）
+    pub fn toggle_task(&self, id: &str) -> Result<Task, String> {
+        let (before, running, (task, next)) = self.write(|conn| {
+            let before = task_repo::get_by_id(conn, id)?;
+            let running = time_entry_repo::running(conn)?;
+            Ok((before, running, task_service::toggle_status(conn, id)?))
+        })?;
+
+        // 撤销时连同生成的下一个重复任务一起撤销
+        let mut undo = vec![Op::Put(Box::new(before))];
+        let mut redo = vec![Op::Put(Box::new(task.clone()))];
+        if let Some(next) = &next {
+            undo.push(Op::Remove(next.id.clone()));
+            redo.push(Op::Put(Box::new(next.clone())));
+        }
+        let label = if task.status == TaskStatus::Completed { "完成任务" } else { "取消完成" };
+        self.record(label, undo, redo);
+
+        self.emit(ChangeEvent::TaskUpdated(task.clone()));
+        if let Some(next) = next {
+            self.emit(ChangeEvent::TaskCreated(next));
+        }
+        // 完成任务时结束了它的计时
+        if task.status == TaskStatus::Completed && running.is_some_and(|entry| entry.task_id == task.id) {
+            self.emit(ChangeEvent::TimerChanged(None));
+        }
+        Ok(task)
+    }
+
+    /// 切换收藏状态
+    pub fn toggle_favorite(&self, id: &str) -> Result<Task, String> {
+        let (before, task) = self.write(|conn| {
+            let before = task_repo::get_by_id(conn, id)?;
+            Ok((before, task_repo::toggle_favorite(conn, id)?))
+        })?;
+        self.record("收藏任务", vec![Op::Put(Box::new(before))], vec![Op::Put(Box::new(task.clone()))]);
+        self.emit(ChangeEvent::TaskUpdated(task.clone()));
+        Ok(task)
+    }
+
+    /// 删除任务。计时与变更记录随任务删除，撤销时一并恢复
+    pub fn delete_task(&self, id: &str) -> Result<(), String> {
+        let (removed, timing) = self.write(|conn| {
+            let timing = time_entry_repo::running(conn)?.is_some_and(|e| e.task_id == id);
+            Ok((undo_service::remove_task(conn, id)?, timing))
+        })?;
+        self.record("删除任务", vec![Op::Restore(Box::new(removed))], vec![Op::Remove(id.to_string())]);
+        self.emit(ChangeEvent::TaskDeleted(Deleted { id: id.to_string() }));
+        if timing {
+            self.emit(ChangeEvent::TimerChanged(None));
+        }
+        Ok(())
+    }
+
+    /// 重新排序任务
+    pub fn reorder_tasks(&self, ordered_ids: &[String]) -> Result<(), String> {
+        let (before, after) = self.write(|conn| {
+            let before = states(conn, ordered_ids)?;
+            task_repo::reorder(conn, ordered_ids)?;
+            Ok((before, states(conn, ordered_ids)?))
+        })?;
+        self.record("调整顺序", before, after);
+        self.emit_tasks_updated(ordered_ids)
+    }
+
+    /// 各智能清单的任务数
+    pub fn task_counts(&self) -> Result<TaskCounts, String> {
+        self.read(task_repo::get_counts)
+    }
+
+    /// 任务的变更记录（创建、改期、完成与重新打开等）
+    pub fn task_history(&self, id: &str) -> Result<Vec<TaskEvent>, String> {
+        self.read(|conn| task_event_repo::get_by_task(conn, id))
+    }
+
+    /// 检查即将到期需要提醒的任务
+    pub fn check_reminders(&self) -> Result<Option<Task>, String> {
+        self.read(reminder_service::check_reminders)
+    }
+
+    // ========== 子任务操作 ==========
+
+    /// 添加子任务
+    pub fn add_subtask(&self, task_id: &str, title: &str) -> Result<SubTask, String> {
+        let id = format!("sub-{}", Uuid::new_v4());
+        let subtask = self.change_task("添加子任务", task_id, |conn| {
+            task_repo::add_subtask(conn, &id, task_id, title)
+        })?;
+        self.emit_tasks_updated(&[task_id.to_string()])?;
+        Ok(subtask)
+    }
+
+    /// 切换子任务完成状态
+    pub fn toggle_subtask(&self, task_id: &str, subtask_id: &str) -> Result<(), String> {
+        self.change_task("切换子任务", task_id, |conn| {
+            task_repo::toggle_subtask(conn, task_id, subtask_id)
+        })?;
+        self.emit_tasks_updated(&[task_id.to_string()])
+    }
+
+    /// 更新子任务标题
+    pub fn update_subtask(&self, task_id: &str, subtask_id: &str, title: &str) -> Result<(), String> {
+        self.change_task("修改子任务", task_id, |conn| {
+            task_repo::update_subtask(conn, task_id, subtask_id, title)
+        })?;
+        self.emit_tasks_updated(&[task_id.to_string()])
+    }
+
+    /// 删除子任务
+    pub fn delete_subtask(&self, task_id: &str, subtask_id: &str) -> Result<(), String> {
+        self.change_task("删除子任务", task_id, |conn| {
+            task_repo::delete_subtask(conn, task_id, subtask_id)
+        })?;
+        self.emit_tasks_updated(&[task_id.to_string()])
+    }
+
+    // ========== 计时 ==========
+
+    /// 正在进行的计时（保存在数据库中，重启后继续）
+    pub fn running_timer(&self) -> Result<Option<TimeEntry>, String> {
+        self.read(time_entry_repo::running)
+    }
+
+    /// 开始为任务计时，同一时间只有一个计时，已有的会先结束
+    pub fn start_timer(&self, task_id: &str) -> Result<TimeEntry, String> {
+        let (stopped, started) = self.write(|conn| time_entry_repo::start(conn, task_id))?;
+        if let Some(stopped) = stopped {
+            self.emit_tasks_updated(&[stopped.task_id])?;
+        }
+        self.emit(ChangeEvent::TimerChanged(Some(started.clone())));
+        Ok(started)
+    }
+
+    /// 结束正在进行的计时，没有计时时返回 None
+    pub fn stop_timer(&self) -> Result<Option<TimeEntry>, String> {
+        let stopped = self.write(time_entry_repo::stop)?;
+        if let Some(stopped) = &stopped {
+            self.emit_tasks_updated(std::slice::from_ref(&stopped.task_id))?;
+            self.emit(ChangeEvent::TimerChanged(None));
+        }
+        Ok(stopped)
+    }
+
+    /// 任务的计时记录，最近的在前
+    pub fn time_entries(&self, task_id: &str) -> Result<Vec<TimeEntry>, String> {
+        self.read(|conn| time_entry_repo::get_by_task(conn, task_id))
+    }
+
+    /// 删除一条计时记录（正在进行的计时一并结束）
+    pub fn delete_time_entry(&self, id: i64) -> Result<(), String> {
+        let (entry, running) = self.write(|conn| {
+            let entry = time_entry_repo::get_by_id(conn, id)?;
+            time_entry_repo::delete(conn, id)?;
+            Ok((entry, time_entry_repo::running(conn)?))
+        })?;
+        self.emit_tasks_updated(&[entry.task_id])?;
+        if entry.ended_at.is_none() {
+            self.emit(ChangeEvent::TimerChanged(running));
+        }
+        Ok(())
+    }
+
+    /// 记录完成的番茄钟专注，未关联任务时不记录
+    pub fn log_focus_session(&self, session: &FocusSession) -> Result<Option<TimeEntry>, String> {
+        let Some(task_id) = &session.task_id else {
+            return Ok(None);
+        };
+        let format = |t: chrono::DateTime<chrono::Utc>| t.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let entry = self.write(|conn| {
+            time_entry_repo::log(
+                conn,
+                task_id,
+                &format(session.started_at),
+                &format(session.ended_at),
+                session.seconds,
+                TimeEntrySource::Pomodoro,
+            )
+        })?;
+        self.emit_tasks_updated(std::slice::from_ref(task_id))?;
+        Ok(Some(entry))
+    }
+
+    /// 按任务、项目和标签汇总的预估与实际用时
+    pub fn time_totals(&self) -> Result<TimeTotals, String> {
+        self.read(time_entry_repo::totals)
+    }
+
+    // ========== 统计 ==========
+
+    /// 最近 count 个周期（日、周或月）每个周期完成的任务数
+    pub fn completion_series(&self, period: StatsPeriod, count: u32) -> Result<Vec<CompletionPoint>, String> {
+        self.read(|conn| stats_service::completion_series(conn, period, count))
+    }
+
+    /// 完成率、连续完成天数、平均耗时、逾期率与按标签、项目、优先级的分布
+    pub fn stats_summary(&self) -> Result<StatsSummary, String> {
+        self.read(stats_service::summary)
+    }
+
+    // ========== 项目操作 ==========
+
+    /// 获取所有项目
+    pub fn projects(&self) -> Result<Vec<Project>, String> {
+        self.read(project_repo::get_all)
+    }
+
+    /// 获取单个项目
+    pub fn project(&self, id: &str) -> Result<Project, String> {
+        self.read(|conn| project_repo::get_by_id(conn, id))
+    }
+
+    /// 创建项目
+    pub fn create_project(&self, name: &str, color: &str, icon: &str) -> Result<Project, String> {
+        let id = format!("proj-{}", Uuid::new_v4());
+        let project = self.write(|conn| project_repo::create(conn, &id, name, color, icon))?;
+        self.emit(ChangeEvent::ProjectCreated(project.clone()));
+        Ok(project)
+    }
+
+    /// 按名称查找项目，不存在时以默认样式创建
+    pub fn find_or_create_project(&self, name: &str) -> Result<Project, String> {
+        if let Some(project) = self.projects()?.into_iter().find(|p| p.name == name) {
+            return Ok(project);
+        }
+        self.create_project(name, DEFAULT_PROJECT_COLOR, DEFAULT_PROJECT_ICON)
+    }
+
+    /// 更新项目
+    pub fn update_project(
+        &self,
+        id: &str,
+        name: Option<&str>,
+        color: Option<&str>,
+        icon: Option<&str>,
+        archived: Option<bool>,
+    ) -> Result<Project, String> {
+        let project = self.write(|conn| project_repo::update(conn, id, name, color, icon, archived))?;
+        self.emit(ChangeEvent::ProjectUpdated(project.clone()));
+        Ok(project)
+    }
+
+    /// 删除项目（其下任务移出项目）
+    pub fn delete_project(&self, id: &str) -> Result<(), String> {
+        let affected: Vec<String> = self
+            .all_tasks()?
+            .into_iter()
+            .filter(|t| t.project_id.as_deref() == Some(id))
+            .map(|t| t.id)
+            .collect();
+        self.write(|conn| project_repo::delete(conn, id))?;
+        self.journal.lock().unwrap_or_else(|e| e.into_inner()).forget_project(id);
+        self.emit(ChangeEvent::ProjectDeleted(Deleted { id: id.to_string() }));
+        self.emit_tasks_updated(&affected)
+    }
+
+    /// 项目下未完成任务数
+    pub fn project_task_count(&self, id: &str) -> Result<usize, String> {
+        self.read(|conn| project_repo::get_task_count(conn, id))
+    }
+
+    // ========== 标签操作 ==========
+
+    /// 获取所有标签
+    pub fn tags(&self) -> Result<Vec<Tag>, String> {
+        self.read(tag_repo::get_all)
+    }
+
+    /// 获取单个标签
+    pub fn tag(&self, id: &str) -> Result<Tag, String> {
+        self.read(|conn| tag_repo::get_by_id(conn, id))
+    }
+
+    /// 创建标签
+    pub fn create_tag(&self, name: &str, color: &str) -> Result<Tag, String> {
+        let id = format!("tag-{}", Uuid::new_v4());
+        let tag = self.write(|conn| tag_repo::create(conn, &id, name, color))?;
+        self.emit(ChangeEvent::TagCreated(tag.clone()));
+        Ok(tag)
+    }
+
+    /// 按名称查找标签，不存在时以默认颜色创建
+    pub fn find_or_create_tag(&self, name: &str) -> Result<Tag, String> {
+        if let Some(tag) = self.tags()?.into_iter().find(|t| t.name == name) {
+            return Ok(tag);
+        }
+        self.create_tag(name, DEFAULT_TAG_COLOR)
+    }
+
+    /// 更新标签
+    pub fn update_tag(&self, id: &str, name: Option<&str>, color: Option<&str>) -> Result<Tag, String> {
+        let tag = self.write(|conn| tag_repo::update(conn, id, name, color))?;
+        self.emit(ChangeEvent::TagUpdated(tag.clone()));
+        Ok(tag)
+    }
+
+    /// 删除标签（同时解除与任务的关联）
+    pub fn delete_tag(&self, id: &str) -> Result<(), String> {
+        let affected: Vec<String> = self
+            .all_tasks()?
+            .into_iter()
+            .filter(|t| t.tags.iter().any(|tag| tag == id))
+            .map(|t| t.id)
+            .collect();
+        self.write(|conn| tag_repo::delete(conn, id))?;
+        self.journal.lock().unwrap_or_else(|e| e.into_inner()).forget_tag(id);
+        self.emit(ChangeEvent::TagDeleted(Deleted { id: id.to_string() }));
+        self.emit_tasks_updated(&affected)
+    }
+
+    /// 标签下未完成任务数
+    pub fn tag_task_count(&self, id: &str) -> Result<usize, String> {
+        self.read(|conn| tag_repo::get_task_count(conn, id))
+    }
+
+    // ========== 设置 ==========
+
+    /// 获取应用设置
+    pub fn settings(&self) -> Result<AppSettings, String> {
+        self.read(settings_repo::get_settings)
+    }
+
+    /// 读取单个设置项（未设置或为空时返回 None）
+    pub fn setting(&self, key: &str) -> Option<String> {
+        self.read(|conn| Ok(settings_repo::get_value(conn, key))).ok().flatten()
+    }
+
+    /// 番茄钟时长设置
+    pub fn pomodoro_settings(&self) -> PomodoroSettings {
+        self.read(|conn| Ok(settings_repo::pomodoro_settings(conn)))
+            .unwrap_or_default()
+    }
+
+    /// 用户时区，未设置时跟随系统
+    pub fn timezone(&self) -> Tz {
+        self.read(|conn| Ok(settings_repo::timezone(conn)))
+            .unwrap_or_else(|_| settings_repo::system_timezone())
+    }
+
+    /// 写入单个设置项
+    pub fn update_setting(&self, key: &str, value: &str) -> Result<(), String> {
+        // 时区为空表示跟随系统
+        if key == "timezone" && !value.is_empty() && value.parse::<Tz>().is_err() {
+            return Err(format!("无法识别的时区: {}", value));
+        }
+        // 番茄钟时长为正整数（分钟或轮数），为空表示恢复默认
+        if key.starts_with("pomodoro_") && !value.is_empty() && !value.parse::<u32>().is_ok_and(|v| v > 0) {
+            return Err(format!("{} 须为正整数: {}", key, value));
+        }
+        self.write(|conn| settings_repo::update_setting(conn, key, value))
+    }
+
+    /// 设置应用锁密码，None 表示取消应用锁
+    pub fn set_app_lock(&self, pin: Option<&str>) -> Result<(), String> {
+        let hash = pin.map(lock_service::hash_pin).transpose()?.unwrap_or_default();
+        self.update_setting("app_lock_hash", &hash)
+    }
+
+    /// 校验应用锁密码（未设置应用锁时总是通过）
+    pub fn verify_app_lock(&self, pin: &str) -> bool {
+        self.setting("app_lock_hash")
+            .is_none_or(|stored| lock_service::verify_pin(&stored, pin))
+    }
+
+    // ========== 导入导出 ==========
+
+    /// 写入导入预览中的内容
+    pub fn commit_import(&self, preview: &ImportPreview) -> Result<ImportSummary, String> {
+        let before = self.snapshot()?;
+        let summary = self.write(|conn| import_service::commit(conn, preview))?;
+        self.clear_journal();
+        self.emit_diff(&before, &[])?;
+        Ok(summary)
+    }
+
+    /// 导出为 org-mode 文本
+    pub fn export_org(&self) -> Result<String, String> {
+        self.read(outline_service::export_org)
+    }
+
+    /// 导出为 OPML 文本
+    pub fn export_opml(&self) -> Result<String, String> {
+        self.read(outline_service::export_opml)
+    }
+
+    /// 导出为 todo.txt 文本
+    pub fn export_todotxt(&self) -> Result<String, String> {
+        self.read(todotxt_service::export)
+    }
+
+    /// 导入 todo.txt 文本，返回涉及的任务ID
+    pub fn import_todotxt(&self, content: &str) -> Result<Vec<String>, String> {
+        let before = self.snapshot()?;
+        let ids = self.write(|conn| todotxt_service::import(conn, content))?;
+        self.clear_journal();
+        self.emit_diff(&before, &ids)?;
+        Ok(ids)
+    }
+
+    /// 执行一轮 todo.txt 同步：文件有外部修改时先导入，文件中删掉的行同步删除任务（可撤销），
+    /// 再把最新内容写回文件。读写文件时不占用数据库连接。返回数据库是否被修改
+    pub fn sync_todotxt(&self, sync: &mut TodoTxtSync) -> Result<bool, String> {
+        let changed = match sync.poll()? {
+            Some(content) => {
+                let before = self.snapshot()?;
+                let removed_ids = sync.removed_ids(&content);
+                let (ids, removed) = self.write(|conn| {
+                    connection::transaction(conn, || {
+                        let ids = todotxt_service::import(conn, &content)?;
+                        let removed = removed_ids
+                            .iter()
+                            .filter(|id| task_repo::get_by_id(conn, id).is_ok())
+                            .map(|id| undo_service::remove_task(conn, id))
+                            .collect::<Result<Vec<_>, String>>()?;
+                        Ok((ids, removed))
+                    })
+                })?;
+                // 导入无法撤销，只有删除记入撤销记录
+                self.clear_journal();
+                if !removed.is_empty() {
+                    let redo = removed.iter().map(|r| Op::Remove(r.task.id.clone())).collect();
+                    let undo = removed.into_iter().map(|r| Op::Restore(Box::new(r))).collect();
+                    self.record("同步删除任务", undo, redo);
+                }
+                self.emit_diff(&before, &ids)?;
+                true
+            }
+            None => false,
+        };
+        sync.write_back(self.export_todotxt()?)?;
+        Ok(changed)
+    }
+
+    // ========== 初始数据 ==========
+
+    /// 是否需要首次启动选择，以及是否还有示例数据
+    pub fn setup_status(&self) -> Result<SetupStatus, String> {
+        self.read(seed_service::setup_status)
+    }
+
+    /// 写入所选的初始数据（空白、示例或模板包）
+    pub fn seed(&self, choice: &SeedChoice) -> Result<ImportSummary, String> {
+        let before = self.snapshot()?;
+        let summary = self.write(|conn| seed_service::seed(conn, choice))?;
+        self.emit_diff(&before, &[])?;
+        Ok(summary)
+    }
+
+    /// 删除全部示例数据，返回删除的任务数
+    pub fn remove_sample_data(&self) -> Result<usize, String> {
+        let before = self.snapshot()?;
+        let tasks = self.all_tasks()?;
+        let removed = self.write(seed_service::remove_sample_data)?;
+        // 删除的项目、标签可能被用户自己的任务引用，这些任务随之更新
+        let touched: Vec<String> = self
+            .all_tasks()?
+            .into_iter()
+            .filter(|t| {
+                tasks
+                    .iter()
+                    .find(|b| b.id == t.id)
+                    .is_some_and(|b| b.project_id != t.project_id || b.tags != t.tags)
+            })
+            .map(|t| t.id)
+            .collect();
+        self.clear_journal();
+        self.emit_diff(&before, &touched)?;
+        Ok(removed.len())
+    }
+
+    // ========== 数据库维护 ==========
+
+    /// 检查并修复数据库，完好时再整理文件
+    pub fn maintain(&self) -> Result<MaintenanceReport, String> {
+        let before = self.all_tasks()?;
+        let report = self.write(maintenance_service::run)?;
+        if report.repaired.total() > 0 {
+            // 快照中可能还引用着已清理的数据
+            self.clear_journal();
+            let changed: Vec<String> = self
+                .all_tasks()?
+                .into_iter()
+                .filter(|t| {
+                    before
+                        .iter()
+                        .find(|b| b.id == t.id)
+                        .is_some_and(|b| b.project_id != t.project_id || b.tags != t.tags)
+                })
+                .map(|t| t.id)
+                .collect();
+            self.emit_tasks_updated(&changed)?;
+        }
+        Ok(report)
+    }
+
+    /// 距上次维护是否已超过间隔
+    pub fn maintenance_due(&self) -> bool {
+        self.read(|conn| Ok(maintenance_service::is_due(conn))).unwrap_or(false)
+    }
+
+    // ========== 撤销与重做 ==========
+
+    /// 撤销最近一步任务操作，返回其说明（没有可撤销的操作时为 None）
+    pub fn undo(&self) -> Result<Option<String>, String> {
+        let result = self.write(|conn| self.journal.lock().map_err(|e| e.to_string())?.undo(conn))?;
+        Ok(result.map(|(label, events)| {
+            events.into_iter().for_each(|e| self.emit(e));
+            label
+        }))
+    }
+
+    /// 重做最近撤销的一步
+    pub fn redo(&self) -> Result<Option<String>, String> {
+        let result = self.write(|conn| self.journal.lock().map_err(|e| e.to_string())?.redo(conn))?;
+        Ok(result.map(|(label, events)| {
+            events.into_iter().for_each(|e| self.emit(e));
+            label
+        }))
+    }
+
+    /// 下一步可撤销、可重做的操作说明
+    pub fn history(&self) -> UndoState {
+        let journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+        UndoState {
+            undo: journal.undo_label().map(str::to_string),
+            redo: journal.redo_label().map(str::to_string),
+        }
+    }
+
+    fn record(&self, label: &str, before: Vec<Op>, after: Vec<Op>) {
+        let mut journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+        journal.record(Entry::new(label, before, after));
+    }
+
+    fn clear_journal(&self) {
+        self.journal.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// 在写连接上修改单个任务，并记录其修改前后的状态
+    fn change_task<T>(
+        &self,
+        label: &str,
+        task_id: &str,
+        f: impl FnOnce(&Connection) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let (value, before, after) = self.write(|conn| {
+            let before = task_repo::get_by_id(conn, task_id)?;
+            let value = f(conn)?;
+            Ok((value, before, task_repo::get_by_id(conn, task_id)?))
+        })?;
+        self.record(label, vec![Op::Put(Box::new(before))], vec![Op::Put(Box::new(after))]);
+        Ok(value)
+    }
+
+    // ========== 变更事件 ==========
+
+    fn emit(&self, event: ChangeEvent) {
+        for listener in &self.listeners {
+            listener(&event);
+        }
+    }
+
+    /// 重新读取并发出指定任务的更新事件
+    fn emit_tasks_updated(&self, ids: &[String]) -> Result<(), String> {
+        if self.listeners.is_empty() || ids.is_empty() {
+            return Ok(());
+        }
+        for task in self.all_tasks()? {
+            if ids.contains(&task.id) {
+                self.emit(ChangeEvent::TaskUpdated(task));
+            }
+        }
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Result<Snapshot, String> {
+        Ok(Snapshot {
+            tasks: self.all_tasks()?.into_iter().map(|t| t.id).collect(),
+            projects: self.projects()?.into_iter().map(|p| p.id).collect(),
+            tags: self.tags()?.into_iter().map(|t| t.id).collect(),
+        })
+    }
+
+    /// 与批量操作前的快照比对，发出新增、删除事件；touched 中已存在的任务发出更新事件
+    fn emit_diff(&self, before: &Snapshot, touched: &[String]) -> Result<(), String> {
+        if self.listeners.is_empty() {
+            return Ok(());
+        }
+
+        let projects = self.projects()?;
+        for project in &projects {
+            if !before.projects.contains(&project.id) {
+                self.emit(ChangeEvent::ProjectCreated(project.clone()));
+            }
+        }
+        let tags = self.tags()?;
+        for tag in &tags {
+            if !before.tags.contains(&tag.id) {
+                self.emit(ChangeEvent::TagCreated(tag.clone()));
+            }
+        }
+
+        let tasks = self.all_tasks()?;
+        for task in &tasks {
+            if !before.tasks.contains(&task.id) {
+                self.emit(ChangeEvent::TaskCreated(task.clone()));
+            } else if touched.contains(&task.id) {
+                self.emit(ChangeEvent::TaskUpdated(task.clone()));
+            }
+        }
+
+        let remaining: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        for id in before.tasks.iter().filter(|id| !remaining.contains(id.as_str())) {
+            self.emit(ChangeEvent::TaskDeleted(Deleted { id: id.clone() }));
+        }
+        let remaining: HashSet<&str> = projects.iter().map(|p| p.id.as_str()).collect();
+        for id in before.projects.iter().filter(|id| !remaining.contains(id.as_str())) {
+            self.emit(ChangeEvent::ProjectDeleted(Deleted { id: id.clone() }));
+        }
+        let remaining: HashSet<&str> = tags.iter().map(|t| t.id.as_str()).collect();
+        for id in before.tags.iter().filter(|id| !remaining.contains(id.as_str())) {
+            self.emit(ChangeEvent::TagDeleted(Deleted { id: id.clone() }));
+        }
+        Ok(())
+    }
+}
+
+/// 各任务的当前状态，已不存在的记为删除
+fn states(conn: &Connection, ids: &[String]) -> Result<Vec<Op>, String> {
+    let tasks = task_repo::get_all(conn)?;
+    Ok(ids
+        .iter()
+        .map(|id| match tasks.iter().find(|t| &t.id == id) {
+            Some(task) => Op::Put(Box::new(task.clone())),
+            None => Op::Remove(id.clone()),
+        })
+        .collect())
+}
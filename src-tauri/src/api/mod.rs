@@ -0,0 +1,63 @@
+mod routes;
+
+use crate::AppState;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tiny_http::Server;
+
+/// 本地 HTTP API 服务（仅监听 127.0.0.1）
+pub struct ApiServer {
+    server: Arc<Server>,
+    port: u16,
+    worker: JoinHandle<()>,
+}
+
+impl ApiServer {
+    /// 在后台线程启动服务
+    pub fn start(app: AppHandle, port: u16) -> Result<Self, String> {
+        let server = Server::http(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+        let server = Arc::new(server);
+
+        let incoming = server.clone();
+        let worker = std::thread::spawn(move || {
+            for mut request in incoming.incoming_requests() {
+                let response = routes::handle(&app, &mut request);
+                let _ = request.respond(response);
+            }
+        });
+
+        Ok(Self { server, port, worker })
+    }
+
+    /// 停止服务，等后台线程处理完当前请求并释放端口后返回
+    pub fn stop(self) {
+        self.server.unblock();
+        let _ = self.worker.join();
+    }
+}
+
+/// API 服务运行状态
+#[derive(Default)]
+pub struct ApiState {
+    pub server: Mutex<Option<ApiServer>>,
+}
+
+/// 按当前设置启动或停止 API 服务
+pub fn apply_settings(app: &AppHandle) -> Result<(), String> {
+    let settings = app.state::<AppState>().store().settings()?;
+
+    let api = app.state::<ApiState>();
+    let mut server = api.server.lock().map_err(|e| e.to_string())?;
+    // 已在同一端口运行时保持不变（令牌每次请求时读取）
+    if settings.api_enabled && server.as_ref().is_some_and(|s| s.port == settings.api_port) {
+        return Ok(());
+    }
+    if let Some(running) = server.take() {
+        running.stop();
+    }
+    if settings.api_enabled {
+        *server = Some(ApiServer::start(app.clone(), settings.api_port)?);
+    }
+    Ok(())
+}
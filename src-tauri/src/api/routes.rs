@@ -0,0 +1,310 @@
+use crate::AppState;
+use ganle_core::models::task::{Due, NewTask, TaskPatch};
+use ganle_core::services::task_service::normalize_start;
+use ganle_core::TaskStore;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use subtle::ConstantTimeEq;
+use std::io::Cursor;
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response};
+
+/// 接口错误（HTTP 状态码 + 信息）
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// 仓储层错误统一视为请求错误
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        Self::new(400, message)
+    }
+}
+
+/// 处理单个请求
+pub fn handle(app: &AppHandle, request: &mut Request) -> Response<Cursor<Vec<u8>>> {
+    match dispatch(app, request) {
+        Ok(value) => json_response(200, &value),
+        Err(e) => json_response(e.status, &json!({ "error": e.message })),
+    }
+}
+
+fn dispatch(app: &AppHandle, request: &mut Request) -> Result<Value, ApiError> {
+    let state = app.state::<AppState>();
+    let store = state.store();
+
+    authorize(&store, request)?;
+    // 应用锁定期间拒绝访问数据
+    state.lock.ensure_unlocked().map_err(|e| ApiError::new(423, e))?;
+    // 不带时区的截止时间按用户时区解释
+    let tz = store.timezone();
+
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let query = parse_query(query);
+    let body = read_body(request)?;
+
+    match (&method, segments.as_slice()) {
+        // ========== 任务 ==========
+        (Method::Get, ["tasks"]) => to_json(store.filtered_tasks(
+            query.get("view_type").map(|s| s.as_str()).unwrap_or("smart"),
+            query.get("view_id").map(|s| s.as_str()).unwrap_or("all"),
+            query.get("search").map(|s| s.as_str()).unwrap_or(""),
+        )?),
+        (Method::Post, ["tasks"]) => to_json(store.create_task(&NewTask {
+            title: required_str(&body, "title")?,
+            priority: str_or(&body, "priority", "medium"),
+            project_id: body_str(&body, "projectId"),
+            due: body_str(&body, "dueDate").map(|d| Due::parse(&d, tz)).transpose()?,
+            start_date: body_str(&body, "startDate").map(|d| normalize_start(&d, tz)).transpose()?,
+            estimate_minutes: body_u32(&body, "estimateMinutes"),
+            repeat_type: str_or(&body, "repeatType", "none"),
+            tag_ids: body_str_list(&body, "tagIds").unwrap_or_default(),
+        })?),
+        (Method::Post, ["tasks", "quick"]) => to_json(store.quick_add(&required_str(&body, "text")?)?),
+        (Method::Get, ["tasks", "counts"]) => to_json(store.task_counts()?),
+        (Method::Post, ["tasks", "reorder"]) => {
+            let ids = body_str_list(&body, "orderedIds")
+                .ok_or_else(|| ApiError::new(400, "缺少 orderedIds"))?;
+            to_json(store.reorder_tasks(&ids)?)
+        }
+        (Method::Get, ["tasks", id]) => {
+            to_json(store.task(id).map_err(|_| not_found())?)
+        }
+        (Method::Patch, ["tasks", id]) => {
+            ensure_task(&store, id)?;
+            to_json(store.update_task(
+                id,
+                &TaskPatch {
+                    title: body_str(&body, "title"),
+                    description: body_str(&body, "description"),
+                    status: body_str(&body, "status"),
+                    priority: body_str(&body, "priority"),
+                    project_id: nullable_str(&body, "projectId"),
+                    due: nullable_str(&body, "dueDate")
+                        .map(|d| d.map(|d| Due::parse(&d, tz)).transpose())
+                        .transpose()?,
+                    start_date: nullable_str(&body, "startDate")
+                        .map(|d| d.map(|d| normalize_start(&d, tz)).transpose())
+                        .transpose()?,
+                    estimate_minutes: nullable_u32(&body, "estimateMinutes"),
+                    reminder: nullable_str(&body, "reminder"),
+                    repeat_type: body_str(&body, "repeatType"),
+                    tag_ids: body_str_list(&body, "tagIds"),
+                },
+            )?)
+        }
+        (Method::Delete, ["tasks", id]) => to_json(store.delete_task(id)?),
+        (Method::Post, ["tasks", id, "toggle"]) => {
+            ensure_task(&store, id)?;
+            to_json(store.toggle_task(id)?)
+        }
+        (Method::Post, ["tasks", id, "favorite"]) => {
+            ensure_task(&store, id)?;
+            to_json(store.toggle_favorite(id)?)
+        }
+
+        (Method::Get, ["tasks", id, "time-entries"]) => {
+            ensure_task(&store, id)?;
+            to_json(store.time_entries(id)?)
+        }
+
+        // ========== 计时 ==========
+        (Method::Get, ["timer"]) => to_json(store.running_timer()?),
+        (Method::Post, ["timer", "start"]) => to_json(store.start_timer(&required_str(&body, "taskId")?)?),
+        (Method::Post, ["timer", "stop"]) => to_json(store.stop_timer()?),
+        (Method::Get, ["time", "totals"]) => to_json(store.time_totals()?),
+
+        // ========== 子任务 ==========
+        (Method::Post, ["tasks", id, "subtasks"]) => {
+            ensure_task(&store, id)?;
+            to_json(store.add_subtask(id, &required_str(&body, "title")?)?)
+        }
+        (Method::Post, ["tasks", id, "subtasks", sub_id, "toggle"]) => {
+            to_json(store.toggle_subtask(id, sub_id)?)
+        }
+        (Method::Patch, ["tasks", id, "subtasks", sub_id]) => to_json(store.update_subtask(
+            id,
+            sub_id,
+            &required_str(&body, "title")?,
+        )?),
+        (Method::Delete, ["tasks", id, "subtasks", sub_id]) => {
+            to_json(store.delete_subtask(id, sub_id)?)
+        }
+
+        // ========== 项目 ==========
+        (Method::Get, ["projects"]) => to_json(store.projects()?),
+        (Method::Post, ["projects"]) => to_json(store.create_project(
+            &required_str(&body, "name")?,
+            &str_or(&body, "color", "#228B22"),
+            &str_or(&body, "icon", "briefcase"),
+        )?),
+        (Method::Patch, ["projects", id]) => {
+            store.project(id).map_err(|_| not_found())?;
+            to_json(store.update_project(
+                id,
+                body_str(&body, "name").as_deref(),
+                body_str(&body, "color").as_deref(),
+                body_str(&body, "icon").as_deref(),
+                body.get("archived").and_then(|v| v.as_bool()),
+            )?)
+        }
+        (Method::Delete, ["projects", id]) => to_json(store.delete_project(id)?),
+        (Method::Get, ["projects", id, "count"]) => to_json(store.project_task_count(id)?),
+
+        // ========== 标签 ==========
+        (Method::Get, ["tags"]) => to_json(store.tags()?),
+        (Method::Post, ["tags"]) => to_json(store.create_tag(
+            &required_str(&body, "name")?,
+            &str_or(&body, "color", "#3B82F6"),
+        )?),
+        (Method::Patch, ["tags", id]) => {
+            store.tag(id).map_err(|_| not_found())?;
+            to_json(store.update_tag(
+                id,
+                body_str(&body, "name").as_deref(),
+                body_str(&body, "color").as_deref(),
+            )?)
+        }
+        (Method::Delete, ["tags", id]) => to_json(store.delete_tag(id)?),
+        (Method::Get, ["tags", id, "count"]) => to_json(store.tag_task_count(id)?),
+
+        _ => Err(not_found()),
+    }
+}
+
+/// 校验 Authorization: Bearer <token>
+fn authorize(store: &TaskStore, request: &Request) -> Result<(), ApiError> {
+    let token = store
+        .setting("api_token")
+        .ok_or_else(|| ApiError::new(401, "未生成 API 令牌"))?;
+    let provided = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "))
+        .map(|s| s.trim());
+
+    // 逐字节恒定时间比较，避免通过响应时间逐位猜出令牌
+    if provided.is_some_and(|p| bool::from(p.as_bytes().ct_eq(token.as_bytes()))) {
+        Ok(())
+    } else {
+        Err(ApiError::new(401, "令牌无效"))
+    }
+}
+
+fn ensure_task(store: &TaskStore, id: &str) -> Result<(), ApiError> {
+    store.task(id).map(|_| ()).map_err(|_| not_found())
+}
+
+fn not_found() -> ApiError {
+    ApiError::new(404, "资源不存在")
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::new(500, e.to_string()))
+}
+
+fn json_response(status: u16, value: &Value) -> Response<Cursor<Vec<u8>>> {
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json; charset=utf-8"[..])
+        .expect("合法的响应头");
+    Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(content_type)
+}
+
+/// 读取 JSON 请求体，空请求体视为 null
+fn read_body(request: &mut Request) -> Result<Value, ApiError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::new(400, e.to_string()))?;
+    if body.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(&body).map_err(|e| ApiError::new(400, format!("请求体不是合法 JSON: {}", e)))
+}
+
+fn body_str(body: &Value, key: &str) -> Option<String> {
+    body.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn str_or(body: &Value, key: &str, default: &str) -> String {
+    body_str(body, key).unwrap_or_else(|| default.to_string())
+}
+
+fn required_str(body: &Value, key: &str) -> Result<String, ApiError> {
+    body_str(body, key)
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| ApiError::new(400, format!("缺少 {}", key)))
+}
+
+/// 区分“未传”（None）与“传 null 清空”（Some(None)）
+fn nullable_str(body: &Value, key: &str) -> Option<Option<String>> {
+    body.get(key).map(|v| v.as_str().map(|s| s.to_string()))
+}
+
+fn body_u32(body: &Value, key: &str) -> Option<u32> {
+    body.get(key).and_then(|v| v.as_u64()).and_then(|n| u32::try_from(n).ok())
+}
+
+fn nullable_u32(body: &Value, key: &str) -> Option<Option<u32>> {
+    body.get(key).map(|v| v.as_u64().and_then(|n| u32::try_from(n).ok()))
+}
+
+fn body_str_list(body: &Value, key: &str) -> Option<Vec<String>> {
+    body.get(key).and_then(|v| v.as_array()).map(|items| {
+        items
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect()
+    })
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .filter(|(k, _)| !k.is_empty())
+        .map(|(k, v)| (percent_decode(k), percent_decode(v)))
+        .collect()
+}
+
+/// URL 解码（支持 UTF-8 中文与 + 表示空格）
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
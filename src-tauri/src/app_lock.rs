@@ -0,0 +1,161 @@
+use crate::AppState;
+use ganle_core::models::settings::AppSettings;
+use ganle_core::services::lock_service;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 检查是否空闲超时的间隔
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 应用锁状态。锁定期间所有数据命令、链接与 HTTP API 都会被拒绝（见 commands::with_store），
+/// 而不只是在前端盖一层遮罩
+pub struct AppLock {
+    enabled: AtomicBool,
+    locked: AtomicBool,
+    idle_minutes: AtomicU32,
+    last_activity: Mutex<Instant>,
+    /// 解锁尝试的计数与等待期
+    attempts: Mutex<Attempts>,
+}
+
+/// 解锁尝试记录
+#[derive(Default)]
+struct Attempts {
+    /// 连续未成功的尝试次数
+    failures: u32,
+    /// 输错过多时，允许再次尝试的时间
+    retry_at: Option<Instant>,
+}
+
+impl AppLock {
+    /// 设置了应用锁时，启动后处于锁定状态
+    pub fn new(settings: &AppSettings) -> Self {
+        Self {
+            enabled: AtomicBool::new(settings.app_lock_enabled),
+            locked: AtomicBool::new(settings.app_lock_enabled),
+            idle_minutes: AtomicU32::new(settings.app_lock_minutes),
+            last_activity: Mutex::new(Instant::now()),
+            attempts: Mutex::new(Attempts::default()),
+        }
+    }
+
+    /// 未锁定时记录一次活动，锁定时返回错误
+    pub fn check(&self) -> Result<(), String> {
+        self.ensure_unlocked()?;
+        self.touch();
+        Ok(())
+    }
+
+    /// 锁定时返回错误，不记录活动（HTTP API 等后台调用不应推迟自动锁定）
+    pub fn ensure_unlocked(&self) -> Result<(), String> {
+        if self.is_locked() {
+            return Err("应用已锁定".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::SeqCst)
+    }
+
+    pub fn idle_minutes(&self) -> u32 {
+        self.idle_minutes.load(Ordering::SeqCst)
+    }
+
+    /// 记录用户活动，重新开始空闲计时
+    pub fn touch(&self) {
+        if let Ok(mut last) = self.last_activity.lock() {
+            *last = Instant::now();
+        }
+    }
+
+    /// 锁定（未设置应用锁时不生效），返回状态是否发生变化
+    pub fn lock(&self) -> bool {
+        self.is_enabled() && !self.locked.swap(true, Ordering::SeqCst)
+    }
+
+    pub fn unlock(&self) {
+        self.locked.store(false, Ordering::SeqCst);
+        if let Ok(mut attempts) = self.attempts.lock() {
+            *attempts = Attempts::default();
+        }
+        self.touch();
+    }
+
+    /// 开始一次解锁尝试：还在等待期内时返回错误，否则先按输错计数（解锁成功时清零）。
+    /// 检查与计数在同一把锁内完成，并发的尝试也会逐个计入，无法绕过等待期
+    pub fn begin_attempt(&self) -> Result<(), String> {
+        let mut attempts = self.attempts.lock().map_err(|e| e.to_string())?;
+        let now = Instant::now();
+        if let Some(wait) = attempts.retry_at.map(|at| at.saturating_duration_since(now)) {
+            if !wait.is_zero() {
+                return Err(format!("尝试次数过多，请 {} 秒后再试", wait.as_secs().max(1)));
+            }
+        }
+        attempts.failures = attempts.failures.saturating_add(1);
+        let delay = lock_service::retry_delay(attempts.failures);
+        if !delay.is_zero() {
+            attempts.retry_at = Some(now + delay);
+        }
+        Ok(())
+    }
+
+    /// 应用新的设置（开启、关闭应用锁或修改空闲时间）
+    pub fn configure(&self, enabled: bool, idle_minutes: u32) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        self.idle_minutes.store(idle_minutes, Ordering::SeqCst);
+        if !enabled {
+            self.locked.store(false, Ordering::SeqCst);
+        }
+    }
+
+    /// 是否已超过空闲时间（0 表示不自动锁定）
+    fn is_idle(&self) -> bool {
+        let minutes = self.idle_minutes();
+        if minutes == 0 {
+            return false;
+        }
+        let last = self.last_activity.lock().map(|l| *l).unwrap_or_else(|_| Instant::now());
+        last.elapsed() >= Duration::from_secs(u64::from(minutes) * 60)
+    }
+}
+
+/// 锁定应用并通知所有窗口
+pub fn lock(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if state.lock.lock() {
+        let _ = app.emit("app-locked", ());
+    }
+}
+
+/// 从托盘恢复隐藏的主窗口前先锁定
+pub fn lock_on_restore(app: &AppHandle) {
+    let hidden = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_visible().ok())
+        .is_some_and(|visible| !visible);
+    if hidden {
+        lock(app);
+    }
+}
+
+/// 后台检查空闲时间，超时自动锁定
+pub fn start_idle_watch(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(IDLE_CHECK_INTERVAL);
+        let Some(state) = app.try_state::<AppState>() else {
+            continue;
+        };
+        if state.lock.is_enabled() && !state.lock.is_locked() && state.lock.is_idle() {
+            lock(&app);
+        }
+    });
+}
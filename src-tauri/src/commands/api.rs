@@ -0,0 +1,40 @@
+use super::with_store;
+use crate::api;
+use crate::AppState;
+use ganle_core::models::settings::AppSettings;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// 开启或关闭本地 HTTP API（首次开启时自动生成令牌）
+#[tauri::command]
+pub async fn set_api_enabled(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<AppSettings, String> {
+    with_store(state, move |store| {
+        if enabled && store.setting("api_token").is_none() {
+            store.update_setting("api_token", &new_token())?;
+        }
+        store.update_setting("api_enabled", &enabled.to_string())?;
+        // 停止旧服务时要等后台线程退出，放在后台线程中执行
+        api::apply_settings(&app)?;
+        store.settings()
+    })
+    .await
+}
+
+/// 重新生成 API 令牌，旧令牌立即失效
+#[tauri::command]
+pub async fn regenerate_api_token(state: State<'_, AppState>) -> Result<String, String> {
+    with_store(state, move |store| {
+        let token = new_token();
+        store.update_setting("api_token", &token)?;
+        Ok(token)
+    })
+    .await
+}
+
+fn new_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
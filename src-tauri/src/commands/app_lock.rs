@@ -0,0 +1,93 @@
+use crate::{app_lock, deep_link, AppState};
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+/// 密码最短长度
+const MIN_PIN_LEN: usize = 4;
+
+/// 应用锁状态
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockStatus {
+    pub enabled: bool,
+    pub locked: bool,
+    /// 无操作自动锁定的分钟数，0 表示不自动锁定
+    pub idle_minutes: u32,
+}
+
+/// 获取应用锁状态（锁定时也可调用）
+#[tauri::command]
+pub fn get_lock_status(state: State<'_, AppState>) -> LockStatus {
+    LockStatus {
+        enabled: state.lock.is_enabled(),
+        locked: state.lock.is_locked(),
+        idle_minutes: state.lock.idle_minutes(),
+    }
+}
+
+/// 输入密码解锁
+#[tauri::command]
+pub async fn unlock_app(app: AppHandle, state: State<'_, AppState>, pin: String) -> Result<(), String> {
+    state.lock.begin_attempt()?;
+    let store = state.store();
+    // 哈希计算较慢，放到后台线程
+    let valid = tauri::async_runtime::spawn_blocking(move || store.verify_app_lock(&pin))
+        .await
+        .map_err(|e| e.to_string())?;
+    if !valid {
+        return Err("密码错误".to_string());
+    }
+    state.lock.unlock();
+    deep_link::replay(&app);
+    Ok(())
+}
+
+/// 立即锁定
+#[tauri::command]
+pub fn lock_app(app: AppHandle) {
+    app_lock::lock(&app);
+}
+
+/// 前端报告用户活动，重新开始空闲计时
+#[tauri::command]
+pub fn report_activity(state: State<'_, AppState>) {
+    if !state.lock.is_locked() {
+        state.lock.touch();
+    }
+}
+
+/// 设置、修改或取消应用锁密码。已设置密码时须提供当前密码
+#[tauri::command]
+pub async fn set_app_lock(
+    state: State<'_, AppState>,
+    pin: Option<String>,
+    current_pin: Option<String>,
+) -> Result<LockStatus, String> {
+    state.lock.check()?;
+    if let Some(pin) = &pin {
+        if pin.chars().count() < MIN_PIN_LEN {
+            return Err(format!("密码至少需要 {} 位", MIN_PIN_LEN));
+        }
+    }
+    let store = state.store();
+    let enabled = pin.is_some();
+    tauri::async_runtime::spawn_blocking(move || {
+        if !store.verify_app_lock(current_pin.as_deref().unwrap_or_default()) {
+            return Err("当前密码错误".to_string());
+        }
+        store.set_app_lock(pin.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    state.lock.configure(enabled, state.lock.idle_minutes());
+    Ok(get_lock_status(state))
+}
+
+/// 修改自动锁定时间
+#[tauri::command]
+pub fn set_app_lock_minutes(state: State<'_, AppState>, minutes: u32) -> Result<LockStatus, String> {
+    state.lock.check()?;
+    state.store().update_setting("app_lock_minutes", &minutes.to_string())?;
+    state.lock.configure(state.lock.is_enabled(), minutes);
+    Ok(get_lock_status(state))
+}
@@ -0,0 +1,9 @@
+use crate::deep_link::DeepLinkState;
+use tauri::State;
+
+/// 取出由链接唤起时待跳转的任务（只返回一次）
+#[tauri::command]
+pub fn take_pending_navigation(state: State<'_, DeepLinkState>) -> Result<Option<String>, String> {
+    let mut pending = state.pending_task.lock().map_err(|e| e.to_string())?;
+    Ok(pending.take())
+}
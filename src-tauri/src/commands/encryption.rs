@@ -0,0 +1,96 @@
+use crate::workspace::{self, WorkspaceState};
+use crate::AppState;
+use ganle_core::db::connection;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+/// 密码最短长度
+const MIN_PASSPHRASE_LEN: usize = 6;
+
+/// 数据库加密状态
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStatus {
+    /// 当前版本是否支持加密
+    pub supported: bool,
+    pub encrypted: bool,
+    /// 数据库是否已打开（加密时需先输入密码）
+    pub unlocked: bool,
+}
+
+/// 获取当前工作区数据库的加密与解锁状态
+#[tauri::command]
+pub fn get_database_status(
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceState>,
+) -> Result<DatabaseStatus, String> {
+    Ok(DatabaseStatus {
+        supported: connection::encryption_supported(),
+        encrypted: connection::is_encrypted(&workspaces.current_path()?),
+        unlocked: app.try_state::<AppState>().is_some(),
+    })
+}
+
+/// 用密码打开加密的数据库，成功后启动各项服务
+#[tauri::command]
+pub async fn unlock_database(app: AppHandle, passphrase: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let workspaces = app.state::<WorkspaceState>();
+        let _opening = workspaces.opening.lock().map_err(|e| e.to_string())?;
+        if app.try_state::<AppState>().is_some() {
+            return Ok(());
+        }
+        let store = workspace::open_store(&app, &workspaces.current_path()?, Some(&passphrase))?;
+        crate::start(&app, store).map_err(|e| e.to_string())?;
+        // 刚输入过数据库密码，不再要求应用锁密码
+        app.state::<AppState>().lock.unlock();
+        crate::deep_link::replay(&app);
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 开启加密：把数据导出为加密文件后重启，启动时替换原数据库
+#[tauri::command]
+pub fn enable_encryption(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    workspaces: State<'_, WorkspaceState>,
+    passphrase: String,
+) -> Result<(), String> {
+    state.lock.check()?;
+    let path = workspaces.current_path()?;
+    if connection::is_encrypted(&path) {
+        return Err("数据库已经加密".to_string());
+    }
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!("密码至少需要 {} 个字符", MIN_PASSPHRASE_LEN));
+    }
+    // 导出后一直占住写连接直到重启，之后的写入不会遗漏在即将被替换的旧文件中；
+    // export_copy 返回时导出文件已写入磁盘
+    state.store().write(|conn| {
+        connection::export_copy(conn, &connection::pending_path(&path), Some(&passphrase))?;
+        app.restart()
+    })
+}
+
+/// 关闭加密：验证密码后导出为明文文件并重启
+#[tauri::command]
+pub fn disable_encryption(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    workspaces: State<'_, WorkspaceState>,
+    passphrase: String,
+) -> Result<(), String> {
+    state.lock.check()?;
+    let path = workspaces.current_path()?;
+    if !connection::is_encrypted(&path) {
+        return Err("数据库未加密".to_string());
+    }
+    connection::verify_key(&path, &passphrase)?;
+    state.store().write(|conn| {
+        connection::export_copy(conn, &connection::pending_path(&path), None)?;
+        app.restart()
+    })
+}
@@ -0,0 +1,22 @@
+use super::with_store;
+use crate::AppState;
+use ganle_core::models::task::UndoState;
+use tauri::State;
+
+/// 撤销最近一步任务操作，返回被撤销操作的说明
+#[tauri::command]
+pub async fn undo(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    with_store(state, move |store| store.undo()).await
+}
+
+/// 重做最近撤销的操作
+#[tauri::command]
+pub async fn redo(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    with_store(state, move |store| store.redo()).await
+}
+
+/// 获取可撤销、可重做的操作说明
+#[tauri::command]
+pub async fn get_undo_state(state: State<'_, AppState>) -> Result<UndoState, String> {
+    with_store(state, move |store| Ok(store.history())).await
+}
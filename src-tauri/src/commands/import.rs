@@ -0,0 +1,24 @@
+use super::with_store;
+use crate::AppState;
+use ganle_core::models::import::{ImportPreview, ImportSummary};
+use ganle_core::services::import_service;
+use tauri::State;
+
+/// 解析外部应用导出文件，返回预览
+#[tauri::command]
+pub fn preview_import(
+    source: String,
+    content: String,
+    file_name: Option<String>,
+) -> Result<ImportPreview, String> {
+    import_service::preview(&source, &content, file_name.as_deref())
+}
+
+/// 确认导入预览内容
+#[tauri::command]
+pub async fn commit_import(
+    state: State<'_, AppState>,
+    preview: ImportPreview,
+) -> Result<ImportSummary, String> {
+    with_store(state, move |store| store.commit_import(&preview)).await
+}
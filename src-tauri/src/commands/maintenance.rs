@@ -0,0 +1,10 @@
+use super::with_store;
+use crate::AppState;
+use ganle_core::models::maintenance::MaintenanceReport;
+use tauri::State;
+
+/// 检查并修复数据库，然后整理文件
+#[tauri::command]
+pub async fn run_maintenance(state: State<'_, AppState>) -> Result<MaintenanceReport, String> {
+    with_store(state, move |store| store.maintain()).await
+}
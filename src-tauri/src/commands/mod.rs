@@ -1,5 +1,38 @@
+pub mod api;
+pub mod app_lock;
+pub mod deep_link;
+pub mod encryption;
+pub mod history;
+pub mod import;
+pub mod maintenance;
+pub mod outline;
+pub mod pomodoro;
 pub mod project;
+pub mod quick_add;
+pub mod seed;
 pub mod settings;
+pub mod stats;
 pub mod tag;
 pub mod task;
+pub mod timer;
+pub mod todotxt;
 pub mod window;
+pub mod workspace;
+
+use crate::AppState;
+use ganle_core::TaskStore;
+use tauri::State;
+
+/// 在后台线程中访问数据，数据库读写不占用 IPC 线程；
+/// 只读命令各自取用只读连接，可以并发执行。应用锁定时拒绝访问
+pub async fn with_store<T, F>(state: State<'_, AppState>, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&TaskStore) -> Result<T, String> + Send + 'static,
+{
+    state.lock.check()?;
+    let store = state.store();
+    tauri::async_runtime::spawn_blocking(move || f(&store))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,15 @@
+use super::with_store;
+use crate::AppState;
+use tauri::State;
+
+/// 导出为 org-mode 文本
+#[tauri::command]
+pub async fn export_org(state: State<'_, AppState>) -> Result<String, String> {
+    with_store(state, move |store| store.export_org()).await
+}
+
+/// 导出为 OPML 文本
+#[tauri::command]
+pub async fn export_opml(state: State<'_, AppState>) -> Result<String, String> {
+    with_store(state, move |store| store.export_opml()).await
+}
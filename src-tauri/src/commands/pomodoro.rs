@@ -0,0 +1,49 @@
+use super::with_store;
+use crate::{pomodoro, AppState};
+use ganle_core::models::pomodoro::PomodoroStatus;
+use tauri::{AppHandle, State};
+
+/// 获取番茄钟状态
+#[tauri::command]
+pub fn get_pomodoro(app: AppHandle, state: State<'_, AppState>) -> Result<PomodoroStatus, String> {
+    state.lock.check()?;
+    Ok(pomodoro::status(&app))
+}
+
+/// 开始一轮专注，可关联任务（完成的专注记入该任务的用时）
+#[tauri::command]
+pub async fn start_pomodoro(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    task_id: Option<String>,
+) -> Result<PomodoroStatus, String> {
+    with_store(state, move |store| pomodoro::start(&app, store, task_id)).await
+}
+
+/// 暂停当前阶段
+#[tauri::command]
+pub fn pause_pomodoro(app: AppHandle, state: State<'_, AppState>) -> Result<PomodoroStatus, String> {
+    state.lock.check()?;
+    Ok(pomodoro::pause(&app))
+}
+
+/// 继续暂停的阶段
+#[tauri::command]
+pub fn resume_pomodoro(app: AppHandle, state: State<'_, AppState>) -> Result<PomodoroStatus, String> {
+    state.lock.check()?;
+    Ok(pomodoro::resume(&app))
+}
+
+/// 跳过当前阶段
+#[tauri::command]
+pub fn skip_pomodoro(app: AppHandle, state: State<'_, AppState>) -> Result<PomodoroStatus, String> {
+    state.lock.check()?;
+    Ok(pomodoro::skip(&app))
+}
+
+/// 结束番茄钟
+#[tauri::command]
+pub fn stop_pomodoro(app: AppHandle, state: State<'_, AppState>) -> Result<PomodoroStatus, String> {
+    state.lock.check()?;
+    Ok(pomodoro::stop(&app))
+}
@@ -0,0 +1,44 @@
+use super::with_store;
+use crate::quick_add;
+use crate::AppState;
+use ganle_core::models::settings::AppSettings;
+use ganle_core::models::task::Task;
+use ganle_core::services::nlp::{self, ParsedTask};
+use tauri::{AppHandle, State};
+
+/// 解析输入内容（供输入时预览）
+#[tauri::command]
+pub async fn parse_quick_add(state: State<'_, AppState>, text: String) -> Result<ParsedTask, String> {
+    with_store(state, move |store| Ok(nlp::parse(&text, store.timezone()))).await
+}
+
+/// 用一句话创建任务，成功后隐藏窗口（主窗口通过变更事件得知新任务）
+#[tauri::command]
+pub async fn quick_add_task(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    text: String,
+) -> Result<Task, String> {
+    let task = with_store(state, move |store| store.quick_add(&text)).await?;
+    quick_add::hide(&app);
+    Ok(task)
+}
+
+/// 隐藏快速添加窗口
+#[tauri::command]
+pub fn hide_quick_add(app: AppHandle) {
+    quick_add::hide(&app);
+}
+
+/// 修改快速添加快捷键（注册成功后才保存）
+#[tauri::command]
+pub fn set_quick_add_shortcut(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    shortcut: String,
+) -> Result<AppSettings, String> {
+    state.lock.check()?;
+    quick_add::register(&app, &shortcut)?;
+    state.store().update_setting("quick_add_shortcut", &shortcut)?;
+    state.store().settings()
+}
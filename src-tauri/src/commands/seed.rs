@@ -0,0 +1,23 @@
+use super::with_store;
+use crate::AppState;
+use ganle_core::models::import::ImportSummary;
+use ganle_core::models::seed::{SeedChoice, SetupStatus};
+use tauri::State;
+
+/// 获取首次启动选择与示例数据状态
+#[tauri::command]
+pub async fn get_setup_status(state: State<'_, AppState>) -> Result<SetupStatus, String> {
+    with_store(state, move |store| store.setup_status()).await
+}
+
+/// 写入初始数据：空白、示例数据或 JSON 模板包
+#[tauri::command]
+pub async fn seed_database(state: State<'_, AppState>, choice: SeedChoice) -> Result<ImportSummary, String> {
+    with_store(state, move |store| store.seed(&choice)).await
+}
+
+/// 删除全部示例数据，返回删除的任务数
+#[tauri::command]
+pub async fn remove_sample_data(state: State<'_, AppState>) -> Result<usize, String> {
+    with_store(state, move |store| store.remove_sample_data()).await
+}
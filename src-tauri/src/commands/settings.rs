@@ -1,22 +1,26 @@
-use crate::db::settings_repo;
-use crate::models::settings::AppSettings;
+use super::with_store;
 use crate::AppState;
+use ganle_core::models::settings::AppSettings;
 use tauri::State;
 
 /// 获取应用设置
 #[tauri::command]
-pub fn get_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    settings_repo::get_settings(&db.conn)
+pub async fn get_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
+    with_store(state, move |store| store.settings()).await
 }
 
+/// 只能通过专门命令修改的设置项（应用锁密码须验证当前密码）
+const PROTECTED_KEYS: &[&str] = &["app_lock_hash", "app_lock_minutes"];
+
 /// 更新设置
 #[tauri::command]
-pub fn update_settings(
+pub async fn update_settings(
     state: State<'_, AppState>,
     key: String,
     value: String,
 ) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    settings_repo::update_setting(&db.conn, &key, &value)
+    if PROTECTED_KEYS.contains(&key.as_str()) {
+        return Err(format!("不能直接修改设置项: {}", key));
+    }
+    with_store(state, move |store| store.update_setting(&key, &value)).await
 }
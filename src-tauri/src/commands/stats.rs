@@ -0,0 +1,20 @@
+use super::with_store;
+use crate::AppState;
+use ganle_core::models::stats::{CompletionPoint, StatsPeriod, StatsSummary};
+use tauri::State;
+
+/// 最近 count 个周期（day / week / month）每个周期完成的任务数
+#[tauri::command]
+pub async fn get_completion_series(
+    state: State<'_, AppState>,
+    period: StatsPeriod,
+    count: u32,
+) -> Result<Vec<CompletionPoint>, String> {
+    with_store(state, move |store| store.completion_series(period, count)).await
+}
+
+/// 获取统计概览（完成率、连续完成天数、平均耗时、逾期率与分布）
+#[tauri::command]
+pub async fn get_stats_summary(state: State<'_, AppState>) -> Result<StatsSummary, String> {
+    with_store(state, move |store| store.stats_summary()).await
+}
@@ -1,50 +1,43 @@
-use crate::db::tag_repo;
-use crate::models::tag::Tag;
+use super::with_store;
 use crate::AppState;
+use ganle_core::models::tag::Tag;
 use tauri::State;
-use uuid::Uuid;
 
 /// 获取所有标签
 #[tauri::command]
-pub fn get_all_tags(state: State<'_, AppState>) -> Result<Vec<Tag>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    tag_repo::get_all(&db.conn)
+pub async fn get_all_tags(state: State<'_, AppState>) -> Result<Vec<Tag>, String> {
+    with_store(state, move |store| store.tags()).await
 }
 
 /// 创建标签
 #[tauri::command]
-pub fn create_tag(
+pub async fn create_tag(
     state: State<'_, AppState>,
     name: String,
     color: String,
 ) -> Result<Tag, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let id = format!("tag-{}", Uuid::new_v4());
-    tag_repo::create(&db.conn, &id, &name, &color)
+    with_store(state, move |store| store.create_tag(&name, &color)).await
 }
 
 /// 更新标签
 #[tauri::command]
-pub fn update_tag(
+pub async fn update_tag(
     state: State<'_, AppState>,
     id: String,
     name: Option<String>,
     color: Option<String>,
 ) -> Result<Tag, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    tag_repo::update(&db.conn, &id, name.as_deref(), color.as_deref())
+    with_store(state, move |store| store.update_tag(&id, name.as_deref(), color.as_deref())).await
 }
 
 /// 删除标签
 #[tauri::command]
-pub fn delete_tag(state: State<'_, AppState>, id: String) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    tag_repo::delete(&db.conn, &id)
+pub async fn delete_tag(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    with_store(state, move |store| store.delete_tag(&id)).await
 }
 
 /// 获取标签下未完成任务数
 #[tauri::command]
-pub fn get_tag_task_count(state: State<'_, AppState>, id: String) -> Result<usize, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    tag_repo::get_task_count(&db.conn, &id)
+pub async fn get_tag_task_count(state: State<'_, AppState>, id: String) -> Result<usize, String> {
+    with_store(state, move |store| store.tag_task_count(&id)).await
 }
@@ -1,57 +1,59 @@
-use crate::db::task_repo;
-use crate::models::task::{Task, TaskCounts, SubTask};
-use crate::services::{repeat_service, reminder_service, task_service};
+use super::with_store;
 use crate::AppState;
+use ganle_core::models::task::{Due, NewTask, SubTask, Task, TaskCounts, TaskPatch};
+use ganle_core::models::task_event::TaskEvent;
+use ganle_core::services::task_service::normalize_start;
 use tauri::State;
-use uuid::Uuid;
 
 /// 获取所有任务
 #[tauri::command]
-pub fn get_all_tasks(state: State<'_, AppState>) -> Result<Vec<Task>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::get_all(&db.conn)
+pub async fn get_all_tasks(state: State<'_, AppState>) -> Result<Vec<Task>, String> {
+    with_store(state, move |store| store.all_tasks()).await
 }
 
 /// 获取过滤后的任务
 #[tauri::command]
-pub fn get_filtered_tasks(
+pub async fn get_filtered_tasks(
     state: State<'_, AppState>,
     view_type: String,
     view_id: String,
     search: String,
 ) -> Result<Vec<Task>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_service::get_filtered_tasks(&db.conn, &view_type, &view_id, &search)
+    with_store(state, move |store| store.filtered_tasks(&view_type, &view_id, &search)).await
 }
 
-/// 创建任务
+/// 创建任务（截止时间的钟点按用户时区解释）
 #[tauri::command]
-pub fn create_task(
+pub async fn create_task(
     state: State<'_, AppState>,
     title: String,
     priority: String,
     project_id: Option<String>,
     due_date: Option<String>,
+    start_date: Option<String>,
+    estimate_minutes: Option<u32>,
     repeat_type: String,
     tag_ids: Vec<String>,
 ) -> Result<Task, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let id = format!("task-{}", Uuid::new_v4());
-    task_repo::create(
-        &db.conn,
-        &id,
-        &title,
-        &priority,
-        project_id.as_deref(),
-        due_date.as_deref(),
-        &repeat_type,
-        &tag_ids,
-    )
+    with_store(state, move |store| {
+        let tz = store.timezone();
+        store.create_task(&NewTask {
+            title,
+            priority,
+            project_id,
+            due: due_date.map(|d| Due::parse(&d, tz)).transpose()?,
+            start_date: start_date.map(|d| normalize_start(&d, tz)).transpose()?,
+            estimate_minutes,
+            repeat_type,
+            tag_ids,
+        })
+    })
+    .await
 }
 
 /// 更新任务
 #[tauri::command]
-pub fn update_task(
+pub async fn update_task(
     state: State<'_, AppState>,
     id: String,
     title: Option<String>,
@@ -60,121 +62,123 @@ pub fn update_task(
     priority: Option<String>,
     project_id: Option<Option<String>>,
     due_date: Option<Option<String>>,
+    start_date: Option<Option<String>>,
+    estimate_minutes: Option<Option<u32>>,
     reminder: Option<Option<String>>,
     repeat_type: Option<String>,
     tag_ids: Option<Vec<String>>,
 ) -> Result<Task, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::update(
-        &db.conn,
-        &id,
-        title.as_deref(),
-        description.as_deref(),
-        status.as_deref(),
-        priority.as_deref(),
-        project_id.as_ref().map(|p| p.as_deref()),
-        due_date.as_ref().map(|d| d.as_deref()),
-        reminder.as_ref().map(|r| r.as_deref()),
-        repeat_type.as_deref(),
-        tag_ids.as_deref(),
-    )
+    with_store(state, move |store| {
+        let tz = store.timezone();
+        let due = due_date
+            .map(|d| d.map(|d| Due::parse(&d, tz)).transpose())
+            .transpose()?;
+        // 前端传 null 时无法与“不修改”区分，开始日期用空字符串、预估用时用 0 表示清除
+        let start_date = start_date
+            .map(|d| d.filter(|d| !d.is_empty()).map(|d| normalize_start(&d, tz)).transpose())
+            .transpose()?;
+        let estimate_minutes = estimate_minutes.map(|m| m.filter(|m| *m > 0));
+        store.update_task(
+            &id,
+            &TaskPatch {
+                title,
+                description,
+                status,
+                priority,
+                project_id,
+                due,
+                start_date,
+                estimate_minutes,
+                reminder,
+                repeat_type,
+                tag_ids,
+            },
+        )
+    })
+    .await
 }
 
 /// 切换任务完成状态（含重复任务生成）
 #[tauri::command]
-pub fn toggle_task_status(state: State<'_, AppState>, id: String) -> Result<Task, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let task = task_repo::toggle_status(&db.conn, &id)?;
-
-    // 如果刚完成且是重复任务，生成下一个
-    if task.status == crate::models::task::TaskStatus::Completed
-        && task.repeat != crate::models::task::RepeatType::None
-    {
-        let _ = repeat_service::create_next_repeat_task(&db.conn, &task);
-    }
-
-    Ok(task)
+pub async fn toggle_task_status(state: State<'_, AppState>, id: String) -> Result<Task, String> {
+    with_store(state, move |store| store.toggle_task(&id)).await
 }
 
 /// 切换收藏状态
 #[tauri::command]
-pub fn toggle_favorite(state: State<'_, AppState>, id: String) -> Result<Task, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::toggle_favorite(&db.conn, &id)
+pub async fn toggle_favorite(state: State<'_, AppState>, id: String) -> Result<Task, String> {
+    with_store(state, move |store| store.toggle_favorite(&id)).await
 }
 
 /// 删除任务
 #[tauri::command]
-pub fn delete_task(state: State<'_, AppState>, id: String) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::delete(&db.conn, &id)
+pub async fn delete_task(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    with_store(state, move |store| store.delete_task(&id)).await
 }
 
 /// 重新排序任务
 #[tauri::command]
-pub fn reorder_tasks(state: State<'_, AppState>, ordered_ids: Vec<String>) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::reorder(&db.conn, &ordered_ids)
+pub async fn reorder_tasks(state: State<'_, AppState>, ordered_ids: Vec<String>) -> Result<(), String> {
+    with_store(state, move |store| store.reorder_tasks(&ordered_ids)).await
 }
 
 /// 获取任务统计数据
 #[tauri::command]
-pub fn get_task_counts(state: State<'_, AppState>) -> Result<TaskCounts, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::get_counts(&db.conn)
+pub async fn get_task_counts(state: State<'_, AppState>) -> Result<TaskCounts, String> {
+    with_store(state, move |store| store.task_counts()).await
+}
+
+/// 获取任务的变更记录
+#[tauri::command]
+pub async fn get_task_history(state: State<'_, AppState>, id: String) -> Result<Vec<TaskEvent>, String> {
+    with_store(state, move |store| store.task_history(&id)).await
 }
 
 /// 检查提醒
 #[tauri::command]
-pub fn check_reminders(state: State<'_, AppState>) -> Result<Option<Task>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    reminder_service::check_reminders(&db.conn)
+pub async fn check_reminders(state: State<'_, AppState>) -> Result<Option<Task>, String> {
+    with_store(state, move |store| store.check_reminders()).await
 }
 
 // ========== 子任务命令 ==========
 
 /// 添加子任务
 #[tauri::command]
-pub fn add_subtask(
+pub async fn add_subtask(
     state: State<'_, AppState>,
     task_id: String,
     title: String,
 ) -> Result<SubTask, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    let id = format!("sub-{}", Uuid::new_v4());
-    task_repo::add_subtask(&db.conn, &id, &task_id, &title)
+    with_store(state, move |store| store.add_subtask(&task_id, &title)).await
 }
 
 /// 切换子任务完成状态
 #[tauri::command]
-pub fn toggle_subtask(
+pub async fn toggle_subtask(
     state: State<'_, AppState>,
     task_id: String,
     subtask_id: String,
 ) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::toggle_subtask(&db.conn, &task_id, &subtask_id)
+    with_store(state, move |store| store.toggle_subtask(&task_id, &subtask_id)).await
 }
 
 /// 更新子任务标题
 #[tauri::command]
-pub fn update_subtask(
+pub async fn update_subtask(
     state: State<'_, AppState>,
     task_id: String,
     subtask_id: String,
     title: String,
 ) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::update_subtask(&db.conn, &task_id, &subtask_id, &title)
+    with_store(state, move |store| store.update_subtask(&task_id, &subtask_id, &title)).await
 }
 
 /// 删除子任务
 #[tauri::command]
-pub fn delete_subtask(
+pub async fn delete_subtask(
     state: State<'_, AppState>,
     task_id: String,
     subtask_id: String,
 ) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    task_repo::delete_subtask(&db.conn, &task_id, &subtask_id)
+    with_store(state, move |store| store.delete_subtask(&task_id, &subtask_id)).await
 }
@@ -0,0 +1,40 @@
+use super::with_store;
+use crate::AppState;
+use ganle_core::models::time_entry::{TimeEntry, TimeTotals};
+use tauri::State;
+
+/// 获取正在进行的计时
+#[tauri::command]
+pub async fn get_running_timer(state: State<'_, AppState>) -> Result<Option<TimeEntry>, String> {
+    with_store(state, move |store| store.running_timer()).await
+}
+
+/// 开始为任务计时（已有计时会先结束）
+#[tauri::command]
+pub async fn start_timer(state: State<'_, AppState>, task_id: String) -> Result<TimeEntry, String> {
+    with_store(state, move |store| store.start_timer(&task_id)).await
+}
+
+/// 结束正在进行的计时
+#[tauri::command]
+pub async fn stop_timer(state: State<'_, AppState>) -> Result<Option<TimeEntry>, String> {
+    with_store(state, move |store| store.stop_timer()).await
+}
+
+/// 获取任务的计时记录
+#[tauri::command]
+pub async fn get_time_entries(state: State<'_, AppState>, task_id: String) -> Result<Vec<TimeEntry>, String> {
+    with_store(state, move |store| store.time_entries(&task_id)).await
+}
+
+/// 删除计时记录
+#[tauri::command]
+pub async fn delete_time_entry(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    with_store(state, move |store| store.delete_time_entry(id)).await
+}
+
+/// 按任务、项目和标签汇总预估与实际用时
+#[tauri::command]
+pub async fn get_time_totals(state: State<'_, AppState>) -> Result<TimeTotals, String> {
+    with_store(state, move |store| store.time_totals()).await
+}
@@ -0,0 +1,19 @@
+use super::with_store;
+use crate::AppState;
+use tauri::State;
+
+/// 导出全部任务为 todo.txt 文本
+#[tauri::command]
+pub async fn export_todotxt(state: State<'_, AppState>) -> Result<String, String> {
+    with_store(state, move |store| store.export_todotxt()).await
+}
+
+/// 导入 todo.txt 文本，返回导入的任务数
+#[tauri::command]
+pub async fn import_todotxt(state: State<'_, AppState>, content: String) -> Result<usize, String> {
+    with_store(state, move |store| {
+        let ids = store.import_todotxt(&content)?;
+        Ok(ids.len())
+    })
+    .await
+}
@@ -0,0 +1,112 @@
+use crate::workspace::{self, WorkspaceState};
+use crate::AppState;
+use ganle_core::db::connection;
+use ganle_core::models::workspace::Workspace;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+/// 工作区列表
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceInfo {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub current: bool,
+    pub encrypted: bool,
+}
+
+/// 应用已启动且处于锁定状态时拒绝管理工作区
+fn ensure_unlocked(app: &AppHandle) -> Result<(), String> {
+    match app.try_state::<AppState>() {
+        Some(state) => state.lock.check(),
+        None => Ok(()),
+    }
+}
+
+/// 获取所有工作区
+#[tauri::command]
+pub fn list_workspaces(workspaces: State<'_, WorkspaceState>) -> Result<Vec<WorkspaceInfo>, String> {
+    let registry = workspaces.registry.lock().map_err(|e| e.to_string())?;
+    let current = registry.current().id.clone();
+    Ok(registry
+        .workspaces()
+        .iter()
+        .map(|w| WorkspaceInfo {
+            id: w.id.clone(),
+            name: w.name.clone(),
+            created_at: w.created_at.clone(),
+            current: w.id == current,
+            encrypted: connection::is_encrypted(&registry.path_of(w)),
+        })
+        .collect())
+}
+
+/// 新建工作区（不会自动切换过去）
+#[tauri::command]
+pub fn create_workspace(
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceState>,
+    name: String,
+) -> Result<Workspace, String> {
+    ensure_unlocked(&app)?;
+    let workspace = workspaces
+        .registry
+        .lock()
+        .map_err(|e| e.to_string())?
+        .create(&name)?;
+    crate::refresh_tray_menu(&app);
+    Ok(workspace)
+}
+
+/// 重命名工作区
+#[tauri::command]
+pub fn rename_workspace(
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceState>,
+    id: String,
+    name: String,
+) -> Result<Workspace, String> {
+    ensure_unlocked(&app)?;
+    let workspace = workspaces
+        .registry
+        .lock()
+        .map_err(|e| e.to_string())?
+        .rename(&id, &name)?;
+    crate::refresh_tray_menu(&app);
+    Ok(workspace)
+}
+
+/// 切换工作区，加密的工作区需提供密码
+#[tauri::command]
+pub async fn switch_workspace(
+    app: AppHandle,
+    id: String,
+    passphrase: Option<String>,
+) -> Result<Workspace, String> {
+    ensure_unlocked(&app)?;
+    tauri::async_runtime::spawn_blocking(move || workspace::switch(&app, &id, passphrase.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 删除工作区及其数据（不能删除当前工作区）
+#[tauri::command]
+pub fn delete_workspace(
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceState>,
+    id: String,
+) -> Result<(), String> {
+    ensure_unlocked(&app)?;
+    {
+        // 避免删除正在打开的工作区
+        let _opening = workspaces.opening.lock().map_err(|e| e.to_string())?;
+        workspaces
+            .registry
+            .lock()
+            .map_err(|e| e.to_string())?
+            .delete(&id)?;
+    }
+    crate::refresh_tray_menu(&app);
+    Ok(())
+}
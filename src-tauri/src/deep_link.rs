@@ -0,0 +1,184 @@
+use crate::AppState;
+use ganle_core::models::task::NewTask;
+use ganle_core::services::task_service;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+/// 自定义 URL 协议
+pub const SCHEME: &str = "ganleme";
+
+/// 解析后的链接动作
+#[derive(Debug, PartialEq)]
+pub enum DeepLink {
+    /// ganleme://task/<id>
+    OpenTask { id: String },
+    /// ganleme://add?title=...&due=...&start=...&project=...
+    AddTask {
+        title: String,
+        due: Option<String>,
+        start: Option<String>,
+        project: Option<String>,
+    },
+}
+
+/// 前端尚未就绪时暂存的跳转目标（冷启动时由链接唤起）
+#[derive(Default)]
+pub struct DeepLinkState {
+    pub pending_task: Mutex<Option<String>>,
+    /// 数据库尚未打开（等待输入加密密码）或应用锁定时收到的链接，就绪后依次执行
+    queued: Mutex<Vec<DeepLink>>,
+}
+
+/// 跳转事件载荷
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NavigatePayload {
+    task_id: String,
+}
+
+/// 注册链接监听，并处理启动时携带的链接。
+/// 在打开数据库之前调用，此时收到的链接先排队，见 replay
+pub fn setup(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    // 开发模式和 AppImage 下需要运行时注册协议，安装包会在安装时注册
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        eprintln!("注册 {}:// 协议失败: {}", SCHEME, e);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            open_url(&handle, &url);
+        }
+    });
+
+    if let Some(urls) = app.deep_link().get_current()? {
+        for url in urls {
+            open_url(app, &url);
+        }
+    }
+    Ok(())
+}
+
+/// 处理一个链接，出错时只记录日志
+pub fn open_url(app: &AppHandle, url: &Url) {
+    let result = parse(url).and_then(|link| {
+        if is_ready(app) {
+            dispatch(app, link)
+        } else {
+            queue(app, link)
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("无法处理链接 {}: {}", url, e);
+    }
+}
+
+/// 数据库打开或解锁后执行排队的链接
+pub fn replay(app: &AppHandle) {
+    if !is_ready(app) {
+        return;
+    }
+    let queued = match app.state::<DeepLinkState>().queued.lock() {
+        Ok(mut queued) => std::mem::take(&mut *queued),
+        Err(e) => {
+            eprintln!("读取待处理链接失败: {}", e);
+            return;
+        }
+    };
+    for link in queued {
+        if let Err(e) = dispatch(app, link) {
+            eprintln!("无法处理链接: {}", e);
+        }
+    }
+}
+
+/// 数据库已打开且应用未锁定
+fn is_ready(app: &AppHandle) -> bool {
+    app.try_state::<AppState>().is_some_and(|state| !state.lock.is_locked())
+}
+
+fn queue(app: &AppHandle, link: DeepLink) -> Result<(), String> {
+    app.state::<DeepLinkState>()
+        .queued
+        .lock()
+        .map_err(|e| e.to_string())?
+        .push(link);
+    Ok(())
+}
+
+/// 校验并解析链接
+pub fn parse(url: &Url) -> Result<DeepLink, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("不支持的协议: {}", url.scheme()));
+    }
+
+    // ganleme://task/1 中 task 是 host，ganleme:task/1 中则在 path 里
+    let mut segments: Vec<String> = url.host_str().map(|h| vec![h.to_string()]).unwrap_or_default();
+    segments.extend(
+        url.path()
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
+    );
+    let query = |key: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    match segments.iter().map(|s| s.as_str()).collect::<Vec<_>>().as_slice() {
+        ["task", id] => Ok(DeepLink::OpenTask { id: id.to_string() }),
+        ["add"] => {
+            let title = query("title").ok_or("缺少 title 参数")?;
+            Ok(DeepLink::AddTask {
+                title,
+                due: query("due"),
+                start: query("start"),
+                project: query("project"),
+            })
+        }
+        _ => Err("无法识别的链接".to_string()),
+    }
+}
+
+/// 执行链接动作，并让主窗口跳转到对应任务
+fn dispatch(app: &AppHandle, link: DeepLink) -> Result<(), String> {
+    let task_id = {
+        let state = app.state::<AppState>();
+        state.lock.check()?;
+        let store = state.store();
+        match link {
+            DeepLink::OpenTask { id } => store.task(&id).map_err(|_| format!("任务不存在: {}", id))?.id,
+            DeepLink::AddTask { title, due, start, project } => {
+                // 项目可以写 ID 或名称，名称不存在时新建
+                let project_id = match project {
+                    Some(p) => Some(match store.project(&p) {
+                        Ok(project) => project.id,
+                        Err(_) => store.find_or_create_project(&p)?.id,
+                    }),
+                    None => None,
+                };
+                let mut new = NewTask::new(title);
+                let tz = store.timezone();
+                new.due = due.map(|d| task_service::normalize_due(&d, tz)).transpose()?;
+                new.start_date = start.map(|d| task_service::normalize_start(&d, tz)).transpose()?;
+                new.project_id = project_id;
+                store.create_task(&new)?.id
+            }
+        }
+    };
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    if let Ok(mut pending) = app.state::<DeepLinkState>().pending_task.lock() {
+        *pending = Some(task_id.clone());
+    }
+    app.emit_to("main", "navigate-task", NavigatePayload { task_id })
+        .map_err(|e| e.to_string())
+}
@@ -1,25 +1,76 @@
+mod api;
+mod app_lock;
 mod commands;
-mod db;
-mod models;
-mod services;
+mod deep_link;
+mod pomodoro;
+mod quick_add;
+mod workspace;
 
-use db::connection::Database;
-use std::sync::Mutex;
+use ganle_core::db::connection;
+use ganle_core::services::todotxt_service::TodoTxtSync;
+use ganle_core::TaskStore;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tauri::{
-    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, WindowEvent,
+    AppHandle, Emitter, Manager, Wry, WindowEvent,
 };
 use tauri_plugin_autostart::MacosLauncher;
 
-/// 应用全局状态
+/// 托盘图标 ID
+pub(crate) const TRAY_ID: &str = "main";
+
+/// 托盘菜单中切换工作区菜单项的 ID 前缀
+const WORKSPACE_MENU_PREFIX: &str = "workspace:";
+
+/// 应用全局状态（数据存储可在多个线程间共享，无需整体加锁）。
+/// 数据库加密时要等解锁后才会注册
 pub struct AppState {
-    pub db: Mutex<Database>,
+    /// 切换工作区时整体替换，已取出的旧存储仍可继续完成手头的操作
+    store: RwLock<Arc<TaskStore>>,
+    pub lock: app_lock::AppLock,
+}
+
+impl AppState {
+    fn new(store: TaskStore, lock: app_lock::AppLock) -> Self {
+        Self {
+            store: RwLock::new(Arc::new(store)),
+            lock,
+        }
+    }
+
+    /// 当前工作区的数据存储
+    pub fn store(&self) -> Arc<TaskStore> {
+        match self.store.read() {
+            Ok(store) => store.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// 切换到另一个工作区的数据存储
+    pub fn replace_store(&self, store: TaskStore) {
+        let store = Arc::new(store);
+        match self.store.write() {
+            Ok(mut current) => *current = store,
+            Err(poisoned) => *poisoned.into_inner() = store,
+        }
+    }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // 单实例需最先注册：再次启动（如点击 ganleme:// 链接）时把链接转交给已运行的实例
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            app_lock::lock_on_restore(app);
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_autostart::init(
@@ -27,29 +78,43 @@ pub fn run() {
             Some(vec![]),
         ))
         .setup(|app| {
-            // 初始化数据库
-            let app_dir = app.path().app_data_dir()?;
-            std::fs::create_dir_all(&app_dir)?;
-            let db_path = app_dir.join("ganle_me.db");
-            let database = Database::new(&db_path)
-                .map_err(|e| e.to_string())?;
-            database.run_migrations()
-                .map_err(|e| e.to_string())?;
-            app.manage(AppState {
-                db: Mutex::new(database),
-            });
+            let workspaces = workspace::WorkspaceState::load(&app.path().app_data_dir()?)?;
+            let db_path = workspaces.current_path()?;
+            app.manage(workspaces);
+            app.manage(api::ApiState::default());
+            app.manage(deep_link::DeepLinkState::default());
+            app.manage(pomodoro::PomodoroState::default());
+
+            // 处理 ganleme:// 链接。数据库打开前收到的链接先排队，打开并解锁后执行
+            deep_link::setup(app.handle())?;
 
             // 创建系统托盘
             setup_tray(app)?;
 
+            // 推进番茄钟并在托盘提示中显示倒计时
+            pomodoro::start_ticker(app.handle().clone());
+
+            // 先完成上次开启/关闭加密留下的迁移，再判断是否需要密码
+            connection::apply_pending(&db_path)?;
+
+            // 加密的数据库由前端提示输入密码后再打开（见 unlock_database 命令）
+            if !connection::is_encrypted(&db_path) {
+                start(app.handle(), workspace::open_store(app.handle(), &db_path, None)?)?;
+            }
+
             Ok(())
         })
-        .on_window_event(|window, event| {
+        .on_window_event(|window, event| match event {
             // 点击关闭按钮时隐藏到托盘而非退出
-            if let WindowEvent::CloseRequested { api, .. } = event {
+            WindowEvent::CloseRequested { api, .. } => {
                 api.prevent_close();
                 let _ = window.hide();
             }
+            // 快速添加窗口失去焦点即收起
+            WindowEvent::Focused(false) if window.label() == quick_add::WINDOW_LABEL => {
+                let _ = window.hide();
+            }
+            _ => {}
         })
         .invoke_handler(tauri::generate_handler![
             // 任务命令
@@ -63,11 +128,33 @@ pub fn run() {
             commands::task::reorder_tasks,
             commands::task::get_task_counts,
             commands::task::check_reminders,
+            commands::task::get_task_history,
             // 子任务命令
             commands::task::add_subtask,
             commands::task::toggle_subtask,
             commands::task::update_subtask,
             commands::task::delete_subtask,
+            // 计时命令
+            commands::timer::get_running_timer,
+            commands::timer::start_timer,
+            commands::timer::stop_timer,
+            commands::timer::get_time_entries,
+            commands::timer::delete_time_entry,
+            commands::timer::get_time_totals,
+            // 番茄钟命令
+            commands::pomodoro::get_pomodoro,
+            commands::pomodoro::start_pomodoro,
+            commands::pomodoro::pause_pomodoro,
+            commands::pomodoro::resume_pomodoro,
+            commands::pomodoro::skip_pomodoro,
+            commands::pomodoro::stop_pomodoro,
+            // 统计命令
+            commands::stats::get_completion_series,
+            commands::stats::get_stats_summary,
+            // 撤销与重做
+            commands::history::undo,
+            commands::history::redo,
+            commands::history::get_undo_state,
             // 项目命令
             commands::project::get_all_projects,
             commands::project::create_project,
@@ -83,6 +170,49 @@ pub fn run() {
             // 设置命令
             commands::settings::get_settings,
             commands::settings::update_settings,
+            // 初始数据命令
+            commands::seed::get_setup_status,
+            commands::seed::seed_database,
+            commands::seed::remove_sample_data,
+            // 导入命令
+            commands::import::preview_import,
+            commands::import::commit_import,
+            // 应用锁命令
+            commands::app_lock::get_lock_status,
+            commands::app_lock::unlock_app,
+            commands::app_lock::lock_app,
+            commands::app_lock::report_activity,
+            commands::app_lock::set_app_lock,
+            commands::app_lock::set_app_lock_minutes,
+            // 数据库加密命令
+            commands::encryption::get_database_status,
+            commands::encryption::unlock_database,
+            commands::encryption::enable_encryption,
+            commands::encryption::disable_encryption,
+            // 工作区命令
+            commands::workspace::list_workspaces,
+            commands::workspace::create_workspace,
+            commands::workspace::rename_workspace,
+            commands::workspace::switch_workspace,
+            commands::workspace::delete_workspace,
+            // 数据库维护命令
+            commands::maintenance::run_maintenance,
+            // 大纲导出命令
+            commands::outline::export_org,
+            commands::outline::export_opml,
+            // todo.txt 命令
+            commands::todotxt::export_todotxt,
+            commands::todotxt::import_todotxt,
+            // HTTP API 命令
+            commands::api::set_api_enabled,
+            commands::api::regenerate_api_token,
+            // 快速添加命令
+            commands::quick_add::parse_quick_add,
+            commands::quick_add::quick_add_task,
+            commands::quick_add::hide_quick_add,
+            commands::quick_add::set_quick_add_shortcut,
+            // 链接命令
+            commands::deep_link::take_pending_navigation,
             // 窗口命令
             commands::window::set_window_mode,
         ])
@@ -90,28 +220,96 @@ pub fn run() {
         .expect("启动应用失败");
 }
 
+/// 数据库打开后注册全局状态，并启动依赖数据的各项服务
+pub(crate) fn start(app: &AppHandle, store: TaskStore) -> Result<(), Box<dyn std::error::Error>> {
+    let lock = app_lock::AppLock::new(&store.settings()?);
+    app.manage(AppState::new(store, lock));
+
+    // 注册快速添加全局快捷键
+    quick_add::setup(app)?;
+
+    // 启动 todo.txt 同步
+    start_todotxt_sync(app.clone());
+
+    // 距上次维护超过一周时在后台检查数据库
+    start_maintenance(app.clone());
+
+    // 无操作超时自动锁定
+    app_lock::start_idle_watch(app.clone());
+
+    // 按设置启动本地 HTTP API（默认关闭）
+    if let Err(e) = api::apply_settings(app) {
+        eprintln!("启动 HTTP API 失败: {}", e);
+    }
+
+    // 执行启动时排队的链接（设置了应用锁时等解锁后再执行）
+    deep_link::replay(app);
+    Ok(())
+}
+
+/// 启动 todo.txt 同步线程（设置了同步文件路径时才生效）
+fn start_todotxt_sync(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut sync: Option<TodoTxtSync> = None;
+        let mut current: Option<Arc<TaskStore>> = None;
+        loop {
+            std::thread::sleep(Duration::from_secs(3));
+
+            // 切换工作区后按新数据库重新开始同步
+            let store = app.state::<AppState>().store();
+            if !current.as_ref().is_some_and(|c| Arc::ptr_eq(c, &store)) {
+                sync = None;
+                current = Some(store.clone());
+            }
+            let path = store.setting("todotxt_path").map(PathBuf::from);
+
+            let Some(path) = path else {
+                sync = None;
+                continue;
+            };
+            if sync.as_ref().map(|s| s.path() != path).unwrap_or(true) {
+                sync = Some(TodoTxtSync::new(path));
+            }
+            if let Some(sync) = sync.as_mut() {
+                match store.sync_todotxt(sync) {
+                    Ok(true) => {
+                        let _ = app.emit("todotxt-synced", ());
+                    }
+                    Ok(false) => {}
+                    Err(e) => eprintln!("todo.txt 同步失败: {}", e),
+                }
+            }
+        }
+    });
+}
+
+/// 数据库维护过期时在后台执行一次（不阻塞启动）
+fn start_maintenance(app: AppHandle) {
+    let store = app.state::<AppState>().store();
+    if !store.maintenance_due() {
+        return;
+    }
+    std::thread::spawn(move || match store.maintain() {
+        Ok(report) if !report.integrity_errors.is_empty() => {
+            eprintln!("数据库完整性检查发现问题: {:?}", report.integrity_errors);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("数据库维护失败: {}", e),
+    });
+}
+
 /// 创建系统托盘
 fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    let show = MenuItem::with_id(app, "show", "显示窗口", true, None::<&str>)?;
-    let auto_launch = CheckMenuItem::with_id(
-        app,
-        "auto_launch",
-        "开机自启",
-        true,
-        false,
-        None::<&str>,
-    )?;
-    let separator = PredefinedMenuItem::separator(app)?;
-    let quit = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
+    let menu = build_tray_menu(app.handle())?;
 
-    let menu = Menu::with_items(app, &[&show, &auto_launch, &separator, &quit])?;
-
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(TRAY_ID)
         .icon(app.default_window_icon().unwrap().clone())
         .tooltip("干了么")
         .menu(&menu)
         .on_menu_event(move |app, event| match event.id.as_ref() {
             "show" => {
+                // 从托盘恢复时需重新输入应用锁密码
+                app_lock::lock_on_restore(app);
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.show();
                     let _ = window.set_focus();
@@ -120,7 +318,13 @@ fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
             "quit" => {
                 app.exit(0);
             }
-            _ => {}
+            id => {
+                if let Some(workspace_id) = id.strip_prefix(WORKSPACE_MENU_PREFIX) {
+                    workspace::switch_from_tray(app, workspace_id);
+                    // 勾选状态以切换结果为准
+                    refresh_tray_menu(app);
+                }
+            }
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click {
@@ -130,6 +334,7 @@ fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
             } = event
             {
                 let app = tray.app_handle();
+                app_lock::lock_on_restore(app);
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.show();
                     let _ = window.set_focus();
@@ -140,3 +345,49 @@ fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// 构建托盘菜单，工作区子菜单勾选当前工作区
+fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let show = MenuItem::with_id(app, "show", "显示窗口", true, None::<&str>)?;
+
+    let workspaces = Submenu::with_id(app, "workspaces", "切换工作区", true)?;
+    if let Ok(registry) = app.state::<workspace::WorkspaceState>().registry.lock() {
+        let current = registry.current().id.clone();
+        for item in registry.workspaces() {
+            workspaces.append(&CheckMenuItem::with_id(
+                app,
+                format!("{}{}", WORKSPACE_MENU_PREFIX, item.id),
+                &item.name,
+                true,
+                item.id == current,
+                None::<&str>,
+            )?)?;
+        }
+    }
+
+    let auto_launch = CheckMenuItem::with_id(
+        app,
+        "auto_launch",
+        "开机自启",
+        true,
+        false,
+        None::<&str>,
+    )?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let quit = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
+
+    Menu::with_items(app, &[&show, &workspaces, &auto_launch, &separator, &quit])
+}
+
+/// 工作区增删改或切换后重建托盘菜单
+pub(crate) fn refresh_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => eprintln!("更新托盘菜单失败: {}", e),
+    }
+}
@@ -1,4 +0,0 @@
-pub mod project;
-pub mod settings;
-pub mod tag;
-pub mod task;
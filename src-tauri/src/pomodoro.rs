@@ -0,0 +1,142 @@
+use crate::AppState;
+use chrono::Utc;
+use ganle_core::models::pomodoro::{PomodoroPhase, PomodoroStatus};
+use ganle_core::services::pomodoro_service::{PhaseChange, Pomodoro};
+use ganle_core::TaskStore;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// 番茄钟推进与托盘倒计时的刷新间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 番茄钟状态。只保存在内存中，切换工作区时结束
+#[derive(Default)]
+pub struct PomodoroState(Mutex<Pomodoro>);
+
+impl PomodoroState {
+    fn with<T>(&self, f: impl FnOnce(&mut Pomodoro) -> T) -> T {
+        match self.0.lock() {
+            Ok(mut pomodoro) => f(&mut pomodoro),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+/// 当前状态
+pub fn status(app: &AppHandle) -> PomodoroStatus {
+    app.state::<PomodoroState>().with(|p| p.status(Utc::now()))
+}
+
+/// 开始一轮专注。关联任务时先结束正在进行的计时，避免同一段时间记两次
+pub fn start(app: &AppHandle, store: &TaskStore, task_id: Option<String>) -> Result<PomodoroStatus, String> {
+    if let Some(id) = &task_id {
+        store.task(id)?;
+        store.stop_timer()?;
+    }
+    let settings = store.pomodoro_settings();
+    app.state::<PomodoroState>()
+        .with(|p| p.start(settings, task_id, Utc::now()));
+    Ok(changed(app))
+}
+
+pub fn pause(app: &AppHandle) -> PomodoroStatus {
+    app.state::<PomodoroState>().with(|p| p.pause(Utc::now()));
+    changed(app)
+}
+
+pub fn resume(app: &AppHandle) -> PomodoroStatus {
+    app.state::<PomodoroState>().with(|p| p.resume(Utc::now()));
+    changed(app)
+}
+
+/// 跳过当前阶段（跳过的专注不记录）
+pub fn skip(app: &AppHandle) -> PomodoroStatus {
+    app.state::<PomodoroState>().with(|p| p.skip(Utc::now()));
+    changed(app)
+}
+
+pub fn stop(app: &AppHandle) -> PomodoroStatus {
+    app.state::<PomodoroState>().with(|p| p.stop());
+    changed(app)
+}
+
+/// 切换工作区时结束番茄钟（关联的任务属于原工作区）
+pub fn reset(app: &AppHandle) {
+    if app.state::<PomodoroState>().with(|p| p.phase()) != PomodoroPhase::Idle {
+        stop(app);
+    }
+}
+
+/// 通知前端并刷新托盘提示
+fn changed(app: &AppHandle) -> PomodoroStatus {
+    let status = status(app);
+    let _ = app.emit("pomodoro-changed", &status);
+    update_tooltip(app, &status);
+    status
+}
+
+/// 后台每秒推进番茄钟：阶段到时记录专注、发送通知，并在托盘提示中显示倒计时
+pub fn start_ticker(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(TICK_INTERVAL);
+        let state = app.state::<PomodoroState>();
+        let (change, status) = state.with(|p| {
+            let now = Utc::now();
+            (p.tick(now), p.status(now))
+        });
+        match change {
+            Some(change) => {
+                finish_phase(&app, &change);
+                changed(&app);
+            }
+            None if status.phase != PomodoroPhase::Idle => update_tooltip(&app, &status),
+            None => {}
+        }
+    });
+}
+
+/// 阶段结束：记录完成的专注并提醒进入下一阶段
+fn finish_phase(app: &AppHandle, change: &PhaseChange) {
+    let store = app.try_state::<AppState>().map(|state| state.store());
+    let mut title = None;
+    if let (Some(session), Some(store)) = (&change.session, &store) {
+        if let Err(e) = store.log_focus_session(session) {
+            eprintln!("记录番茄钟失败: {}", e);
+        }
+        title = session.task_id.as_deref().and_then(|id| store.task(id).ok()).map(|t| t.title);
+    }
+
+    let settings = store.map(|s| s.pomodoro_settings()).unwrap_or_default();
+    let (heading, body) = match change.next {
+        PomodoroPhase::ShortBreak => ("专注完成", format!("休息 {} 分钟", settings.short_break_minutes)),
+        PomodoroPhase::LongBreak => ("专注完成", format!("长休息 {} 分钟", settings.long_break_minutes)),
+        _ => ("休息结束", format!("开始专注 {} 分钟", settings.work_minutes)),
+    };
+    let body = match title {
+        Some(title) => format!("{}：{}", title, body),
+        None => body,
+    };
+    if let Err(e) = app.notification().builder().title(heading).body(body).show() {
+        eprintln!("发送通知失败: {}", e);
+    }
+}
+
+/// 托盘提示显示当前阶段与剩余时间
+fn update_tooltip(app: &AppHandle, status: &PomodoroStatus) {
+    let Some(tray) = app.tray_by_id(crate::TRAY_ID) else {
+        return;
+    };
+    let tooltip = match status.phase {
+        PomodoroPhase::Idle => "干了么".to_string(),
+        phase => format!(
+            "干了么 · {}{} {:02}:{:02}",
+            phase.label(),
+            if status.paused { "（已暂停）" } else { "" },
+            status.remaining_seconds / 60,
+            status.remaining_seconds % 60
+        ),
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+}
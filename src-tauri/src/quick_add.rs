@@ -0,0 +1,59 @@
+use crate::AppState;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+/// 快速添加窗口标签（见 tauri.conf.json）
+pub const WINDOW_LABEL: &str = "quick-add";
+
+/// 注册全局快捷键插件，并按设置绑定快捷键
+pub fn setup(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    app.plugin(
+        tauri_plugin_global_shortcut::Builder::new()
+            .with_handler(|app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    toggle(app);
+                }
+            })
+            .build(),
+    )?;
+
+    let shortcut = app.state::<AppState>().store().settings()?.quick_add_shortcut;
+    // 快捷键被其他程序占用时不影响启动
+    if let Err(e) = register(app, &shortcut) {
+        eprintln!("注册快捷键 {} 失败: {}", shortcut, e);
+    }
+    Ok(())
+}
+
+/// 替换当前快捷键
+pub fn register(app: &AppHandle, shortcut: &str) -> Result<(), String> {
+    let shortcut: Shortcut = shortcut
+        .parse()
+        .map_err(|_| format!("无法识别的快捷键: {}", shortcut))?;
+    let manager = app.global_shortcut();
+    manager.unregister_all().map_err(|e| e.to_string())?;
+    manager.register(shortcut).map_err(|e| e.to_string())
+}
+
+/// 显示或隐藏快速添加窗口
+pub fn toggle(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(WINDOW_LABEL) else {
+        return;
+    };
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.hide();
+        return;
+    }
+    let _ = window.center();
+    let _ = window.show();
+    let _ = window.set_focus();
+    // 通知前端清空输入框并聚焦
+    let _ = app.emit_to(WINDOW_LABEL, "quick-add-shown", ());
+}
+
+/// 隐藏快速添加窗口
+pub fn hide(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.hide();
+    }
+}
@@ -1,3 +0,0 @@
-pub mod reminder_service;
-pub mod repeat_service;
-pub mod task_service;
@@ -0,0 +1,119 @@
+use crate::{api, app_lock, quick_add, AppState};
+use ganle_core::db::connection;
+use ganle_core::models::workspace::Workspace;
+use ganle_core::services::workspace_service::WorkspaceRegistry;
+use ganle_core::TaskStore;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 工作区登记表
+pub struct WorkspaceState {
+    pub registry: Mutex<WorkspaceRegistry>,
+    /// 打开数据库（解锁、切换）时串行执行
+    pub opening: Mutex<()>,
+}
+
+impl WorkspaceState {
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        Ok(Self {
+            registry: Mutex::new(WorkspaceRegistry::load(dir)?),
+            opening: Mutex::new(()),
+        })
+    }
+
+    /// 当前工作区的数据库路径
+    pub fn current_path(&self) -> Result<PathBuf, String> {
+        let registry = self.registry.lock().map_err(|e| e.to_string())?;
+        Ok(registry.current_path())
+    }
+}
+
+/// 打开数据库，并把数据变更以事件推送给前端（task-created、project-deleted 等）
+pub fn open_store(app: &AppHandle, path: &Path, key: Option<&str>) -> Result<TaskStore, String> {
+    let mut store = TaskStore::open_with_key(path, key)?;
+    let handle = app.clone();
+    store.subscribe(move |event| {
+        let _ = handle.emit(event.name(), event);
+    });
+    Ok(store)
+}
+
+/// 切换到指定工作区：打开其数据库并替换 AppState 中的数据存储
+pub fn switch(app: &AppHandle, id: &str, passphrase: Option<&str>) -> Result<Workspace, String> {
+    let workspaces = app.state::<WorkspaceState>();
+    let _opening = workspaces.opening.lock().map_err(|e| e.to_string())?;
+
+    let (workspace, path, is_current) = {
+        let registry = workspaces.registry.lock().map_err(|e| e.to_string())?;
+        let workspace = registry.find(id)?.clone();
+        let path = registry.path_of(&workspace);
+        (workspace, path, registry.current().id == id)
+    };
+    let state = app.try_state::<AppState>();
+    if is_current && state.is_some() {
+        return Ok(workspace);
+    }
+
+    connection::apply_pending(&path)?;
+    if passphrase.is_none() && connection::is_encrypted(&path) {
+        return Err(format!("工作区“{}”已加密，需要输入密码", workspace.name));
+    }
+    let store = open_store(app, &path, passphrase)?;
+    let settings = store.settings()?;
+    workspaces
+        .registry
+        .lock()
+        .map_err(|e| e.to_string())?
+        .set_current(id)?;
+
+    match state {
+        Some(state) => {
+            state.replace_store(store);
+            // 应用锁按工作区设置：切换到设置了应用锁的工作区时需输入它的密码
+            state.lock.configure(settings.app_lock_enabled, settings.app_lock_minutes);
+            app_lock::lock(app);
+            if let Err(e) = api::apply_settings(app) {
+                eprintln!("启动 HTTP API 失败: {}", e);
+            }
+            if let Err(e) = quick_add::register(app, &settings.quick_add_shortcut) {
+                eprintln!("注册快捷键 {} 失败: {}", settings.quick_add_shortcut, e);
+            }
+        }
+        // 启动时的工作区尚未解锁，直接以新工作区启动
+        None => {
+            crate::start(app, store).map_err(|e| e.to_string())?;
+            app.state::<AppState>().lock.unlock();
+            crate::deep_link::replay(app);
+        }
+    }
+
+    crate::pomodoro::reset(app);
+    let _ = app.emit("workspace-switched", &workspace);
+    crate::refresh_tray_menu(app);
+    Ok(workspace)
+}
+
+/// 托盘菜单切换工作区。加密的工作区需要密码，交给主窗口提示输入
+pub fn switch_from_tray(app: &AppHandle, id: &str) {
+    let encrypted = app
+        .state::<WorkspaceState>()
+        .registry
+        .lock()
+        .ok()
+        .and_then(|registry| registry.find(id).map(|w| registry.path_of(w)).ok())
+        .is_some_and(|path| connection::is_encrypted(&path));
+
+    let result = if encrypted {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        app.emit("workspace-unlock-requested", id).map_err(|e| e.to_string())
+    } else {
+        switch(app, id, None).map(|_| ())
+    };
+    if let Err(e) = result {
+        eprintln!("切换工作区失败: {}", e);
+    }
+}
@@ -0,0 +1,5 @@
+import { createApp } from 'vue'
+import QuickAdd from './QuickAdd.vue'
+import './styles/main.css'
+
+createApp(QuickAdd).mount('#app')
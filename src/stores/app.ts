@@ -1,7 +1,8 @@
 import { defineStore } from 'pinia'
 import { ref, computed } from 'vue'
-import type { Task, Project, Tag, CurrentView, SmartListType, RepeatType, SubTask } from '@/types'
+import type { Task, Project, Tag, CurrentView, SmartListType, RepeatType, SubTask, TaskEvent, MaintenanceReport, DatabaseStatus, LockStatus, Workspace, SetupStatus, SeedChoice, TimeEntry, TimeTotals, PomodoroStatus, PomodoroSettings, StatsPeriod, CompletionPoint, StatsSummary } from '@/types'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { sendNotification } from '@tauri-apps/plugin-notification'
 
 // 全局时间（每分钟更新，用于倒计时显示）
@@ -21,6 +22,32 @@ function startGlobalTimer() {
 // 启动全局定时器
 startGlobalTimer()
 
+// 按 ID 插入或替换（本地操作与后端事件都可能先到，需保持幂等）
+function upsert<T extends { id: string }>(list: T[], item: T, prepend = false) {
+  const index = list.findIndex(x => x.id === item.id)
+  if (index !== -1) {
+    list[index] = item
+  } else if (prepend) {
+    list.unshift(item)
+  } else {
+    list.push(item)
+  }
+}
+
+// 某一时刻在指定时区下的日期（YYYY-MM-DD）
+export function dateInTimezone(date: Date, timeZone: string): string {
+  return new Intl.DateTimeFormat('en-CA', { timeZone, year: 'numeric', month: '2-digit', day: '2-digit' }).format(date)
+}
+
+// 秒数显示为“1小时5分”“12分”
+export function formatDuration(seconds: number): string {
+  const minutes = Math.floor(seconds / 60)
+  const h = Math.floor(minutes / 60)
+  const m = minutes % 60
+  if (h === 0) return `${m}分`
+  return m === 0 ? `${h}小时` : `${h}小时${m}分`
+}
+
 export const useAppStore = defineStore('app', () => {
   // 主题状态
   const isDark = ref(false)
@@ -46,27 +73,298 @@ export const useAppStore = defineStore('app', () => {
   // 标签列表
   const tags = ref<Tag[]>([])
 
+  // 数据库加密状态（加密时需先输入密码才能加载数据）
+  const databaseStatus = ref<DatabaseStatus>({ supported: false, encrypted: false, unlocked: true })
+
+  // 应用锁状态（锁定期间后端拒绝返回数据）
+  const lockStatus = ref<LockStatus>({ enabled: false, locked: false, idleMinutes: 5 })
+
+  // 初始数据状态（新数据库需先选择空白开始、示例数据或模板）
+  const setupStatus = ref<SetupStatus>({ needsSetup: false, hasSampleData: false })
+
+  // 工作区列表
+  const workspaces = ref<Workspace[]>([])
+
+  // 托盘请求切换到加密的工作区，等待输入密码
+  const workspaceUnlockRequest = ref<string | null>(null)
+
+  // 用户时区（IANA 名称），任务的 dueDate 已按它换算
+  const timezone = ref(Intl.DateTimeFormat().resolvedOptions().timeZone)
+
+  // 智能列表是否显示开始日期未到的任务
+  const showDeferred = ref(false)
+
+  // 开始日期还没到（只出现在“已计划”中）
+  function isDeferred(task: Task) {
+    return !!task.startDate && task.startDate > dateInTimezone(new Date(), timezone.value)
+  }
+
+  // 按设置隐藏开始日期未到的任务
+  function visibleInSmartLists(task: Task) {
+    return showDeferred.value || !isDeferred(task)
+  }
+
+  // 正在进行的计时（保存在数据库中，重启后继续）
+  const runningTimer = ref<TimeEntry | null>(null)
+
+  // 计时期间每秒更新，用于显示已计时长
+  const timerNow = ref(Date.now())
+  let timerTick: ReturnType<typeof setInterval> | null = null
+
+  function setRunningTimer(entry: TimeEntry | null) {
+    runningTimer.value = entry
+    if (entry && !timerTick) {
+      timerNow.value = Date.now()
+      timerTick = setInterval(() => (timerNow.value = Date.now()), 1000)
+    } else if (!entry && timerTick) {
+      clearInterval(timerTick)
+      timerTick = null
+    }
+  }
+
+  // 番茄钟状态（由后端推进，阶段变化时推送），剩余时间按结束时间在本地倒数
+  const pomodoro = ref<PomodoroStatus | null>(null)
+  const pomodoroSettings = ref<PomodoroSettings>({ workMinutes: 25, shortBreakMinutes: 5, longBreakMinutes: 15, longBreakEvery: 4 })
+  const pomodoroNow = ref(Date.now())
+  let pomodoroTick: ReturnType<typeof setInterval> | null = null
+
+  function setPomodoro(status: PomodoroStatus) {
+    pomodoro.value = status
+    pomodoroNow.value = Date.now()
+    if (status.endsAt && !pomodoroTick) {
+      pomodoroTick = setInterval(() => (pomodoroNow.value = Date.now()), 1000)
+    } else if (!status.endsAt && pomodoroTick) {
+      clearInterval(pomodoroTick)
+      pomodoroTick = null
+    }
+  }
+
+  const pomodoroRemaining = computed(() => {
+    const status = pomodoro.value
+    if (!status) return 0
+    if (!status.endsAt) return status.remainingSeconds
+    return Math.max(0, Math.ceil((Date.parse(status.endsAt) - pomodoroNow.value) / 1000))
+  })
+
+  // 本次计时已进行的秒数
+  const runningSeconds = computed(() => {
+    if (!runningTimer.value) return 0
+    return Math.max(0, Math.floor((timerNow.value - Date.parse(runningTimer.value.startedAt)) / 1000))
+  })
+
+  // 任务的总用时，正在计时的任务加上本次已进行的部分
+  function trackedSeconds(task: Task) {
+    const running = runningTimer.value?.taskId === task.id ? runningSeconds.value : 0
+    return task.trackedSeconds + running
+  }
+
+  // 需要展开并滚动到的任务（由 ganleme:// 链接触发）
+  const focusedTaskId = ref<string | null>(null)
+
   // 从 Rust 后端加载所有数据
   async function initFromBackend() {
     try {
-      const [taskList, projectList, tagList, settings] = await Promise.all([
+      const [taskList, projectList, tagList, settings, setup, timer, pomodoroStatus] = await Promise.all([
         invoke<Task[]>('get_all_tasks'),
         invoke<Project[]>('get_all_projects'),
         invoke<Tag[]>('get_all_tags'),
-        invoke<{ isDark: boolean }>('get_settings'),
+        invoke<{ isDark: boolean; timezone: string; showDeferred: boolean; pomodoro: PomodoroSettings }>('get_settings'),
+        invoke<SetupStatus>('get_setup_status'),
+        invoke<TimeEntry | null>('get_running_timer'),
+        invoke<PomodoroStatus>('get_pomodoro'),
       ])
+      setupStatus.value = setup
+      setRunningTimer(timer)
+      setPomodoro(pomodoroStatus)
+      pomodoroSettings.value = settings.pomodoro
       tasks.value = taskList
       projects.value = projectList
       tags.value = tagList
       isDark.value = settings.isDark
+      timezone.value = settings.timezone
+      showDeferred.value = settings.showDeferred
       document.documentElement.classList.toggle('dark', settings.isDark)
     } catch (e) {
       console.error('从后端加载数据失败:', e)
     }
   }
 
+  // 跳转到指定任务：切到全部任务视图并清空搜索，保证任务可见
+  async function navigateToTask(taskId: string) {
+    await initFromBackend()
+    if (!tasks.value.some(t => t.id === taskId)) return
+    searchQuery.value = ''
+    currentView.value = { type: 'smart', id: 'all' }
+    focusedTaskId.value = taskId
+  }
+
+  // 处理链接唤起的跳转（冷启动时事件可能早于前端就绪，从后端取暂存的目标）
+  async function takePendingNavigation() {
+    const taskId = await invoke<string | null>('take_pending_navigation')
+    if (taskId) await navigateToTask(taskId)
+  }
+
+  // 数据库已打开才加载数据，否则等待解锁
+  async function loadDatabaseStatus() {
+    try {
+      databaseStatus.value = await invoke<DatabaseStatus>('get_database_status')
+    } catch (e) {
+      console.error('获取数据库状态失败:', e)
+    }
+  }
+
+  async function loadLockStatus() {
+    try {
+      lockStatus.value = await invoke<LockStatus>('get_lock_status')
+    } catch (e) {
+      console.error('获取应用锁状态失败:', e)
+    }
+  }
+
+  // ========== 数据库加密 ==========
+  async function unlockDatabase(passphrase: string) {
+    await invoke('unlock_database', { passphrase })
+    databaseStatus.value.unlocked = true
+    await loadLockStatus()
+    await initFromBackend()
+    await takePendingNavigation()
+  }
+
+  // 开启或关闭加密后应用会自动重启
+  async function enableEncryption(passphrase: string) {
+    await invoke('enable_encryption', { passphrase })
+  }
+
+  async function disableEncryption(passphrase: string) {
+    await invoke('disable_encryption', { passphrase })
+  }
+
+  // ========== 应用锁 ==========
+  async function unlockApp(pin: string) {
+    await invoke('unlock_app', { pin })
+    lockStatus.value.locked = false
+    await initFromBackend()
+    await takePendingNavigation()
+  }
+
+  async function lockApp() {
+    await invoke('lock_app')
+  }
+
+  // pin 为 null 表示取消应用锁；已设置时需提供当前密码
+  async function setAppLock(pin: string | null, currentPin: string | null) {
+    lockStatus.value = await invoke<LockStatus>('set_app_lock', { pin, currentPin })
+  }
+
+  async function setAppLockMinutes(minutes: number) {
+    lockStatus.value = await invoke<LockStatus>('set_app_lock_minutes', { minutes })
+  }
+
+  // 报告用户活动（节流，避免每次鼠标移动都调用后端）
+  let lastActivityReport = 0
+  function reportActivity() {
+    if (!lockStatus.value.enabled || lockStatus.value.locked) return
+    const now = Date.now()
+    if (now - lastActivityReport < 30000) return
+    lastActivityReport = now
+    invoke('report_activity').catch(() => {})
+  }
+
+  // ========== 工作区 ==========
+  const currentWorkspace = computed(() => workspaces.value.find(w => w.current) ?? null)
+
+  async function loadWorkspaces() {
+    try {
+      workspaces.value = await invoke<Workspace[]>('list_workspaces')
+    } catch (e) {
+      console.error('获取工作区失败:', e)
+    }
+  }
+
+  async function createWorkspace(name: string) {
+    await invoke<Workspace>('create_workspace', { name })
+    await loadWorkspaces()
+  }
+
+  async function renameWorkspace(id: string, name: string) {
+    await invoke<Workspace>('rename_workspace', { id, name })
+    await loadWorkspaces()
+  }
+
+  // 加密的工作区需提供密码，切换完成后由 workspace-switched 事件重新加载数据
+  async function switchWorkspace(id: string, passphrase: string | null = null) {
+    await invoke<Workspace>('switch_workspace', { id, passphrase })
+    workspaceUnlockRequest.value = null
+  }
+
+  async function deleteWorkspace(id: string) {
+    await invoke('delete_workspace', { id })
+    await loadWorkspaces()
+  }
+
+  function clearData() {
+    tasks.value = []
+    projects.value = []
+    tags.value = []
+    pendingReminder.value = null
+    setRunningTimer(null)
+  }
+
+  // 锁定后清空内存中的数据，解锁后重新加载
+  listen('app-locked', () => {
+    lockStatus.value.locked = true
+    clearData()
+  })
+
+  // 切换工作区后回到默认视图，并按新工作区的状态重新加载
+  listen('workspace-switched', async () => {
+    clearData()
+    currentView.value = { type: 'smart', id: 'today' }
+    await loadWorkspaces()
+    await loadDatabaseStatus()
+    if (!databaseStatus.value.unlocked) return
+    await loadLockStatus()
+    if (!lockStatus.value.locked) await initFromBackend()
+  })
+
+  listen<string>('workspace-unlock-requested', e => {
+    workspaceUnlockRequest.value = e.payload
+  })
+
   // 初始化
-  initFromBackend()
+  loadWorkspaces()
+  loadDatabaseStatus().then(async () => {
+    if (!databaseStatus.value.unlocked) return
+    await loadLockStatus()
+    if (!lockStatus.value.locked) initFromBackend().then(takePendingNavigation)
+  })
+
+  // todo.txt 文件同步修改了数据库时重新加载
+  listen('todotxt-synced', () => initFromBackend())
+
+  // 后端数据变更事件：增量更新本地状态（快速添加、重复任务、HTTP API 等来源的修改都会推送）
+  listen<Task>('task-created', e => upsert(tasks.value, e.payload, true))
+  listen<Task>('task-updated', e => upsert(tasks.value, e.payload))
+  listen<{ id: string }>('task-deleted', e => {
+    tasks.value = tasks.value.filter(t => t.id !== e.payload.id)
+    // 删除任务时其计时记录一并删除
+    if (runningTimer.value?.taskId === e.payload.id) setRunningTimer(null)
+  })
+  listen<TimeEntry | null>('timer-changed', e => setRunningTimer(e.payload))
+  listen<PomodoroStatus>('pomodoro-changed', e => setPomodoro(e.payload))
+  listen<Project>('project-created', e => upsert(projects.value, e.payload))
+  listen<Project>('project-updated', e => upsert(projects.value, e.payload))
+  listen<{ id: string }>('project-deleted', e => {
+    projects.value = projects.value.filter(p => p.id !== e.payload.id)
+  })
+  listen<Tag>('tag-created', e => upsert(tags.value, e.payload))
+  listen<Tag>('tag-updated', e => upsert(tags.value, e.payload))
+  listen<{ id: string }>('tag-deleted', e => {
+    tags.value = tags.value.filter(t => t.id !== e.payload.id)
+  })
+
+  // ganleme:// 链接打开或新建了任务
+  listen('navigate-task', () => takePendingNavigation())
 
   // 计算属性 - 根据当前视图过滤任务
   const filteredTasks = computed(() => {
@@ -84,7 +382,10 @@ export const useAppStore = defineStore('app', () => {
 
     const view = currentView.value
     if (view.type === 'smart') {
-      const today = new Date().toISOString().split('T')[0]
+      const today = dateInTimezone(new Date(), timezone.value)
+      if (view.id !== 'scheduled' && view.id !== 'completed') {
+        result = result.filter(visibleInSmartLists)
+      }
       switch (view.id as SmartListType) {
         case 'today':
           // 支持 YYYY-MM-DD 和 YYYY-MM-DDTHH:mm:ss 两种格式
@@ -93,7 +394,7 @@ export const useAppStore = defineStore('app', () => {
         case 'week': {
           const weekLater = new Date()
           weekLater.setDate(weekLater.getDate() + 7)
-          const weekStr = weekLater.toISOString().split('T')[0]
+          const weekStr = dateInTimezone(weekLater, timezone.value)
           // 取日期部分进行比较
           result = result.filter(t => {
             if (!t.dueDate) return false
@@ -110,6 +411,11 @@ export const useAppStore = defineStore('app', () => {
         case 'favorite':
           result = result.filter(t => t.favorite)
           break
+        case 'scheduled':
+          result = result
+            .filter(t => t.status !== 'completed' && isDeferred(t))
+            .sort((a, b) => a.startDate!.localeCompare(b.startDate!))
+          break
       }
     } else if (view.type === 'project') {
       result = result.filter(t => t.projectId === view.id)
@@ -130,6 +436,7 @@ export const useAppStore = defineStore('app', () => {
         all: '全部任务',
         completed: '已完成',
         favorite: '收藏任务',
+        scheduled: '已计划',
       }
       return titles[view.id] || '任务列表'
     } else if (view.type === 'project') {
@@ -144,26 +451,39 @@ export const useAppStore = defineStore('app', () => {
 
   // 统计数据
   const todayCount = computed(() => {
-    const today = new Date().toISOString().split('T')[0]
+    const today = dateInTimezone(new Date(), timezone.value)
     // 支持 YYYY-MM-DD 和 YYYY-MM-DDTHH:mm:ss 两种格式
-    return tasks.value.filter(t => t.dueDate && t.dueDate.startsWith(today) && t.status !== 'completed').length
+    return tasks.value.filter(
+      t => t.dueDate && t.dueDate.startsWith(today) && t.status !== 'completed' && visibleInSmartLists(t)
+    ).length
   })
 
   const weekCount = computed(() => {
     const weekLater = new Date()
     weekLater.setDate(weekLater.getDate() + 7)
-    const weekStr = weekLater.toISOString().split('T')[0]
+    const weekStr = dateInTimezone(weekLater, timezone.value)
     // 取日期部分进行比较
     return tasks.value.filter(t => {
-      if (!t.dueDate || t.status === 'completed') return false
+      if (!t.dueDate || t.status === 'completed' || !visibleInSmartLists(t)) return false
       const taskDate = t.dueDate.split('T')[0]
       return taskDate <= weekStr
     }).length
   })
 
-  const allCount = computed(() => tasks.value.filter(t => t.status !== 'completed').length)
+  const allCount = computed(() => tasks.value.filter(t => t.status !== 'completed' && visibleInSmartLists(t)).length)
   const completedCount = computed(() => tasks.value.filter(t => t.status === 'completed').length)
-  const favoriteCount = computed(() => tasks.value.filter(t => t.favorite && t.status !== 'completed').length)
+  const favoriteCount = computed(() =>
+    tasks.value.filter(t => t.favorite && t.status !== 'completed' && visibleInSmartLists(t)).length
+  )
+  const scheduledCount = computed(() => tasks.value.filter(t => t.status !== 'completed' && isDeferred(t)).length)
+
+  // 今天到期的未完成任务的预估用时合计（分钟）
+  const todayEstimateMinutes = computed(() => {
+    const today = dateInTimezone(new Date(), timezone.value)
+    return tasks.value
+      .filter(t => t.dueDate && t.dueDate.startsWith(today) && t.status !== 'completed' && visibleInSmartLists(t))
+      .reduce((sum, t) => sum + (t.estimateMinutes ?? 0), 0)
+  })
 
   // ========== 基础操作 ==========
   async function toggleTheme() {
@@ -172,6 +492,17 @@ export const useAppStore = defineStore('app', () => {
     await invoke('update_settings', { key: 'isDark', value: String(isDark.value) })
   }
 
+  async function toggleShowDeferred() {
+    showDeferred.value = !showDeferred.value
+    await invoke('update_settings', { key: 'show_deferred', value: String(showDeferred.value) })
+  }
+
+  // 修改时区后重新加载任务，使 dueDate 按新时区显示；空字符串表示跟随系统
+  async function setTimezone(name: string) {
+    await invoke('update_settings', { key: 'timezone', value: name })
+    await initFromBackend()
+  }
+
   function toggleSidebar() {
     sidebarCollapsed.value = !sidebarCollapsed.value
   }
@@ -200,33 +531,35 @@ export const useAppStore = defineStore('app', () => {
       tagId: taskTagId,
       repeat,
     })
-    tasks.value.unshift(newTask)
+    upsert(tasks.value, newTask, true)
+  }
+
+  // 设置开始日期（YYYY-MM-DD），null 表示清除（后端以空字符串表示清除）
+  async function setStartDate(taskId: string, startDate: string | null) {
+    const updatedTask = await invoke<Task>('update_task', { id: taskId, startDate: startDate ?? '' })
+    upsert(tasks.value, updatedTask)
+  }
+
+  // 设置预估用时（分钟），null 表示清除（后端以 0 表示清除）
+  async function setEstimate(taskId: string, minutes: number | null) {
+    const updatedTask = await invoke<Task>('update_task', { id: taskId, estimateMinutes: minutes ?? 0 })
+    upsert(tasks.value, updatedTask)
   }
 
   async function updateTask(taskId: string, updates: Partial<Task>) {
     const updatedTask = await invoke<Task>('update_task', { id: taskId, updates })
-    const index = tasks.value.findIndex(t => t.id === taskId)
-    if (index !== -1) {
-      tasks.value[index] = updatedTask
-    }
+    upsert(tasks.value, updatedTask)
   }
 
+  // 生成的下一个重复任务由 task-created 事件送达
   async function toggleTask(taskId: string) {
     const updatedTask = await invoke<Task>('toggle_task_status', { id: taskId })
-    const index = tasks.value.findIndex(t => t.id === taskId)
-    if (index !== -1) {
-      tasks.value[index] = updatedTask
-    }
-    // 重新加载任务列表（可能生成了重复任务）
-    tasks.value = await invoke<Task[]>('get_all_tasks')
+    upsert(tasks.value, updatedTask)
   }
 
   async function toggleFavorite(taskId: string) {
     const updatedTask = await invoke<Task>('toggle_favorite', { id: taskId })
-    const index = tasks.value.findIndex(t => t.id === taskId)
-    if (index !== -1) {
-      tasks.value[index] = updatedTask
-    }
+    upsert(tasks.value, updatedTask)
   }
 
   async function deleteTask(taskId: string) {
@@ -235,57 +568,137 @@ export const useAppStore = defineStore('app', () => {
   }
 
   // ========== 子任务操作 ==========
+  // 子任务变化后所属任务通过 task-updated 事件刷新
   async function addSubtask(taskId: string, title: string) {
     await invoke('add_subtask', { taskId, title })
-    // 重新加载该任务
-    const allTasks = await invoke<Task[]>('get_all_tasks')
-    tasks.value = allTasks
   }
 
   async function toggleSubtask(taskId: string, subtaskId: string) {
     await invoke('toggle_subtask', { taskId, subtaskId })
-    const allTasks = await invoke<Task[]>('get_all_tasks')
-    tasks.value = allTasks
   }
 
   async function deleteSubtask(taskId: string, subtaskId: string) {
     await invoke('delete_subtask', { taskId, subtaskId })
-    const allTasks = await invoke<Task[]>('get_all_tasks')
-    tasks.value = allTasks
   }
 
   async function updateSubtask(taskId: string, subtaskId: string, title: string) {
     await invoke('update_subtask', { taskId, subtaskId, title })
-    const allTasks = await invoke<Task[]>('get_all_tasks')
-    tasks.value = allTasks
   }
 
   // ========== 重复任务 ==========
   async function setTaskRepeat(taskId: string, repeat: RepeatType) {
-    await invoke('update_task', { id: taskId, updates: { repeat } })
-    const allTasks = await invoke<Task[]>('get_all_tasks')
-    tasks.value = allTasks
+    const updatedTask = await invoke<Task>('update_task', { id: taskId, updates: { repeat } })
+    upsert(tasks.value, updatedTask)
+  }
+
+  // ========== 计时 ==========
+  // 开始计时会结束其他任务的计时，被结束任务的用时由 task-updated 事件送达
+  async function startTimer(taskId: string) {
+    setRunningTimer(await invoke<TimeEntry>('start_timer', { taskId }))
+  }
+
+  async function stopTimer() {
+    await invoke<TimeEntry | null>('stop_timer')
+    setRunningTimer(null)
+  }
+
+  async function loadTimeTotals() {
+    return invoke<TimeTotals>('get_time_totals')
+  }
+
+  // ========== 统计 ==========
+  async function loadCompletionSeries(period: StatsPeriod, count: number) {
+    return invoke<CompletionPoint[]>('get_completion_series', { period, count })
+  }
+
+  async function loadStatsSummary() {
+    return invoke<StatsSummary>('get_stats_summary')
+  }
+
+  // ========== 番茄钟 ==========
+  // 关联任务时，完成的专注记入该任务的用时
+  async function startPomodoro(taskId: string | null = null) {
+    setPomodoro(await invoke<PomodoroStatus>('start_pomodoro', { taskId }))
+  }
+
+  async function pausePomodoro() {
+    setPomodoro(await invoke<PomodoroStatus>('pause_pomodoro'))
+  }
+
+  async function resumePomodoro() {
+    setPomodoro(await invoke<PomodoroStatus>('resume_pomodoro'))
+  }
+
+  async function skipPomodoro() {
+    setPomodoro(await invoke<PomodoroStatus>('skip_pomodoro'))
+  }
+
+  async function stopPomodoro() {
+    setPomodoro(await invoke<PomodoroStatus>('stop_pomodoro'))
+  }
+
+  // 修改后从下一轮专注开始生效
+  async function setPomodoroSetting(key: keyof PomodoroSettings, value: number) {
+    const keys: Record<keyof PomodoroSettings, string> = {
+      workMinutes: 'pomodoro_work_minutes',
+      shortBreakMinutes: 'pomodoro_short_break_minutes',
+      longBreakMinutes: 'pomodoro_long_break_minutes',
+      longBreakEvery: 'pomodoro_long_break_every',
+    }
+    await invoke('update_settings', { key: keys[key], value: String(value) })
+    pomodoroSettings.value = { ...pomodoroSettings.value, [key]: value }
   }
 
   // ========== 标签操作 ==========
   async function addTag(name: string, color: string) {
     const newTag = await invoke<Tag>('create_tag', { name, color })
-    tags.value.push(newTag)
+    upsert(tags.value, newTag)
   }
 
   async function updateTag(tagId: string, updates: Partial<Tag>) {
     const updatedTag = await invoke<Tag>('update_tag', { id: tagId, updates })
-    const index = tags.value.findIndex(t => t.id === tagId)
-    if (index !== -1) {
-      tags.value[index] = updatedTag
-    }
+    upsert(tags.value, updatedTag)
   }
 
+  // 受影响任务的标签变化由 task-updated 事件送达
   async function deleteTag(tagId: string) {
     await invoke('delete_tag', { id: tagId })
     tags.value = tags.value.filter(t => t.id !== tagId)
-    // 重新加载任务（后端已处理标签关联删除）
-    tasks.value = await invoke<Task[]>('get_all_tasks')
+  }
+
+  // ========== 变更记录 ==========
+  async function getTaskHistory(taskId: string) {
+    return invoke<TaskEvent[]>('get_task_history', { id: taskId })
+  }
+
+  // ========== 撤销与重做 ==========
+  // 变化由后端事件推送，这里只需调用命令
+  async function undo() {
+    return invoke<string | null>('undo')
+  }
+
+  async function redo() {
+    return invoke<string | null>('redo')
+  }
+
+  // ========== 数据库维护 ==========
+  // 修复涉及的任务由后端事件推送
+  async function runMaintenance() {
+    return invoke<MaintenanceReport>('run_maintenance')
+  }
+
+  // ========== 初始数据 ==========
+  // 新增的数据由后端事件推送到本地状态
+  async function seedDatabase(choice: SeedChoice) {
+    await invoke('seed_database', { choice })
+    setupStatus.value = await invoke<SetupStatus>('get_setup_status')
+  }
+
+  // 返回删除的示例任务数
+  async function removeSampleData() {
+    const removed = await invoke<number>('remove_sample_data')
+    setupStatus.value.hasSampleData = false
+    return removed
   }
 
   // ========== 排序操作 ==========
@@ -375,6 +788,8 @@ export const useAppStore = defineStore('app', () => {
   return {
     // 状态
     isDark,
+    timezone,
+    showDeferred,
     isMinimalMode,
     sidebarCollapsed,
     currentView,
@@ -382,8 +797,19 @@ export const useAppStore = defineStore('app', () => {
     tasks,
     projects,
     tags,
+    focusedTaskId,
+    databaseStatus,
+    lockStatus,
+    setupStatus,
+    workspaces,
+    currentWorkspace,
+    workspaceUnlockRequest,
     globalNow,
     pendingReminder,
+    runningTimer,
+    pomodoro,
+    pomodoroSettings,
+    pomodoroRemaining,
     // 计算属性
     filteredTasks,
     currentViewTitle,
@@ -392,8 +818,12 @@ export const useAppStore = defineStore('app', () => {
     allCount,
     completedCount,
     favoriteCount,
+    scheduledCount,
+    todayEstimateMinutes,
     // 基础方法
     toggleTheme,
+    setTimezone,
+    toggleShowDeferred,
     toggleSidebar,
     toggleMinimalMode,
     setView,
@@ -401,9 +831,27 @@ export const useAppStore = defineStore('app', () => {
     // 任务方法
     addTask,
     updateTask,
+    setStartDate,
+    isDeferred,
     toggleTask,
     toggleFavorite,
     deleteTask,
+    // 计时
+    setEstimate,
+    trackedSeconds,
+    startTimer,
+    stopTimer,
+    loadTimeTotals,
+    // 统计
+    loadCompletionSeries,
+    loadStatsSummary,
+    // 番茄钟
+    startPomodoro,
+    pausePomodoro,
+    resumePomodoro,
+    skipPomodoro,
+    stopPomodoro,
+    setPomodoroSetting,
     // 子任务方法
     addSubtask,
     toggleSubtask,
@@ -415,6 +863,32 @@ export const useAppStore = defineStore('app', () => {
     addTag,
     updateTag,
     deleteTag,
+    // 变更记录
+    getTaskHistory,
+    // 撤销与重做
+    undo,
+    redo,
+    // 数据库维护
+    runMaintenance,
+    // 初始数据
+    seedDatabase,
+    removeSampleData,
+    // 数据库加密
+    unlockDatabase,
+    enableEncryption,
+    disableEncryption,
+    // 工作区
+    loadWorkspaces,
+    createWorkspace,
+    renameWorkspace,
+    switchWorkspace,
+    deleteWorkspace,
+    // 应用锁
+    unlockApp,
+    lockApp,
+    setAppLock,
+    setAppLockMinutes,
+    reportActivity,
     // 排序方法
     reorderTasks,
     reorderByIndex,
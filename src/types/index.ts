@@ -22,6 +22,11 @@ export interface SubTask {
 }
 
 // 任务
+// 截止时间：全天日期，或带时区的具体时刻（instant 为 UTC）
+export type Due =
+  | { kind: 'allDay'; date: string }
+  | { kind: 'at'; instant: string; tz: string }
+
 export interface Task {
   id: string
   title: string
@@ -31,7 +36,11 @@ export interface Task {
   projectId: string | null
   tags: string[]       // 标签ID列表
   subtasks: SubTask[]
-  dueDate: string | null
+  due: Due | null
+  dueDate: string | null  // 用户时区下的显示形式（YYYY-MM-DD 或 YYYY-MM-DDTHH:mm）
+  startDate: string | null  // 开始日期（YYYY-MM-DD），之前不出现在智能列表中
+  estimateMinutes: number | null  // 预估用时（分钟）
+  trackedSeconds: number  // 已结束的计时合计（秒），不含正在进行的计时
   reminder: string | null
   repeat: RepeatType
   favorite: boolean
@@ -40,6 +49,141 @@ export interface Task {
   completedAt: string | null
 }
 
+// 任务变更记录
+export interface TaskEvent {
+  id: number
+  taskId: string
+  kind: string
+  field: string | null
+  oldValue: string | null
+  newValue: string | null
+  createdAt: string
+}
+
+// 数据库加密状态
+export interface DatabaseStatus {
+  supported: boolean
+  encrypted: boolean
+  unlocked: boolean
+}
+
+// 初始数据状态
+export interface SetupStatus {
+  needsSetup: boolean
+  hasSampleData: boolean
+}
+
+// 首次启动的初始数据选择
+export type SeedChoice =
+  | { kind: 'empty' }
+  | { kind: 'sample' }
+  | { kind: 'template'; content: string }
+
+// 工作区（每个工作区使用独立的数据库）
+export interface Workspace {
+  id: string
+  name: string
+  createdAt: string
+  current: boolean
+  encrypted: boolean
+}
+
+// 应用锁状态
+export interface LockStatus {
+  enabled: boolean
+  locked: boolean
+  idleMinutes: number
+}
+
+// 计时记录（endedAt 为空表示正在计时）
+export interface TimeEntry {
+  id: number
+  taskId: string
+  startedAt: string
+  endedAt: string | null
+  seconds: number | null
+  source: 'timer' | 'pomodoro'  // 手动计时或完成的番茄钟
+}
+
+// 统计
+export type StatsPeriod = 'day' | 'week' | 'month'
+
+export interface CompletionPoint {
+  start: string  // 周期第一天（YYYY-MM-DD），周从周一开始
+  completed: number
+}
+
+export interface Distribution {
+  id: string | null
+  name: string
+  total: number
+  completed: number
+}
+
+export interface StatsSummary {
+  total: number
+  completed: number
+  completionRate: number  // 0 到 1
+  currentStreak: number
+  longestStreak: number
+  averageLeadHours: number | null  // 从创建到完成的平均小时数
+  overdue: number
+  overdueRate: number  // 逾期任务占已到期任务的比例
+  byTag: Distribution[]
+  byProject: Distribution[]
+  byPriority: Distribution[]
+}
+
+// 番茄钟
+export type PomodoroPhase = 'idle' | 'work' | 'shortBreak' | 'longBreak'
+
+export interface PomodoroSettings {
+  workMinutes: number
+  shortBreakMinutes: number
+  longBreakMinutes: number
+  longBreakEvery: number  // 每完成几轮专注后长休息一次
+}
+
+export interface PomodoroStatus {
+  phase: PomodoroPhase
+  taskId: string | null
+  round: number  // 本组已完成的专注轮数
+  remainingSeconds: number
+  endsAt: string | null  // 当前阶段结束时间（UTC），暂停或未开始时为空
+  paused: boolean
+  settings: PomodoroSettings
+}
+
+// 预估与实际用时汇总
+export interface TimeTotal {
+  id: string | null
+  name: string
+  estimateMinutes: number
+  trackedSeconds: number
+}
+
+export interface TimeTotals {
+  tasks: TimeTotal[]
+  projects: TimeTotal[]
+  tags: TimeTotal[]
+}
+
+// 数据库维护结果
+export interface MaintenanceReport {
+  integrityErrors: string[]
+  foreignKeyErrors: string[]
+  repaired: {
+    taskTags: number
+    subtasks: number
+    taskEvents: number
+    taskProjects: number
+    timeEntries: number
+  }
+  sizeBefore: number
+  sizeAfter: number
+  checkedAt: string
+}
+
 // 项目
 export interface Project {
   id: string
@@ -51,7 +195,7 @@ export interface Project {
 }
 
 // 智能清单类型
-export type SmartListType = 'today' | 'week' | 'all' | 'completed' | 'favorite'
+export type SmartListType = 'today' | 'week' | 'all' | 'completed' | 'favorite' | 'scheduled'
 
 // 当前视图
 export interface CurrentView {
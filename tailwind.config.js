@@ -2,6 +2,7 @@
 module.exports = {
   content: [
     "./index.html",
+    "./quick-add.html",
     "./src/**/*.{vue,js,ts,jsx,tsx}",
   ],
   darkMode: 'class',
@@ -10,6 +10,15 @@ export default defineConfig({
     strictPort: true,
   },
   plugins: [vue()],
+  // 主窗口与快速添加窗口各自一个入口
+  build: {
+    rollupOptions: {
+      input: {
+        main: resolve(__dirname, 'index.html'),
+        quickAdd: resolve(__dirname, 'quick-add.html'),
+      },
+    },
+  },
   resolve: {
     alias: {
       '@': resolve(__dirname, 'src'),